@@ -3653,32 +3653,32 @@ impl BULLET_PARAM_ST {
 
     #[allow(clippy::identity_op)]
     pub fn emitte_pos_type(&self) -> u8 {
-        (self.bits_9a >> 1) & 0b00000111
+        (self.bits_9a >> 3) & 0b00000111
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_emitte_pos_type(&mut self, value: u8) {
-        self.bits_9a = (self.bits_9a & !(0b00000111 << 1)) | ((value & 0b00000111) << 1);
+        self.bits_9a = (self.bits_9a & !(0b00000111 << 3)) | ((value & 0b00000111) << 3);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_attack_sfx(&self) -> u8 {
-        (self.bits_9a >> 2) & 0b00000001
+        (self.bits_9a >> 6) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_attack_sfx(&mut self, value: u8) {
-        self.bits_9a = (self.bits_9a & !(0b00000001 << 2)) | ((value & 0b00000001) << 2);
+        self.bits_9a = (self.bits_9a & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_endless_hit(&self) -> u8 {
-        (self.bits_9a >> 3) & 0b00000001
+        (self.bits_9a >> 7) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_endless_hit(&mut self, value: u8) {
-        self.bits_9a = (self.bits_9a & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+        self.bits_9a = (self.bits_9a & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
     }
 
     #[allow(clippy::identity_op)]
@@ -6004,10 +6004,7 @@ pub struct DECAL_PARAM_ST {
     near_size: f32,
     far_size: f32,
     mask_speffect_id: i32,
-    bits_24: u8,
-    bits_25: u8,
-    bits_26: u8,
-    bits_27: u8,
+    bits_24: u32,
     random_size_min: i16,
     random_size_max: i16,
     random_roll_min: f32,
@@ -6057,10 +6054,7 @@ pub struct DECAL_PARAM_ST {
     dist_thin_out_max_num: u8,
     dist_thin_out_check_num: u8,
     delay_appear_frame: i16,
-    bits_b0: u8,
-    bits_b1: u8,
-    bits_b2: u8,
-    bits_b3: u8,
+    bits_b0: u32,
     fade_in_time_sec: f32,
     pad1: [u8; 8],
 }
@@ -6144,93 +6138,102 @@ impl DECAL_PARAM_ST {
     }
 
     #[allow(clippy::identity_op)]
-    pub fn replace_texture_id_by_material(&self) -> u8 {
-        (self.bits_24 >> 1) & 0b00000001
+    pub fn replace_texture_id_by_material(&self) -> u32 {
+        (self.bits_24 >> 4) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_replace_texture_id_by_material(&mut self, value: u8) {
-        self.bits_24 = (self.bits_24 & !(0b00000001 << 1)) | ((value & 0b00000001) << 1);
+    pub fn set_replace_texture_id_by_material(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 4))
+            | ((value & 0b00000000000000000000000000000001) << 4);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn dmypoly_category(&self) -> u8 {
-        (self.bits_24 >> 2) & 0b00000011
+    pub fn dmypoly_category(&self) -> u32 {
+        (self.bits_24 >> 5) & 0b00000000000000000000000000000011
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_dmypoly_category(&mut self, value: u8) {
-        self.bits_24 = (self.bits_24 & !(0b00000011 << 2)) | ((value & 0b00000011) << 2);
+    pub fn set_dmypoly_category(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000011 << 5))
+            | ((value & 0b00000000000000000000000000000011) << 5);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn use_deferred_decal(&self) -> u8 {
-        (self.bits_25 >> 1) & 0b00000001
+    pub fn use_deferred_decal(&self) -> u32 {
+        (self.bits_24 >> 11) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_use_deferred_decal(&mut self, value: u8) {
-        self.bits_25 = (self.bits_25 & !(0b00000001 << 1)) | ((value & 0b00000001) << 1);
+    pub fn set_use_deferred_decal(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 11))
+            | ((value & 0b00000000000000000000000000000001) << 11);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn use_paint_decal(&self) -> u8 {
-        (self.bits_25 >> 2) & 0b00000001
+    pub fn use_paint_decal(&self) -> u32 {
+        (self.bits_24 >> 12) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_use_paint_decal(&mut self, value: u8) {
-        self.bits_25 = (self.bits_25 & !(0b00000001 << 2)) | ((value & 0b00000001) << 2);
+    pub fn set_use_paint_decal(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 12))
+            | ((value & 0b00000000000000000000000000000001) << 12);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn blood_type_enable(&self) -> u8 {
-        (self.bits_25 >> 3) & 0b00000001
+    pub fn blood_type_enable(&self) -> u32 {
+        (self.bits_24 >> 13) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_blood_type_enable(&mut self, value: u8) {
-        self.bits_25 = (self.bits_25 & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+    pub fn set_blood_type_enable(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 13))
+            | ((value & 0b00000000000000000000000000000001) << 13);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn b_use_normal(&self) -> u8 {
-        (self.bits_25 >> 4) & 0b00000001
+    pub fn b_use_normal(&self) -> u32 {
+        (self.bits_24 >> 14) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_b_use_normal(&mut self, value: u8) {
-        self.bits_25 = (self.bits_25 & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+    pub fn set_b_use_normal(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 14))
+            | ((value & 0b00000000000000000000000000000001) << 14);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn use_pom(&self) -> u8 {
-        (self.bits_26 >> 2) & 0b00000001
+    pub fn use_pom(&self) -> u32 {
+        (self.bits_24 >> 17) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_use_pom(&mut self, value: u8) {
-        self.bits_26 = (self.bits_26 & !(0b00000001 << 2)) | ((value & 0b00000001) << 2);
+    pub fn set_use_pom(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 17))
+            | ((value & 0b00000000000000000000000000000001) << 17);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn use_emissive(&self) -> u8 {
-        (self.bits_26 >> 3) & 0b00000001
+    pub fn use_emissive(&self) -> u32 {
+        (self.bits_24 >> 18) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_use_emissive(&mut self, value: u8) {
-        self.bits_26 = (self.bits_26 & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+    pub fn set_use_emissive(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 18))
+            | ((value & 0b00000000000000000000000000000001) << 18);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn put_vertical(&self) -> u8 {
-        (self.bits_26 >> 4) & 0b00000001
+    pub fn put_vertical(&self) -> u32 {
+        (self.bits_24 >> 19) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_put_vertical(&mut self, value: u8) {
-        self.bits_26 = (self.bits_26 & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+    pub fn set_put_vertical(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 19))
+            | ((value & 0b00000000000000000000000000000001) << 19);
     }
 
     pub fn random_size_min(&self) -> i16 {
@@ -6610,63 +6613,69 @@ impl DECAL_PARAM_ST {
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_diffuse(&self) -> u8 {
-        (self.bits_b0 >> 0) & 0b00001111
+    pub fn rand_varia_diffuse(&self) -> u32 {
+        (self.bits_b0 >> 0) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_diffuse(&mut self, value: u8) {
-        self.bits_b0 = (self.bits_b0 & !(0b00001111 << 0)) | ((value & 0b00001111) << 0);
+    pub fn set_rand_varia_diffuse(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 0))
+            | ((value & 0b00000000000000000000000000001111) << 0);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_mask(&self) -> u8 {
-        (self.bits_b0 >> 1) & 0b00001111
+    pub fn rand_varia_mask(&self) -> u32 {
+        (self.bits_b0 >> 4) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_mask(&mut self, value: u8) {
-        self.bits_b0 = (self.bits_b0 & !(0b00001111 << 1)) | ((value & 0b00001111) << 1);
+    pub fn set_rand_varia_mask(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 4))
+            | ((value & 0b00000000000000000000000000001111) << 4);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_reflec(&self) -> u8 {
-        (self.bits_b1 >> 0) & 0b00001111
+    pub fn rand_varia_reflec(&self) -> u32 {
+        (self.bits_b0 >> 8) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_reflec(&mut self, value: u8) {
-        self.bits_b1 = (self.bits_b1 & !(0b00001111 << 0)) | ((value & 0b00001111) << 0);
+    pub fn set_rand_varia_reflec(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 8))
+            | ((value & 0b00000000000000000000000000001111) << 8);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_normal(&self) -> u8 {
-        (self.bits_b2 >> 0) & 0b00001111
+    pub fn rand_varia_normal(&self) -> u32 {
+        (self.bits_b0 >> 16) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_normal(&mut self, value: u8) {
-        self.bits_b2 = (self.bits_b2 & !(0b00001111 << 0)) | ((value & 0b00001111) << 0);
+    pub fn set_rand_varia_normal(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 16))
+            | ((value & 0b00000000000000000000000000001111) << 16);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_height(&self) -> u8 {
-        (self.bits_b2 >> 1) & 0b00001111
+    pub fn rand_varia_height(&self) -> u32 {
+        (self.bits_b0 >> 20) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_height(&mut self, value: u8) {
-        self.bits_b2 = (self.bits_b2 & !(0b00001111 << 1)) | ((value & 0b00001111) << 1);
+    pub fn set_rand_varia_height(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 20))
+            | ((value & 0b00000000000000000000000000001111) << 20);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_emissive(&self) -> u8 {
-        (self.bits_b3 >> 0) & 0b00001111
+    pub fn rand_varia_emissive(&self) -> u32 {
+        (self.bits_b0 >> 24) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_emissive(&mut self, value: u8) {
-        self.bits_b3 = (self.bits_b3 & !(0b00001111 << 0)) | ((value & 0b00001111) << 0);
+    pub fn set_rand_varia_emissive(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 24))
+            | ((value & 0b00000000000000000000000000001111) << 24);
     }
 
     pub fn fade_in_time_sec(&self) -> f32 {
@@ -11846,12 +11855,12 @@ impl EQUIP_PARAM_WEAPON_ST {
 
     #[allow(clippy::identity_op)]
     pub fn disable_gem_attr(&self) -> u8 {
-        (self.bits_105 >> 6) & 0b00000001
+        (self.bits_105 >> 7) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_disable_gem_attr(&mut self, value: u8) {
-        self.bits_105 = (self.bits_105 & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
+        self.bits_105 = (self.bits_105 & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
     }
 
     pub fn def_sfx_material1(&self) -> u16 {
@@ -21399,42 +21408,42 @@ impl HIT_MTRL_PARAM_ST {
 
     #[allow(clippy::identity_op)]
     pub fn foot_effect_dir_type(&self) -> u8 {
-        (self.bits_c >> 1) & 0b00000011
+        (self.bits_c >> 2) & 0b00000011
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_foot_effect_dir_type(&mut self, value: u8) {
-        self.bits_c = (self.bits_c & !(0b00000011 << 1)) | ((value & 0b00000011) << 1);
+        self.bits_c = (self.bits_c & !(0b00000011 << 2)) | ((value & 0b00000011) << 2);
     }
 
     #[allow(clippy::identity_op)]
     pub fn floor_height_type(&self) -> u8 {
-        (self.bits_c >> 2) & 0b00000011
+        (self.bits_c >> 4) & 0b00000011
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_floor_height_type(&mut self, value: u8) {
-        self.bits_c = (self.bits_c & !(0b00000011 << 2)) | ((value & 0b00000011) << 2);
+        self.bits_c = (self.bits_c & !(0b00000011 << 4)) | ((value & 0b00000011) << 4);
     }
 
     #[allow(clippy::identity_op)]
     pub fn disable_fall_damage(&self) -> u8 {
-        (self.bits_c >> 3) & 0b00000001
+        (self.bits_c >> 6) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_disable_fall_damage(&mut self, value: u8) {
-        self.bits_c = (self.bits_c & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+        self.bits_c = (self.bits_c & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_hardness_for_sound_reverb(&self) -> u8 {
-        (self.bits_c >> 4) & 0b00000001
+        (self.bits_c >> 7) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_hardness_for_sound_reverb(&mut self, value: u8) {
-        self.bits_c = (self.bits_c & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+        self.bits_c = (self.bits_c & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
     }
 
     pub fn hardness_type(&self) -> u8 {
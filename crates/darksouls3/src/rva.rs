@@ -5,6 +5,7 @@ use windows::core::PCSTR;
 
 mod bundle;
 mod rva_data;
+mod scan;
 
 pub use bundle::*;
 
@@ -29,13 +30,21 @@ impl GameVersion {
 
 /// Returns the RVA bundle for the current executable region and version.
 ///
-/// This will panic if the current executable isn't supported by this package.
+/// Versions without generated RVAs are scanned for the patterns they were
+/// generated from instead, see [RvaBundle::scan]. This will panic if that
+/// fails too.
 pub fn get() -> &'static RvaBundle {
     static RVAS: LazyLock<RvaBundle> = LazyLock::new(|| {
         let module = unsafe {
             PeView::module(GetModuleHandleA(PCSTR(std::ptr::null())).unwrap().0 as *const u8)
         };
         detect_version_and_get_rvas(&module)
+            .or_else(|| {
+                tracing::warn!("Unknown game version, scanning for RVAs instead");
+                RvaBundle::scan()
+                    .inspect_err(|e| tracing::error!("Could not scan for RVAs: {e}"))
+                    .ok()
+            })
             .expect("This game version or distribution is not supported")
     });
 
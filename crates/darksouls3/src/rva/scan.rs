@@ -0,0 +1,20 @@
+//! Generated runtime lookups of the RVAs.
+
+// DO NOT EDIT THIS FILE DIRECTLY.
+
+use shared::scan::{ScanError, find_pattern};
+
+use super::RvaBundle;
+
+impl RvaBundle {
+    /// Looks up every RVA in the current executable, the same way they're
+    /// found by `tools/binary-mapper` using `mapper-profile.toml` in the
+    /// root of this package. This is slow, so it's only done for versions
+    /// without generated RVAs.
+    pub fn scan() -> Result<Self, ScanError> {
+        let pattern_0 = find_pattern("45 33 c0 41 8d ? ? 48 83 c4 ? 5b e9 $ { ' }")?;
+        Ok(Self {
+            register_task: pattern_0[1],
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt::Display;
+use core::fmt::Display;
 
 use bitfield::bitfield;
 
@@ -61,7 +61,7 @@ impl From<i32> for BlockId {
 }
 
 impl Display for BlockId {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "m{:0>2}_{:0>2}_{:0>2}_{:0>2}",
@@ -75,7 +75,7 @@ impl Display for BlockId {
 
 #[cfg(test)]
 mod tests {
-    use crate::cs::BlockId;
+    use crate::BlockId;
 
     #[test]
     fn test_bitfield() {
@@ -0,0 +1,36 @@
+use core::fmt;
+
+/// Returned by the `try_` accessors of containers whose invariants don't
+/// hold, e.g. because the game is modifying them from another thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerError {
+    OutOfOrder,
+    Misaligned(usize),
+    PartialElement,
+    InvalidLength { len: usize, capacity: usize },
+    LengthMismatch(usize),
+    BrokenLink(usize),
+    InvalidHeader,
+}
+
+impl fmt::Display for ContainerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfOrder => write!(f, "Container pointers are out of order"),
+            Self::Misaligned(address) => write!(f, "Container pointer {address:#x} is not aligned"),
+            Self::PartialElement => write!(f, "Container holds a partial element"),
+            Self::InvalidLength { len, capacity } => {
+                write!(f, "Container length {len} exceeds its capacity {capacity}")
+            }
+            Self::LengthMismatch(len) => {
+                write!(f, "Container length {len} does not match its contents")
+            }
+            Self::BrokenLink(index) => {
+                write!(f, "Container link {index} does not point back to its node")
+            }
+            Self::InvalidHeader => write!(f, "Container allocation header is invalid"),
+        }
+    }
+}
+
+impl core::error::Error for ContainerError {}
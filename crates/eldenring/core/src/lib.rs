@@ -0,0 +1,17 @@
+//! Layout-only Elden Ring types that don't depend on the game running.
+//!
+//! Everything in here is `no_std` and free of the Windows and PE parsing
+//! dependencies of the `eldenring` crate, which re-exports these types at
+//! their usual paths. This makes them usable from small injected payloads and
+//! from analysis tools running on other platforms.
+#![no_std]
+
+mod block_id;
+mod container;
+mod dlut;
+mod field_ins;
+
+pub use block_id::*;
+pub use container::*;
+pub use dlut::*;
+pub use field_ins::*;
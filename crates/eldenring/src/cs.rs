@@ -1,6 +1,5 @@
 mod action_button;
 mod auto_invade_point;
-mod block_id;
 mod bullet_ins;
 mod bullet_manager;
 mod camera;
@@ -56,7 +55,6 @@ mod world_sfx_man;
 
 pub use action_button::*;
 pub use auto_invade_point::*;
-pub use block_id::*;
 pub use bullet_ins::*;
 pub use bullet_manager::*;
 pub use camera::*;
@@ -109,3 +107,5 @@ pub use world_chr_man_dbg::*;
 pub use world_geom_man::*;
 pub use world_scene_draw_param_manager::*;
 pub use world_sfx_man::*;
+
+pub use eldenring_core::{BlockId, FieldInsHandle, FieldInsSelector, FieldInsType};
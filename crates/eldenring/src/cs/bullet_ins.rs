@@ -3,6 +3,7 @@ use crate::position::{DirectionalVector, HavokPosition};
 use crate::rotation::Quaternion;
 use std::ffi::OsStr;
 use std::ptr::NonNull;
+use thiserror::Error;
 use vtable_rs::VPtr;
 
 use super::{CSBulletTargetingSystemOwner, CSTargetingSystemBase, FieldInsBaseVmt, FieldInsHandle};
@@ -40,8 +41,8 @@ pub struct CSBulletIns {
     unk4ac: u32,
     unk4b0_struct: [u8; 0x660],
     pub time_alive: f32,
-    // TODO: check relevant usages before exposing any of this
     life: f32,
+    // TODO: check relevant usages before exposing any of this
     life_max: f32,
     accel_time: f32,
     create_interval_time_left: f32,
@@ -68,6 +69,49 @@ pub struct CSBulletIns {
     unke28: u64,
 }
 
+#[derive(Debug, Error, PartialEq)]
+pub enum BulletInsError {
+    #[error("{0} is not a valid bullet lifetime")]
+    InvalidLife(f32),
+    #[error("Velocity {0:?} is not finite")]
+    InvalidVelocity(DirectionalVector),
+}
+
+impl CSBulletIns {
+    /// Lifetime of the bullet in seconds, initialized from its BulletParam
+    /// row.
+    pub fn life(&self) -> f32 {
+        self.life
+    }
+
+    /// Changes the bullet's lifetime. Negative and non-finite values are
+    /// rejected.
+    pub fn set_life(&mut self, life: f32) -> Result<(), BulletInsError> {
+        if !life.is_finite() || life < 0.0 {
+            return Err(BulletInsError::InvalidLife(life));
+        }
+
+        self.life = life;
+        Ok(())
+    }
+
+    pub fn velocity(&self) -> DirectionalVector {
+        self.physics.velocity
+    }
+
+    /// Changes the bullet's velocity mid-flight. Non-finite components are
+    /// rejected since they'd end up in the bullet's position and hit checks.
+    pub fn set_velocity(&mut self, velocity: DirectionalVector) -> Result<(), BulletInsError> {
+        let DirectionalVector(x, y, z, w) = velocity;
+        if ![x, y, z, w].iter().all(|c| c.is_finite()) {
+            return Err(BulletInsError::InvalidVelocity(velocity));
+        }
+
+        self.physics.velocity = velocity;
+        Ok(())
+    }
+}
+
 #[repr(C)]
 pub struct BulletPhysics {
     pub position: HavokPosition,
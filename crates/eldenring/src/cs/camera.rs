@@ -62,6 +62,29 @@ impl CSCam {
             self.matrix.3.3,
         )
     }
+
+    /// Projects a world space position to normalized device coordinates,
+    /// with (-1, -1) being the bottom left of the screen and (1, 1) the top
+    /// right. Returns None for positions behind the near plane.
+    ///
+    /// The projection is done from the camera's own basis vectors and its
+    /// vertical field of view, so it doesn't depend on the depth convention
+    /// (the game renders with reversed Z) of the projection matrix.
+    pub fn project(&self, position: glam::Vec3) -> Option<glam::Vec2> {
+        let delta = position - glam::Vec3::from(self.position());
+
+        let depth = delta.dot(self.forward().into());
+        if depth < self.near_plane {
+            return None;
+        }
+
+        let half_height = (self.fov * 0.5).tan() * depth;
+        let half_width = half_height * self.aspect_ratio;
+        Some(glam::vec2(
+            delta.dot(self.right().into()) / half_width,
+            delta.dot(self.up().into()) / half_height,
+        ))
+    }
 }
 
 pub type CSPersCam = CSCam;
@@ -101,3 +124,42 @@ pub enum ChrCamType {
     Unk6 = 6,
     DeathCam = 7,
 }
+
+#[cfg(test)]
+mod tests {
+    use shared::{F32Vector4, F32ViewMatrix};
+
+    use super::CSCam;
+
+    fn camera() -> CSCam {
+        CSCam {
+            vftable: 0,
+            unk8: 0,
+            unkc: 0,
+            matrix: F32ViewMatrix::new(
+                F32Vector4(1.0, 0.0, 0.0, 0.0),
+                F32Vector4(0.0, 1.0, 0.0, 0.0),
+                F32Vector4(0.0, 0.0, 1.0, 0.0),
+                F32Vector4(10.0, 0.0, 0.0, 1.0),
+            ),
+            fov: std::f32::consts::FRAC_PI_2,
+            aspect_ratio: 2.0,
+            near_plane: 0.1,
+            far_plane: 1000.0,
+        }
+    }
+
+    #[test]
+    fn project() {
+        let camera = camera();
+        assert_eq!(
+            camera.project(glam::vec3(10.0, 0.0, 5.0)),
+            Some(glam::vec2(0.0, 0.0))
+        );
+
+        let top_right = camera.project(glam::vec3(20.0, 5.0, 5.0)).unwrap();
+        assert!((top_right - glam::vec2(1.0, 1.0)).length() < 1e-5);
+
+        assert_eq!(camera.project(glam::vec3(10.0, 0.0, -5.0)), None);
+    }
+}
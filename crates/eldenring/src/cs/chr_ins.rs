@@ -7,7 +7,6 @@ use vtable_rs::VPtr;
 use windows::core::PCWSTR;
 
 use crate::Vector;
-use crate::cs::field_ins::{FieldInsBaseVmt, FieldInsHandle};
 use crate::cs::network_session::PlayerNetworkSession;
 use crate::cs::player_game_data::{ChrAsm, PlayerGameData};
 use crate::cs::session_manager::SessionManagerPlayerEntryBase;
@@ -16,6 +15,7 @@ use crate::cs::task::{CSEzRabbitNoUpdateTask, CSEzVoidTask};
 use crate::cs::world_chr_man::{ChrSetEntry, WorldBlockChr};
 use crate::cs::world_geom_man::CSMsbPartsEne;
 use crate::cs::{BlockId, CSPlayerMenuCtrl, EquipmentDurabilityStatus, ItemId};
+use crate::cs::{FieldInsBaseVmt, FieldInsHandle};
 use crate::dltx::DLString;
 use crate::fd4::FD4Time;
 use crate::param::{ATK_PARAM_ST, NPC_PARAM_ST};
@@ -1300,6 +1300,24 @@ pub enum ThrowNodeState {
     Unk8 = 8,
 }
 
+impl TryFrom<u32> for ThrowNodeState {
+    type Error = u32;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            1 => Self::Unk1,
+            2 => Self::Unk2,
+            3 => Self::InThrowAttacker,
+            4 => Self::InThrowTarget,
+            5 => Self::DeathAttacker,
+            6 => Self::DeathTarget,
+            7 => Self::Unk7,
+            8 => Self::Unk8,
+            _ => return Err(value),
+        })
+    }
+}
+
 #[repr(C)]
 /// Source of name: RTTI
 pub struct CSThrowNode {
@@ -1316,6 +1334,19 @@ pub struct CSThrowNode {
     unkb8: [u8; 0xb8],
 }
 
+impl CSThrowNode {
+    /// The node's state, None while the character isn't part of a throw.
+    ///
+    /// Unlike reading [CSThrowNode::throw_state] directly, this doesn't assume
+    /// the game only ever stores mapped states there.
+    pub fn state(&self) -> Option<ThrowNodeState> {
+        // SAFETY: the enum is repr(u32), reading it as an integer is always
+        // valid.
+        let raw = unsafe { *(&raw const self.throw_state as *const u32) };
+        ThrowNodeState::try_from(raw).ok()
+    }
+}
+
 #[repr(C)]
 /// Source of name: RTTI
 pub struct CSChrThrowModule {
@@ -1818,3 +1849,37 @@ pub enum ChrType {
     RecusantNpc = 21,
     Unk22 = 22,
 }
+
+impl TryFrom<i32> for ChrType {
+    type Error = i32;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        Ok(match value {
+            -1 => Self::None,
+            0 => Self::Local,
+            1 => Self::WhitePhantom,
+            2 => Self::Duelist,
+            3 => Self::Ghost,
+            4 => Self::Ghost1,
+            5 => Self::Npc,
+            6 => Self::Unk6,
+            7 => Self::Unk7,
+            8 => Self::GrayPhantom,
+            9 => Self::Unk9,
+            10 => Self::BloodstainGhost,
+            11 => Self::BonfireGhost,
+            12 => Self::Unk12,
+            13 => Self::Arena,
+            14 => Self::MessageGhost,
+            15 => Self::BloodyFinger,
+            16 => Self::Recusant,
+            17 => Self::BluePhantom,
+            18 => Self::FesteringBloodyFinger,
+            19 => Self::WhiteSummonNpc,
+            20 => Self::BloodyFingerNpc,
+            21 => Self::RecusantNpc,
+            22 => Self::Unk22,
+            _ => return Err(value),
+        })
+    }
+}
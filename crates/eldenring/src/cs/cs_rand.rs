@@ -100,20 +100,15 @@ impl<T: CSRandVmt> CSRand<T> {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct DLRandomGeneratorXorshift {
     pub state: [u32; 4],
 }
 
-#[repr(C)]
-pub struct CSRandXorshift {
-    pub base: CSRand<Self>,
-    pub xorshift_state: DLRandomGeneratorXorshift,
-}
-
-impl CSRandXorshift {
+impl DLRandomGeneratorXorshift {
     /// Replicates the game's Xorshift RNG initialization
     /// with Linear Congruential Generator seeding.
-    pub fn new(seed: u64) -> Self {
+    pub fn from_seed(seed: u64) -> Self {
         let mut state = [0u32; 4];
 
         let mut lcg_state = (seed & 0xffff_ffff) * 0x5deece66d + 0xb;
@@ -122,13 +117,38 @@ impl CSRandXorshift {
             lcg_state = lcg_state.wrapping_mul(0x5deece66d).wrapping_add(0xb);
         }
 
+        Self { state }
+    }
+}
+
+/// Xorshift RNG. Instances live in e.g. [GameMan](crate::cs::GameMan),
+/// [CSGaitemImp](crate::cs::CSGaitemImp) and
+/// [CSNowLoadingHelper](crate::cs::CSNowLoadingHelper). Which rolls each of
+/// them is responsible for hasn't been fully mapped yet.
+///
+/// Copying [CSRandXorshift::xorshift_state] and writing it back later makes
+/// the generator repeat the same sequence of rolls.
+#[repr(C)]
+pub struct CSRandXorshift {
+    pub base: CSRand<Self>,
+    pub xorshift_state: DLRandomGeneratorXorshift,
+}
+
+impl CSRandXorshift {
+    pub fn new(seed: u64) -> Self {
         CSRandXorshift {
             base: CSRand {
                 vftable: VPtr::<dyn CSRandVmt, CSRandXorshift>::new(),
             },
-            xorshift_state: DLRandomGeneratorXorshift { state },
+            xorshift_state: DLRandomGeneratorXorshift::from_seed(seed),
         }
     }
+
+    /// Reseeds the generator in place, the same way it would've been seeded
+    /// had it been created with `seed`.
+    pub fn reseed(&mut self, seed: u64) {
+        self.xorshift_state = DLRandomGeneratorXorshift::from_seed(seed);
+    }
 }
 
 impl CSRandVmt for CSRandXorshift {
@@ -197,3 +217,29 @@ impl CSRandVmt for CSRandSFMT {
         unimplemented!()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{CSRandVmt, CSRandXorshift, DLRandomGeneratorXorshift};
+
+    #[test]
+    fn restored_state_repeats_rolls() {
+        let mut rand = CSRandXorshift::new(1234);
+        assert_eq!(
+            rand.xorshift_state,
+            DLRandomGeneratorXorshift::from_seed(1234)
+        );
+
+        let snapshot = rand.xorshift_state;
+        let rolls = [rand.next_uint(), rand.next_uint(), rand.next_uint()];
+
+        rand.xorshift_state = snapshot;
+        assert_eq!(
+            [rand.next_uint(), rand.next_uint(), rand.next_uint()],
+            rolls
+        );
+
+        rand.reseed(1234);
+        assert_eq!(rand.xorshift_state, snapshot);
+    }
+}
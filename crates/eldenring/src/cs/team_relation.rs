@@ -112,3 +112,63 @@ pub struct TeamRelationTargetInfo {
     pub friendly_target: bool,
     pub self_target: bool,
 }
+
+/// Team a character belongs to, deciding who it can hit and who it targets.
+/// Stored in [ChrIns::team_type](super::ChrIns::team_type) and NPC_PARAM_ST's
+/// teamType field. The game uses higher values as well, those haven't been
+/// mapped yet and fail to convert. Code that has to handle them can work with
+/// the raw value instead.
+///
+/// Source of name: Paramdex TEAM_TYPE enum
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TeamType {
+    None = 0,
+    /// The host of the world.
+    Live = 1,
+    /// Cooperators.
+    WhiteGhost = 2,
+    /// Invaders.
+    BlackGhost = 3,
+    /// Other players seen as ghosts.
+    GrayGhost = 4,
+    WanderingGhost = 5,
+    Enemy = 6,
+    /// Enemies that are hostile to everything but their own team.
+    StrongEnemy = 7,
+    Ally = 8,
+    /// Allies that have been angered, e.g. by being hit by the player.
+    HostileAlly = 9,
+    DecoyEnemy = 10,
+    RedChild = 11,
+    FightingAlly = 12,
+}
+
+impl TryFrom<u8> for TeamType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Ok(match value {
+            0 => Self::None,
+            1 => Self::Live,
+            2 => Self::WhiteGhost,
+            3 => Self::BlackGhost,
+            4 => Self::GrayGhost,
+            5 => Self::WanderingGhost,
+            6 => Self::Enemy,
+            7 => Self::StrongEnemy,
+            8 => Self::Ally,
+            9 => Self::HostileAlly,
+            10 => Self::DecoyEnemy,
+            11 => Self::RedChild,
+            12 => Self::FightingAlly,
+            _ => return Err(value),
+        })
+    }
+}
+
+impl From<TeamType> for u8 {
+    fn from(value: TeamType) -> Self {
+        value as u8
+    }
+}
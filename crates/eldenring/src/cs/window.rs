@@ -53,6 +53,86 @@ pub struct CSWindowScreenConfig {
     pub grass_quality: GrassQuality,
 }
 
+impl CSWindowImp {
+    /// Size of the game's window or screen as it's currently being rendered.
+    pub fn screen_resolution(&self) -> Resolution {
+        Resolution {
+            width: self.screen_width as u32,
+            height: self.screen_height as u32,
+        }
+    }
+
+    /// Changes the resolution for a window type in both the runtime and the
+    /// persisted config, so it survives the game rewriting one from the
+    /// other. The change is picked up the next time the screen mode is
+    /// applied, e.g. when toggling the window type.
+    ///
+    /// Only the resolution is changed. The game still constrains the
+    /// rendered image to 16:9 and letterboxes the rest, there's no patch for
+    /// its aspect ratio checks here.
+    pub fn set_resolution(&mut self, window_type: CSWindowType, resolution: Resolution) {
+        self.runtime_window_config
+            .set_resolution(window_type, resolution);
+        self.persistent_window_config
+            .set_resolution(window_type, resolution);
+    }
+}
+
+impl CSWindowScreenConfig {
+    /// The configured resolution for a window type.
+    pub fn resolution(&self, window_type: CSWindowType) -> Resolution {
+        let (width, height) = match window_type {
+            CSWindowType::Windowed => (self.windowed_screen_width, self.windowed_screen_height),
+            CSWindowType::Fullscreen => (self.fullscreen_width, self.fullscreen_height),
+            CSWindowType::Borderless => {
+                (self.borderless_screen_width, self.borderless_screen_height)
+            }
+        };
+
+        Resolution {
+            width: width as u32,
+            height: height as u32,
+        }
+    }
+
+    pub fn set_resolution(&mut self, window_type: CSWindowType, resolution: Resolution) {
+        let (width, height) = match window_type {
+            CSWindowType::Windowed => (
+                &mut self.windowed_screen_width,
+                &mut self.windowed_screen_height,
+            ),
+            CSWindowType::Fullscreen => (&mut self.fullscreen_width, &mut self.fullscreen_height),
+            CSWindowType::Borderless => (
+                &mut self.borderless_screen_width,
+                &mut self.borderless_screen_height,
+            ),
+        };
+
+        *width = resolution.width as i32;
+        *height = resolution.height as i32;
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Resolution {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Resolution {
+    /// The aspect ratio the game renders at natively.
+    pub const NATIVE_ASPECT_RATIO: f32 = 16.0 / 9.0;
+
+    pub fn aspect_ratio(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
+    /// Whether the game would letterbox or pillarbox this resolution.
+    pub fn is_letterboxed(&self) -> bool {
+        (self.aspect_ratio() - Self::NATIVE_ASPECT_RATIO).abs() > 0.01
+    }
+}
+
 #[repr(u32)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum CSWindowType {
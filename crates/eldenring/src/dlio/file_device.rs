@@ -310,6 +310,7 @@ pub struct DLFileOperatorContainer {
     flags: u32,
 }
 
+/// Owns every file device and virtual root the game resolves paths through.
 #[repr(C)]
 pub struct DLFileDeviceManager {
     pub devices: Vector<NonNull<DLFileDeviceBase>>,
@@ -323,6 +324,49 @@ pub struct DLFileDeviceManager {
     pub mutex: DLPlainLightMutex,
 }
 
+/// A virtual root like `data0:` along with the location it resolves to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DLFileMount {
+    pub root: String,
+    pub target: String,
+}
+
+impl DLFileDeviceManager {
+    /// Lists every virtual root the game has mounted, in lookup order.
+    pub fn mounts(&self) -> Vec<DLFileMount> {
+        self.virtual_roots
+            .items()
+            .iter()
+            .map(|[root, target]| DLFileMount {
+                root: root.to_str().unwrap_or_default(),
+                target: target.to_str().unwrap_or_default(),
+            })
+            .collect()
+    }
+
+    /// Yields the devices that serve files out of the game's encrypted
+    /// BHD/BDT archives.
+    pub fn archive_devices(&self) -> impl Iterator<Item = &DLFileDeviceBase> {
+        self.devices
+            .items()
+            .iter()
+            // SAFETY: the manager owns its devices for as long as it's alive.
+            .map(|device| unsafe { device.as_ref() })
+            .filter(|device| device.is_encrypted())
+    }
+
+    /// Yields the devices that aren't backed by an encrypted archive, like
+    /// the one reading loose files from disk.
+    pub fn plain_devices(&self) -> impl Iterator<Item = &DLFileDeviceBase> {
+        self.devices
+            .items()
+            .iter()
+            // SAFETY: the manager owns its devices for as long as it's alive.
+            .map(|device| unsafe { device.as_ref() })
+            .filter(|device| !device.is_encrypted())
+    }
+}
+
 impl DLFileDeviceVmt for DLFileDeviceBase {
     extern "C" fn destructor(&mut self) {
         (self.vftable.destructor)(self);
@@ -1,7 +1,15 @@
+#[cfg(feature = "alloc-tracking")]
+mod alloc_tracking;
 mod allocator;
+#[cfg(feature = "heap-canary")]
+mod canary;
 mod mutex;
 mod signal;
 
+#[cfg(feature = "alloc-tracking")]
+pub use alloc_tracking::*;
 pub use allocator::*;
+#[cfg(feature = "heap-canary")]
+pub use canary::*;
 pub use mutex::*;
 pub use signal::*;
@@ -0,0 +1,127 @@
+//! Accounting for allocations made through [DLAllocatorRef](super::DLAllocatorRef),
+//! enabled with the `alloc-tracking` feature.
+//!
+//! Every mod links its own copy of this crate, so the numbers reported here
+//! only ever cover allocations made by the mod asking for them. Allocations
+//! can additionally be attributed to a tag with [track_allocations] to narrow
+//! down which part of a mod is responsible for a leak.
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+/// Tag used for allocations made outside of [track_allocations].
+pub const UNTAGGED: &str = "untagged";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocationStats {
+    /// Allocations that haven't been freed yet.
+    pub live_allocations: usize,
+    pub live_bytes: usize,
+    /// Allocations made since the mod was loaded, including freed ones.
+    pub total_allocations: usize,
+    pub total_bytes: usize,
+}
+
+/// An allocation that was still live when [leak_report] was requested.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LiveAllocation {
+    pub address: usize,
+    pub size: usize,
+    pub tag: &'static str,
+}
+
+#[derive(Default)]
+struct Registry {
+    live: HashMap<usize, (usize, &'static str)>,
+    stats: HashMap<&'static str, AllocationStats>,
+}
+
+static REGISTRY: LazyLock<Mutex<Registry>> = LazyLock::new(Default::default);
+
+thread_local! {
+    static CURRENT_TAG: Cell<&'static str> = const { Cell::new(UNTAGGED) };
+}
+
+/// Attributes every allocation made on this thread while running `f` to `tag`.
+pub fn track_allocations<R>(tag: &'static str, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT_TAG.replace(tag);
+    let result = f();
+    CURRENT_TAG.set(previous);
+    result
+}
+
+pub(super) fn record_alloc(ptr: *mut u8, size: usize) {
+    if ptr.is_null() {
+        return;
+    }
+
+    let tag = CURRENT_TAG.get();
+    let mut registry = REGISTRY.lock().unwrap();
+    registry.live.insert(ptr as usize, (size, tag));
+
+    let stats = registry.stats.entry(tag).or_default();
+    stats.live_allocations += 1;
+    stats.live_bytes += size;
+    stats.total_allocations += 1;
+    stats.total_bytes += size;
+}
+
+pub(super) fn record_dealloc(ptr: *mut u8) {
+    let mut registry = REGISTRY.lock().unwrap();
+    let Some((size, tag)) = registry.live.remove(&(ptr as usize)) else {
+        return;
+    };
+
+    if let Some(stats) = registry.stats.get_mut(tag) {
+        stats.live_allocations -= 1;
+        stats.live_bytes -= size;
+    }
+}
+
+/// Totals across all tags.
+pub fn allocation_stats() -> AllocationStats {
+    REGISTRY
+        .lock()
+        .unwrap()
+        .stats
+        .values()
+        .fold(AllocationStats::default(), |acc, s| AllocationStats {
+            live_allocations: acc.live_allocations + s.live_allocations,
+            live_bytes: acc.live_bytes + s.live_bytes,
+            total_allocations: acc.total_allocations + s.total_allocations,
+            total_bytes: acc.total_bytes + s.total_bytes,
+        })
+}
+
+/// Totals per tag passed to [track_allocations].
+pub fn allocation_stats_by_tag() -> HashMap<&'static str, AllocationStats> {
+    REGISTRY.lock().unwrap().stats.clone()
+}
+
+/// Lists every allocation that hasn't been freed yet, and logs a summary.
+/// Allocations the game freed by itself after taking ownership still show
+/// up here. Meant to be called when the mod is unloaded.
+pub fn leak_report() -> Vec<LiveAllocation> {
+    let registry = REGISTRY.lock().unwrap();
+    for (tag, stats) in registry
+        .stats
+        .iter()
+        .filter(|(_, s)| s.live_allocations > 0)
+    {
+        tracing::warn!(
+            "{} allocations ({} bytes) tagged \"{tag}\" are still live",
+            stats.live_allocations,
+            stats.live_bytes
+        );
+    }
+
+    registry
+        .live
+        .iter()
+        .map(|(address, (size, tag))| LiveAllocation {
+            address: *address,
+            size: *size,
+            tag: *tag,
+        })
+        .collect()
+}
@@ -5,6 +5,13 @@ use std::{
 
 use vtable_rs::VPtr;
 
+use crate::Error;
+
+#[cfg(feature = "heap-canary")]
+use super::canary::GUARD_SIZE;
+#[cfg(not(feature = "heap-canary"))]
+const GUARD_SIZE: usize = 0;
+
 #[vtable_rs::vtable]
 pub trait DLAllocatorVmt {
     fn destructor(&mut self, param_2: bool);
@@ -78,20 +85,69 @@ pub struct DLAllocatorBase {
 #[derive(Clone)]
 pub struct DLAllocatorRef(NonNull<DLAllocatorBase>);
 
+/// The alignment the allocators' plain `allocate` guarantees. Layouts that
+/// need more go through `allocate_aligned`.
+const DEFAULT_ALIGNMENT: usize = 16;
+
 unsafe impl GlobalAlloc for DLAllocatorRef {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
         let allocator = self.0.as_ptr();
-        unsafe { ((*allocator).vftable.allocate)(&mut *allocator, layout.size()) as *mut u8 }
+        let size = layout.size() + GUARD_SIZE;
+        let ptr = unsafe {
+            if layout.align() > DEFAULT_ALIGNMENT {
+                ((*allocator).vftable.allocate_aligned)(&mut *allocator, size, layout.align())
+                    as *mut u8
+            } else {
+                ((*allocator).vftable.allocate)(&mut *allocator, size) as *mut u8
+            }
+        };
+
+        #[cfg(feature = "heap-canary")]
+        unsafe {
+            super::canary::guard(ptr, layout.size())
+        };
+
+        #[cfg(feature = "alloc-tracking")]
+        super::alloc_tracking::record_alloc(ptr, layout.size());
+
+        #[cfg(feature = "audit")]
+        crate::util::audit::record_alloc(ptr as usize, layout.size());
+
+        ptr
     }
 
     unsafe fn dealloc(&self, ptr: *mut u8, _layout: Layout) {
         let allocator = self.0.as_ptr();
+
+        #[cfg(feature = "heap-canary")]
+        unsafe {
+            super::canary::release(ptr)
+        };
+
+        #[cfg(feature = "alloc-tracking")]
+        super::alloc_tracking::record_dealloc(ptr);
+
+        #[cfg(feature = "audit")]
+        crate::util::audit::record_dealloc(ptr as usize);
+
         unsafe {
             ((*allocator).vftable.deallocate)(&mut *allocator, ptr);
         }
     }
 }
 
+impl DLAllocatorRef {
+    /// Allocates memory for `layout` with this allocator, returning
+    /// [Error::AllocatorUnavailable] instead of a null pointer if it fails.
+    ///
+    /// # Safety
+    ///
+    /// Same as [GlobalAlloc::alloc].
+    pub unsafe fn try_alloc(&self, layout: Layout) -> Result<NonNull<u8>, Error> {
+        NonNull::new(unsafe { self.alloc(layout) }).ok_or(Error::AllocatorUnavailable)
+    }
+}
+
 impl From<NonNull<DLAllocatorBase>> for DLAllocatorRef {
     fn from(ptr: NonNull<DLAllocatorBase>) -> Self {
         Self(ptr)
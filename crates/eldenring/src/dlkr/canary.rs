@@ -0,0 +1,90 @@
+//! Guard bytes for allocations made through [DLAllocatorRef](super::DLAllocatorRef),
+//! enabled with the `heap-canary` feature.
+//!
+//! Every allocation is padded with a trailing run of guard bytes which is
+//! checked when it's freed again, or on demand with [validate_heap]. The
+//! pointer handed out isn't shifted so the game can still free allocations
+//! made by mods through its own allocators. Allocations freed by the game
+//! directly aren't seen here and stay in the registry, ex. the buffer of a
+//! [Vector](crate::Vector) the game grew itself, which is why
+//! [validate_heap] is unsafe.
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+/// Same pattern the MSVC debug heap uses for its "no man's land".
+const GUARD_BYTE: u8 = 0xfd;
+pub(super) const GUARD_SIZE: usize = 16;
+
+static ALLOCATIONS: LazyLock<Mutex<HashMap<usize, usize>>> = LazyLock::new(Default::default);
+
+/// An allocation with one or more overwritten guard bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HeapCorruption {
+    /// Address of the allocation, as returned by the allocator.
+    pub address: usize,
+    /// Size that was requested for the allocation.
+    pub size: usize,
+    /// Offset of the first overwritten guard byte past the end of the allocation.
+    pub overrun: usize,
+}
+
+/// Writes the guard bytes for and registers an allocation of `size` bytes.
+///
+/// # Safety
+/// `ptr` must be valid for `size + GUARD_SIZE` bytes.
+pub(super) unsafe fn guard(ptr: *mut u8, size: usize) {
+    if ptr.is_null() {
+        return;
+    }
+
+    unsafe { std::ptr::write_bytes(ptr.add(size), GUARD_BYTE, GUARD_SIZE) };
+    ALLOCATIONS.lock().unwrap().insert(ptr as usize, size);
+}
+
+/// Checks and forgets an allocation that's about to be freed.
+///
+/// # Safety
+/// `ptr` must not have been freed yet.
+pub(super) unsafe fn release(ptr: *mut u8) {
+    let Some(size) = ALLOCATIONS.lock().unwrap().remove(&(ptr as usize)) else {
+        return;
+    };
+
+    if let Some(corruption) = unsafe { check(ptr as usize, size) } {
+        tracing::error!("Heap corruption detected on free: {corruption:x?}");
+    }
+}
+
+unsafe fn check(address: usize, size: usize) -> Option<HeapCorruption> {
+    let guard = unsafe { std::slice::from_raw_parts((address + size) as *const u8, GUARD_SIZE) };
+    let overrun = guard.iter().position(|b| *b != GUARD_BYTE)?;
+    Some(HeapCorruption {
+        address,
+        size,
+        overrun,
+    })
+}
+
+/// Checks the guard bytes of every live allocation made through the crate's
+/// allocator wrappers and reports all allocations that have been overrun.
+///
+/// # Safety
+/// Every allocation made through [DLAllocatorRef](super::DLAllocatorRef) that
+/// was freed so far must have been freed through it as well. This doesn't
+/// hold once the game frees one of them itself, ex. by growing or destroying
+/// a container a mod allocated, since the guard bytes of that allocation are
+/// then read from freed memory.
+pub unsafe fn validate_heap() -> Vec<HeapCorruption> {
+    let allocations = ALLOCATIONS.lock().unwrap();
+    let corruptions = allocations
+        .iter()
+        // SAFETY: the caller guarantees every registered allocation is live.
+        .filter_map(|(address, size)| unsafe { check(*address, *size) })
+        .collect::<Vec<_>>();
+
+    for corruption in corruptions.iter() {
+        tracing::error!("Heap corruption detected: {corruption:x?}");
+    }
+
+    corruptions
+}
@@ -1,7 +1,8 @@
 use vtable_rs::VPtr;
+use windows::Win32::System::Threading::CRITICAL_SECTION;
+#[cfg(windows)]
 use windows::Win32::System::Threading::{
-    CRITICAL_SECTION, DeleteCriticalSection, EnterCriticalSection, InitializeCriticalSection,
-    LeaveCriticalSection,
+    DeleteCriticalSection, EnterCriticalSection, InitializeCriticalSection, LeaveCriticalSection,
 };
 
 #[vtable_rs::vtable]
@@ -16,6 +17,7 @@ pub struct DLPlainLightMutex {
     pub critical_section: CRITICAL_SECTION,
 }
 
+#[cfg(windows)]
 impl Default for DLPlainLightMutex {
     fn default() -> Self {
         let mut ins = Self {
@@ -29,12 +31,14 @@ impl Default for DLPlainLightMutex {
     }
 }
 
+#[cfg(windows)]
 impl Drop for DLPlainLightMutex {
     fn drop(&mut self) {
         unsafe { DeleteCriticalSection(&mut self.critical_section) }
     }
 }
 
+#[cfg(windows)]
 impl DLPlainLightMutex {
     pub fn lock(&mut self) {
         unsafe { EnterCriticalSection(&mut self.critical_section) }
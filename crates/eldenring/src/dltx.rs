@@ -3,7 +3,7 @@ use std::fmt::Display;
 use std::ops::{Deref, DerefMut};
 use std::ptr::NonNull;
 
-use crate::dlkr::DLAllocatorRef;
+use crate::dlkr::{DLAllocatorBase, DLAllocatorRef};
 
 use encoding_rs;
 
@@ -43,6 +43,55 @@ trait CxxString<CharType> {
     fn from_bytes_in(bytes: &[CharType], allocator: DLAllocatorRef) -> Self;
     fn as_u8_slice(&self) -> &[u8];
     fn as_bytes(&self) -> &[CharType];
+    fn allocator(&self) -> DLAllocatorRef;
+    /// Appends `chars` within the current capacity. Returns false without
+    /// changing the string if they don't fit.
+    fn extend_in_place(&mut self, chars: &[CharType]) -> bool;
+}
+
+/// The layout of MSVC's `basic_string` with a stateful allocator, which the
+/// cxx-stl strings share. Only used to read the allocator and to append
+/// without reallocating, since cxx-stl doesn't expose either.
+#[repr(C)]
+struct MsvcString {
+    allocator: DLAllocatorRef,
+    /// The characters themselves if they fit, a pointer to them otherwise.
+    buffer: [u8; 16],
+    len: usize,
+    capacity: usize,
+}
+
+impl MsvcString {
+    /// Appends `chars`, see [CxxString::extend_in_place].
+    ///
+    /// # Safety
+    ///
+    /// `C` has to be the string's character type.
+    unsafe fn extend_in_place<C: Copy + Default>(&mut self, chars: &[C]) -> bool {
+        let Some(len) = self.len.checked_add(chars.len()) else {
+            return false;
+        };
+        if len > self.capacity {
+            return false;
+        }
+
+        // Strings switch to a heap buffer once they don't fit into the
+        // inline one with their terminator.
+        let inline_capacity = (self.buffer.len() / size_of::<C>()).max(1);
+        let data = if self.capacity >= inline_capacity {
+            unsafe { *(self.buffer.as_ptr() as *const *mut C) }
+        } else {
+            self.buffer.as_mut_ptr() as *mut C
+        };
+
+        // The buffer always has room for the terminator past the capacity.
+        unsafe {
+            std::ptr::copy_nonoverlapping(chars.as_ptr(), data.add(self.len), chars.len());
+            data.add(len).write(C::default());
+        }
+        self.len = len;
+        true
+    }
 }
 
 macro_rules! impl_cxx_string {
@@ -66,6 +115,20 @@ macro_rules! impl_cxx_string {
             fn as_bytes(&self) -> &[$char_type] {
                 self.as_bytes()
             }
+            fn allocator(&self) -> DLAllocatorRef {
+                // SAFETY: The string has MsvcString's layout, which the
+                // layout test checks.
+                unsafe {
+                    (*(self as *const Self as *const MsvcString))
+                        .allocator
+                        .clone()
+                }
+            }
+
+            fn extend_in_place(&mut self, chars: &[$char_type]) -> bool {
+                // SAFETY: Same as above, and $char_type is its character type.
+                unsafe { (*(self as *mut Self as *mut MsvcString)).extend_in_place(chars) }
+            }
         }
     };
 }
@@ -81,7 +144,7 @@ trait DLStringKindSeal {}
 #[allow(private_bounds)]
 pub trait DLStringKind: DLStringKindSeal {
     type InnerType: CxxString<Self::CharType>;
-    type CharType: Sized + Copy;
+    type CharType: Sized + Copy + PartialEq;
     const ENCODING: DLCharacterSet;
 
     fn encode(s: &str) -> Result<Vec<Self::CharType>, DLStringEncodingError> {
@@ -246,6 +309,34 @@ impl<T: DLStringKind> DLString<T> {
         })
     }
 
+    /// Same as [DLString::from_str], for allocators that aren't wrapped in a
+    /// [DLAllocatorRef] yet.
+    pub fn from_str_in(
+        s: &str,
+        allocator: &mut DLAllocatorBase,
+    ) -> Result<Self, DLStringEncodingError> {
+        Self::from_str(NonNull::from(allocator).into(), s)
+    }
+
+    /// Appends `s`. If it doesn't fit into the current capacity, the string
+    /// is reallocated with the allocator it was created with.
+    pub fn push_str(&mut self, s: &str) -> Result<(), DLStringEncodingError> {
+        let encoded = T::encode(s)?;
+        if self.base.extend_in_place(&encoded) {
+            return Ok(());
+        }
+
+        let mut chars = Vec::with_capacity(self.base.as_bytes().len() + encoded.len());
+        chars.extend_from_slice(self.base.as_bytes());
+        chars.extend(encoded);
+        self.base = T::InnerType::from_bytes_in(&chars, self.base.allocator());
+        Ok(())
+    }
+
+    pub fn clear(&mut self) {
+        self.base = T::InnerType::new_in(self.base.allocator());
+    }
+
     pub fn to_str(&self) -> Result<String, DLStringEncodingError> {
         let bytes = self.base.as_u8_slice();
         T::decode(bytes).map(|cow| cow.into_owned())
@@ -294,6 +385,20 @@ impl<T: DLStringKind> Display for DLString<T> {
     }
 }
 
+/// Strings that can't be encoded with the string's character set are never
+/// equal to it.
+impl<T: DLStringKind> PartialEq<str> for DLString<T> {
+    fn eq(&self, other: &str) -> bool {
+        T::encode(other).is_ok_and(|encoded| self.base.as_bytes() == encoded.as_slice())
+    }
+}
+
+impl<T: DLStringKind> PartialEq<&str> for DLString<T> {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
 #[repr(C)]
 pub struct DLRawString<T: DLStringKind = DLUTF16StringKind> {
     vftable: usize,
@@ -349,3 +454,54 @@ impl<T: DLStringKind, const N: usize> Display for DLInplaceStr<T, N> {
         self.base.fmt(f)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::mem::{offset_of, size_of};
+    use std::ptr::NonNull;
+
+    use cxx_stl::string::{CxxNarrowString, CxxUtf16String};
+
+    use super::{CxxString, DLString, MsvcString};
+    use crate::dlkr::{DLAllocatorBase, DLAllocatorRef};
+
+    #[test]
+    fn strings_have_msvc_layout() {
+        assert_eq!(
+            size_of::<CxxUtf16String<DLAllocatorRef>>(),
+            size_of::<MsvcString>()
+        );
+        assert_eq!(
+            size_of::<CxxNarrowString<DLAllocatorRef>>(),
+            size_of::<MsvcString>()
+        );
+        assert_eq!(offset_of!(MsvcString, allocator), 0);
+        assert_eq!(size_of::<DLString>(), 0x30);
+
+        // Empty strings use the inline buffer, so nothing is allocated.
+        let allocator = NonNull::<DLAllocatorBase>::dangling();
+        let string = CxxUtf16String::new_in(DLAllocatorRef::from(allocator));
+        let read: NonNull<DLAllocatorBase> =
+            unsafe { std::mem::transmute(CxxString::<u16>::allocator(&string)) };
+        assert_eq!(read, allocator);
+        std::mem::forget(string);
+    }
+
+    #[test]
+    fn extends_inline_strings_in_place() {
+        let mut string = MsvcString {
+            allocator: DLAllocatorRef::from(NonNull::dangling()),
+            buffer: [0xFF; 16],
+            len: 2,
+            capacity: 7,
+        };
+        string.buffer[..4].copy_from_slice(&[b'a', 0, b'b', 0]);
+
+        assert!(unsafe { string.extend_in_place(&[u16::from(b'c')]) });
+        assert_eq!(string.len, 3);
+        assert_eq!(&string.buffer[..8], &[b'a', 0, b'b', 0, b'c', 0, 0, 0]);
+
+        assert!(!unsafe { string.extend_in_place(&[0u16; 5]) });
+        assert_eq!(string.len, 3);
+    }
+}
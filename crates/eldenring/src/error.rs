@@ -0,0 +1,21 @@
+use shared::InstanceError;
+use thiserror::Error;
+
+use crate::ContainerError;
+
+/// Errors shared by the crate's lookup and allocation paths, for callers that
+/// need to report why something isn't available rather than just that it
+/// isn't.
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("This game version or distribution is not supported")]
+    UnknownVersion,
+    #[error("The allocator returned no memory")]
+    AllocatorUnavailable,
+    #[error("The requested capacity is too large")]
+    CapacityOverflow,
+    #[error("Could not get instance: {0}")]
+    SingletonMissing(#[from] InstanceError),
+    #[error("Memory does not have the expected layout: {0}")]
+    LayoutMismatch(#[from] ContainerError),
+}
@@ -1,3 +1,5 @@
+use std::marker::PhantomData;
+
 use windows::core::PCSTR;
 
 use crate::param::ParamDef;
@@ -15,6 +17,45 @@ pub struct FD4ParamRepository {
 }
 
 impl FD4ParamRepository {
+    /// The first param with the definition `T`, for typed access to all of
+    /// its rows.
+    pub fn get_param<T: ParamDef>(&self) -> Option<TypedParam<'_, T>> {
+        let file_header = self
+            .res_rep
+            .res_cap_holder
+            .entries()
+            .find(|e| e.data.name().as_str().eq(T::NAME))?;
+
+        Some(TypedParam {
+            data: &file_header.data,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Like [Self::get_param] but for the param named `param_name`.
+    pub fn get_param_in<T: ParamDef>(&self, param_name: &str) -> Option<TypedParam<'_, T>> {
+        let file_header = self.res_rep.res_cap_holder.entries().find(|e| {
+            e.data.name().as_str().eq(T::NAME) && e.inner.name.to_string() == param_name
+        })?;
+
+        Some(TypedParam {
+            data: &file_header.data,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Every row of the first param with the definition `T` along with its
+    /// ID, in the order they're stored in.
+    pub fn rows_mut<T: ParamDef>(&mut self) -> impl Iterator<Item = (u32, &mut T)> {
+        self.res_rep
+            .res_cap_holder
+            .entries_mut()
+            .find(|e| e.data.name().as_str().eq(T::NAME))
+            .into_iter()
+            // SAFETY: the definition name is checked the same way as in get.
+            .flat_map(|e| unsafe { e.data.rows_mut::<T>() })
+    }
+
     pub fn get<T: ParamDef>(&self, id: u32) -> Option<&T> {
         let file_header = self
             .res_rep
@@ -36,6 +77,60 @@ impl FD4ParamRepository {
         // SAFETY: we shouldn't run into invalid casts because of the code gen dictating T::NAME.
         unsafe { file_header.data.get_mut(id) }
     }
+
+    /// Like [Self::get] but looks in the param named `param_name`, ex.
+    /// "ItemLotParam_enemy", for definitions shared between several params.
+    pub fn get_in<T: ParamDef>(&self, param_name: &str, id: u32) -> Option<&T> {
+        let file_header = self.res_rep.res_cap_holder.entries().find(|e| {
+            e.data.name().as_str().eq(T::NAME) && e.inner.name.to_string() == param_name
+        })?;
+
+        // SAFETY: the definition name is checked the same way as in get.
+        unsafe { file_header.data.get(id) }
+    }
+
+    /// Retrieves the IDs of every row in a param, in the order they're
+    /// stored in.
+    pub fn row_ids<T: ParamDef>(&self) -> Vec<u32> {
+        self.res_rep
+            .res_cap_holder
+            .entries()
+            .find(|e| e.data.name().as_str().eq(T::NAME))
+            .and_then(|e| e.table().ok())
+            .map(|table| table.ids().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Typed view of a loaded param, see [FD4ParamRepository::get_param].
+pub struct TypedParam<'a, T: ParamDef> {
+    data: &'a ParamData,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T: ParamDef> TypedParam<'a, T> {
+    pub fn get(&self, id: u32) -> Option<&'a T> {
+        // SAFETY: only created for params whose definition matches T::NAME.
+        unsafe { self.data.get(id) }
+    }
+
+    /// Every row along with its ID, in the order they're stored in.
+    pub fn rows(&self) -> impl Iterator<Item = (u32, &'a T)> + use<'a, T> {
+        // SAFETY: only created for params whose definition matches T::NAME.
+        unsafe { self.data.rows() }
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = u32> + use<'a, T> {
+        self.data.row_descriptors().iter().map(|r| r.id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.header.row_count as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 #[repr(C)]
@@ -100,6 +195,28 @@ impl ParamData {
         unsafe { Some(&*((self as *const _ as usize + offset) as *const T)) }
     }
 
+    /// Every row along with its ID, in the order they're stored in.
+    ///
+    /// # Safety
+    /// Caller has to ensure that the param type passed to T is valid for the param file.
+    pub unsafe fn rows<T: Sized>(&self) -> impl Iterator<Item = (u32, &T)> {
+        let base = self as *const _ as usize;
+        self.row_descriptors()
+            .iter()
+            .map(move |r| (r.id, unsafe { &*((base + r.data_offset) as *const T) }))
+    }
+
+    /// Like [Self::rows] but with mutable rows.
+    ///
+    /// # Safety
+    /// Caller has to ensure that the param type passed to T is valid for the param file.
+    pub unsafe fn rows_mut<T: Sized>(&mut self) -> impl Iterator<Item = (u32, &mut T)> {
+        let base = self as *mut _ as usize;
+        self.row_descriptors()
+            .iter()
+            .map(move |r| (r.id, unsafe { &mut *((base + r.data_offset) as *mut T) }))
+    }
+
     /// Retrieve a param entry mutable by its ID and type.
     ///
     /// # Safety
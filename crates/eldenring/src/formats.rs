@@ -0,0 +1,22 @@
+//! Readers and writers for the game's on-disk file formats. These don't
+//! depend on the game running and can be used from offline tools as well as
+//! from within a mod.
+
+mod fmg;
+mod param;
+mod paramdef;
+mod save_blob;
+#[cfg(feature = "save-data")]
+mod save_data;
+mod soundbank;
+mod texture_atlas;
+mod xml;
+
+pub use fmg::*;
+pub use param::*;
+pub use paramdef::*;
+pub use save_blob::*;
+#[cfg(feature = "save-data")]
+pub use save_data::*;
+pub use soundbank::*;
+pub use texture_atlas::*;
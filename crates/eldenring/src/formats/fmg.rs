@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+use thiserror::Error;
+
+/// Identifies a single message inside of an FMG. The same ID is used by the
+/// game's message repository lookups and by params referencing text.
+#[repr(transparent)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MsgId(pub i32);
+
+impl From<i32> for MsgId {
+    fn from(value: i32) -> Self {
+        MsgId(value)
+    }
+}
+
+impl Display for MsgId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum FmgError {
+    #[error("Unexpected end of data at offset {0:#x}")]
+    UnexpectedEof(usize),
+    #[error("Unsupported FMG version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Big endian FMGs are not supported")]
+    BigEndian,
+    #[error("Group {index} has an invalid ID range {first}..={last}")]
+    InvalidGroup { index: usize, first: i32, last: i32 },
+    #[error("String at offset {0:#x} is not valid UTF-16")]
+    InvalidString(usize),
+    #[error("Invalid offset {0:#x}")]
+    InvalidOffset(i64),
+}
+
+/// Version of the FMG format used by Elden Ring (and everything since DS3).
+const FMG_VERSION: u8 = 2;
+const HEADER_SIZE: usize = 0x28;
+const GROUP_SIZE: usize = 0x10;
+
+/// A message file, mapping message IDs to (possibly absent) strings.
+///
+/// Entries without text are kept around so that a file can be read and
+/// written back without losing any IDs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fmg {
+    entries: BTreeMap<MsgId, Option<String>>,
+}
+
+impl Fmg {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parses an unpacked (not DCX compressed) FMG file.
+    pub fn parse(bytes: &[u8]) -> Result<Self, FmgError> {
+        if read_u8(bytes, 0x1)? != 0 {
+            return Err(FmgError::BigEndian);
+        }
+
+        let version = read_u8(bytes, 0x2)?;
+        if version != FMG_VERSION {
+            return Err(FmgError::UnsupportedVersion(version));
+        }
+
+        let group_count = read_i32(bytes, 0xc)?.max(0) as usize;
+        let string_offsets_offset = to_offset(read_i64(bytes, 0x18)?)?;
+
+        let mut entries = BTreeMap::new();
+        for index in 0..group_count {
+            let group = HEADER_SIZE + index * GROUP_SIZE;
+            let offset_index = to_offset(read_i32(bytes, group)?.into())?;
+            let first = read_i32(bytes, group + 0x4)?;
+            let last = read_i32(bytes, group + 0x8)?;
+            if last < first {
+                return Err(FmgError::InvalidGroup { index, first, last });
+            }
+
+            for (i, id) in (first..=last).enumerate() {
+                let string_offset = offset_index
+                    .checked_add(i)
+                    .and_then(|index| index.checked_mul(0x8))
+                    .and_then(|offset| offset.checked_add(string_offsets_offset))
+                    .ok_or(FmgError::UnexpectedEof(string_offsets_offset))?;
+
+                let text = match to_offset(read_i64(bytes, string_offset)?)? {
+                    0 => None,
+                    offset => Some(read_utf16(bytes, offset)?),
+                };
+
+                entries.insert(MsgId(id), text);
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Serializes this FMG in the layout the game expects. IDs are grouped
+    /// into contiguous ranges the same way the official tooling does.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut groups: Vec<(usize, i32, i32)> = Vec::new();
+        for (index, id) in self.entries.keys().enumerate() {
+            match groups.last_mut() {
+                Some((_, _, last)) if last.checked_add(1) == Some(id.0) => *last = id.0,
+                _ => groups.push((index, id.0, id.0)),
+            }
+        }
+
+        let string_offsets_offset = HEADER_SIZE + groups.len() * GROUP_SIZE;
+        let strings_offset = string_offsets_offset + self.entries.len() * 0x8;
+
+        let mut offsets = Vec::with_capacity(self.entries.len());
+        let mut strings = Vec::new();
+        for text in self.entries.values() {
+            match text {
+                Some(text) => {
+                    offsets.push((strings_offset + strings.len()) as i64);
+                    strings.extend(text.encode_utf16().chain([0]).flat_map(u16::to_le_bytes));
+                }
+                None => offsets.push(0),
+            }
+        }
+
+        let file_size = strings_offset + strings.len();
+        let mut result = Vec::with_capacity(file_size);
+        result.extend([0, 0, FMG_VERSION, 0]);
+        result.extend((file_size as i32).to_le_bytes());
+        result.extend([1, 0, 0, 0]);
+        result.extend((groups.len() as i32).to_le_bytes());
+        result.extend((self.entries.len() as i32).to_le_bytes());
+        result.extend(0xffi32.to_le_bytes());
+        result.extend((string_offsets_offset as i64).to_le_bytes());
+        result.extend(0i64.to_le_bytes());
+
+        for (offset_index, first, last) in groups {
+            result.extend((offset_index as i32).to_le_bytes());
+            result.extend(first.to_le_bytes());
+            result.extend(last.to_le_bytes());
+            result.extend(0i32.to_le_bytes());
+        }
+
+        result.extend(offsets.into_iter().flat_map(i64::to_le_bytes));
+        result.extend(strings);
+        result
+    }
+
+    /// Retrieves the text for an entry. Returns None both when the entry is
+    /// missing and when it exists without any text.
+    pub fn get(&self, id: impl Into<MsgId>) -> Option<&str> {
+        self.entries.get(&id.into())?.as_deref()
+    }
+
+    /// Sets the text for an entry, returning the previous text if any.
+    pub fn insert(&mut self, id: impl Into<MsgId>, text: impl Into<String>) -> Option<String> {
+        self.entries.insert(id.into(), Some(text.into())).flatten()
+    }
+
+    /// Removes an entry entirely, including its ID.
+    pub fn remove(&mut self, id: impl Into<MsgId>) -> Option<String> {
+        self.entries.remove(&id.into()).flatten()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Yields all entries that have text, ordered by ID.
+    pub fn iter(&self) -> impl Iterator<Item = (MsgId, &str)> {
+        self.entries
+            .iter()
+            .filter_map(|(id, text)| Some((*id, text.as_deref()?)))
+    }
+}
+
+fn to_offset(value: i64) -> Result<usize, FmgError> {
+    usize::try_from(value).map_err(|_| FmgError::InvalidOffset(value))
+}
+
+fn read_bytes<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N], FmgError> {
+    let end = offset
+        .checked_add(N)
+        .ok_or(FmgError::UnexpectedEof(offset))?;
+    bytes
+        .get(offset..end)
+        .and_then(|b| b.try_into().ok())
+        .ok_or(FmgError::UnexpectedEof(offset))
+}
+
+fn read_u8(bytes: &[u8], offset: usize) -> Result<u8, FmgError> {
+    Ok(read_bytes::<1>(bytes, offset)?[0])
+}
+
+fn read_i32(bytes: &[u8], offset: usize) -> Result<i32, FmgError> {
+    Ok(i32::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+fn read_i64(bytes: &[u8], offset: usize) -> Result<i64, FmgError> {
+    Ok(i64::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+fn read_utf16(bytes: &[u8], offset: usize) -> Result<String, FmgError> {
+    let mut units = Vec::new();
+    let mut current = offset;
+    loop {
+        let unit = u16::from_le_bytes(read_bytes(bytes, current)?);
+        if unit == 0 {
+            break;
+        }
+        units.push(unit);
+        current += 2;
+    }
+
+    String::from_utf16(&units).map_err(|_| FmgError::InvalidString(offset))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Fmg, FmgError, MsgId};
+
+    #[test]
+    fn write_and_read_round_trip() {
+        let mut fmg = Fmg::new();
+        fmg.insert(100, "Margit, the Fell Omen");
+        fmg.insert(101, "Godrick the Grafted");
+        fmg.insert(200, "褪せ人");
+        fmg.insert(201, "");
+
+        let parsed = Fmg::parse(&fmg.to_bytes()).unwrap();
+        assert_eq!(parsed, fmg);
+        assert_eq!(parsed.get(200), Some("褪せ人"));
+        assert_eq!(
+            parsed.iter().map(|(id, _)| id).collect::<Vec<_>>(),
+            [MsgId(100), MsgId(101), MsgId(200), MsgId(201)]
+        );
+    }
+
+    #[test]
+    fn rejects_negative_offsets() {
+        let mut fmg = Fmg::new();
+        fmg.insert(100, "Margit, the Fell Omen");
+        let mut bytes = fmg.to_bytes();
+        bytes[0x28..0x2c].copy_from_slice(&(-1i32).to_le_bytes());
+        assert_eq!(Fmg::parse(&bytes), Err(FmgError::InvalidOffset(-1)));
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let bytes = Fmg::new().to_bytes();
+        assert_eq!(
+            Fmg::parse(&bytes[..0x10]),
+            Err(FmgError::UnexpectedEof(0x18))
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use std::ffi::CStr;
+
+use thiserror::Error;
+
+use crate::fd4::FD4ParamResCap;
+use crate::param::ParamDef;
+
+use super::{ParamDefinition, ParamValue};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParamTableError {
+    #[error("Unexpected end of data at offset {0:#x}")]
+    UnexpectedEof(usize),
+    #[error("Big endian params are not supported")]
+    BigEndian,
+    #[error("Param type name at offset {0:#x} is not valid")]
+    InvalidName(usize),
+    #[error("Row data for row {0} is out of bounds")]
+    InvalidRow(u32),
+}
+
+const HEADER_SIZE: usize = 0x40;
+const ROW_DESCRIPTOR_SIZE: usize = 0x18;
+const ENDIANNESS_OFFSET: usize = 0x2c;
+
+/// Read-only view over a single PARAM, either a standalone .param file
+/// or a param that the game has loaded from regulation.bin.
+///
+/// The game keeps params in memory in the same layout as they are stored on
+/// disk so both end up going through the same code path.
+#[derive(Clone, Copy, Debug)]
+pub struct ParamTable<'a> {
+    bytes: &'a [u8],
+    name: &'a str,
+    row_count: usize,
+    row_size: usize,
+}
+
+impl<'a> ParamTable<'a> {
+    /// Parses an unpacked (not DCX compressed) PARAM file.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, ParamTableError> {
+        if read_u8(bytes, ENDIANNESS_OFFSET)? != 0 {
+            return Err(ParamTableError::BigEndian);
+        }
+
+        let strings_offset = read_u32(bytes, 0x0)? as usize;
+        let row_count = read_u16(bytes, 0xa)? as usize;
+        let name_offset = read_u32(bytes, 0x10)? as usize;
+
+        let name = bytes
+            .get(name_offset..)
+            .and_then(|b| CStr::from_bytes_until_nul(b).ok())
+            .and_then(|s| s.to_str().ok())
+            .ok_or(ParamTableError::InvalidName(name_offset))?;
+
+        // Row descriptors have to be in bounds for every other accessor to
+        // be able to skip the checks.
+        read_bytes::<ROW_DESCRIPTOR_SIZE>(
+            bytes,
+            (HEADER_SIZE + row_count * ROW_DESCRIPTOR_SIZE).saturating_sub(ROW_DESCRIPTOR_SIZE),
+        )?;
+
+        // All rows in a param have the same size and are laid out back to
+        // back, followed by the row names.
+        let row_size = match row_count {
+            0 => 0,
+            _ => {
+                let data_start = read_u64(bytes, HEADER_SIZE + 0x8)? as usize;
+                strings_offset.saturating_sub(data_start) / row_count
+            }
+        };
+
+        Ok(Self {
+            bytes,
+            name,
+            row_count,
+            row_size,
+        })
+    }
+
+    /// The param type, which matches [ParamDef::NAME] for the generated
+    /// param structs.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    pub fn paramdef_version(&self) -> u16 {
+        u16::from_le_bytes([self.bytes[0x8], self.bytes[0x9]])
+    }
+
+    pub fn row_count(&self) -> usize {
+        self.row_count
+    }
+
+    pub fn row_size(&self) -> usize {
+        self.row_size
+    }
+
+    /// Yields all row IDs in the order they're stored in.
+    pub fn ids(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..self.row_count).map(|i| self.descriptor(i).0)
+    }
+
+    /// Yields every row alongside its ID.
+    pub fn rows(&self) -> impl Iterator<Item = (u32, &'a [u8])> + '_ {
+        (0..self.row_count).filter_map(|i| {
+            let (id, offset) = self.descriptor(i);
+            Some((id, self.row_bytes(offset)?))
+        })
+    }
+
+    /// Retrieves the raw bytes of a row by its ID.
+    pub fn row(&self, id: u32) -> Result<Option<&'a [u8]>, ParamTableError> {
+        let Some((_, offset)) = (0..self.row_count)
+            .map(|i| self.descriptor(i))
+            .find(|(row_id, _)| *row_id == id)
+        else {
+            return Ok(None);
+        };
+
+        self.row_bytes(offset)
+            .map(Some)
+            .ok_or(ParamTableError::InvalidRow(id))
+    }
+
+    /// Retrieves a copy of a row as its generated param struct. Returns None
+    /// if the param isn't of type T or the row doesn't exist.
+    pub fn get<T: ParamDef>(&self, id: u32) -> Option<T> {
+        if self.name != T::NAME {
+            return None;
+        }
+
+        let row = self.row(id).ok()??;
+        if row.len() < size_of::<T>() {
+            return None;
+        }
+
+        // SAFETY: generated param structs only consist of plain integers and
+        // floats so any bit pattern is valid, and the length was checked above.
+        Some(unsafe { std::ptr::read_unaligned(row.as_ptr() as *const T) })
+    }
+
+    /// Reads a single field from a row using a paramdef loaded at runtime.
+    pub fn field(&self, def: &ParamDefinition, id: u32, field: &str) -> Option<ParamValue> {
+        def.field(field)?.read(self.row(id).ok()??)
+    }
+
+    fn descriptor(&self, index: usize) -> (u32, u64) {
+        let start = HEADER_SIZE + index * ROW_DESCRIPTOR_SIZE;
+        let descriptor = &self.bytes[start..start + ROW_DESCRIPTOR_SIZE];
+
+        let id = u32::from_le_bytes(descriptor[0x0..0x4].try_into().unwrap());
+        let offset = u64::from_le_bytes(descriptor[0x8..0x10].try_into().unwrap());
+        (id, offset)
+    }
+
+    /// The data of the row at `offset`, None if any of it is out of bounds.
+    fn row_bytes(&self, offset: u64) -> Option<&'a [u8]> {
+        let start = usize::try_from(offset).ok()?;
+        self.bytes.get(start..start.checked_add(self.row_size)?)
+    }
+}
+
+impl FD4ParamResCap {
+    /// Exposes the loaded param through the same [ParamTable] used for files.
+    pub fn table(&self) -> Result<ParamTable<'_>, ParamTableError> {
+        // SAFETY: the game keeps the size of the allocation alongside the data.
+        let bytes = unsafe {
+            std::slice::from_raw_parts(self.data.as_ptr() as *const u8, self.size as usize)
+        };
+
+        ParamTable::parse(bytes)
+    }
+
+    /// Retrieves the raw bytes of a loaded row by its ID for editing.
+    pub fn row_mut(&mut self, id: u32) -> Result<Option<&mut [u8]>, ParamTableError> {
+        let base = self.data.as_ptr() as *mut u8;
+        let Some(row) = self.table()?.row(id)? else {
+            return Ok(None);
+        };
+        let (offset, len) = (row.as_ptr() as usize - base as usize, row.len());
+
+        // SAFETY: the row was bounds checked against the allocation by the
+        // table, and self is borrowed mutably for as long as it's used.
+        Ok(Some(unsafe {
+            std::slice::from_raw_parts_mut(base.add(offset), len)
+        }))
+    }
+}
+
+fn read_bytes<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N], ParamTableError> {
+    let end = offset
+        .checked_add(N)
+        .ok_or(ParamTableError::UnexpectedEof(offset))?;
+    bytes
+        .get(offset..end)
+        .and_then(|b| b.try_into().ok())
+        .ok_or(ParamTableError::UnexpectedEof(offset))
+}
+
+fn read_u8(bytes: &[u8], offset: usize) -> Result<u8, ParamTableError> {
+    Ok(read_bytes::<1>(bytes, offset)?[0])
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, ParamTableError> {
+    Ok(u16::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, ParamTableError> {
+    Ok(u32::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Result<u64, ParamTableError> {
+    Ok(u64::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ParamTable, ParamTableError};
+    use crate::formats::{ParamDefinition, ParamValue};
+
+    const PARAMDEF: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<PARAMDEF XmlVersion="2">
+  <ParamType>TEST_PARAM_ST</ParamType>
+  <Fields>
+    <Field Def="s32 textId = -1" />
+    <Field Def="u8 isEnabled:1" />
+    <Field Def="u8 isHidden:1" />
+    <Field Def="dummy8 pad:6" />
+    <Field Def="dummy8 oldField[4]" RemovedVersion="10000" />
+    <Field Def="f32 radius" />
+  </Fields>
+</PARAMDEF>"#;
+
+    /// Lays out a PARAM file the same way the official tooling does: header,
+    /// row descriptors, row data and finally the strings.
+    fn build_param(rows: &[(u32, [u8; 12])]) -> Vec<u8> {
+        let data_start = 0x40 + rows.len() * 0x18;
+        let strings_offset = data_start + rows.len() * 12;
+        let name = b"TEST_PARAM_ST\0";
+
+        let mut bytes = vec![0u8; 0x40];
+        bytes[0x0..0x4].copy_from_slice(&(strings_offset as u32).to_le_bytes());
+        bytes[0xa..0xc].copy_from_slice(&(rows.len() as u16).to_le_bytes());
+        bytes[0x10..0x14].copy_from_slice(&(strings_offset as u32).to_le_bytes());
+        bytes[0x2d] = 0x85;
+        bytes[0x2f] = 5;
+
+        for (index, (id, _)) in rows.iter().enumerate() {
+            bytes.extend(id.to_le_bytes());
+            bytes.extend(0u32.to_le_bytes());
+            bytes.extend(((data_start + index * 12) as u64).to_le_bytes());
+            bytes.extend(0u64.to_le_bytes());
+        }
+        for (_, data) in rows {
+            bytes.extend(data);
+        }
+        bytes.extend(name);
+        bytes
+    }
+
+    #[test]
+    fn reads_rows_with_paramdef() {
+        let mut row = [0u8; 12];
+        row[0x0..0x4].copy_from_slice(&1234i32.to_le_bytes());
+        row[0x4] = 0b10;
+        row[0x8..0xc].copy_from_slice(&2.5f32.to_le_bytes());
+
+        let bytes = build_param(&[(10, [0u8; 12]), (20, row)]);
+        let table = ParamTable::parse(&bytes).unwrap();
+        let def = ParamDefinition::from_xml(PARAMDEF).unwrap();
+
+        assert_eq!(table.name(), "TEST_PARAM_ST");
+        assert_eq!(table.ids().collect::<Vec<_>>(), [10, 20]);
+        assert_eq!(table.row_size(), def.row_size());
+        assert_eq!(table.row(30), Ok(None));
+        assert_eq!(
+            table.field(&def, 20, "textId"),
+            Some(ParamValue::Signed(1234))
+        );
+        assert_eq!(
+            table.field(&def, 20, "isEnabled"),
+            Some(ParamValue::Unsigned(0))
+        );
+        assert_eq!(
+            table.field(&def, 20, "isHidden"),
+            Some(ParamValue::Unsigned(1))
+        );
+        assert_eq!(
+            table.field(&def, 20, "radius"),
+            Some(ParamValue::Float(2.5))
+        );
+        assert_eq!(table.field(&def, 20, "oldField"), None);
+    }
+
+    #[test]
+    fn rejects_out_of_bounds_rows() {
+        let mut bytes = build_param(&[(10, [0u8; 12]), (20, [0u8; 12])]);
+        bytes[0x58 + 0x8..0x58 + 0x10].copy_from_slice(&u64::MAX.to_le_bytes());
+        let table = ParamTable::parse(&bytes).unwrap();
+
+        assert_eq!(table.row(20), Err(ParamTableError::InvalidRow(20)));
+        assert_eq!(table.rows().map(|(id, _)| id).collect::<Vec<_>>(), [10]);
+    }
+
+    #[test]
+    fn writes_fields_with_paramdef() {
+        let def = ParamDefinition::from_xml(PARAMDEF).unwrap();
+        let mut row = [0u8; 12];
+
+        let text_id = def.field("textId").unwrap();
+        assert!(text_id.write(&mut row, &ParamValue::Signed(-5)));
+        assert_eq!(text_id.read(&row), Some(ParamValue::Signed(-5)));
+
+        let is_hidden = def.field("isHidden").unwrap();
+        assert!(is_hidden.write(&mut row, &ParamValue::Unsigned(1)));
+        assert!(!is_hidden.write(&mut row, &ParamValue::Unsigned(2)));
+        assert_eq!(row[0x4], 0b10);
+
+        let radius = def.field("radius").unwrap();
+        assert!(!radius.write(&mut row, &ParamValue::Signed(1)));
+        assert!(radius.write(&mut row, &ParamValue::Float(0.5)));
+        assert_eq!(radius.read(&row), Some(ParamValue::Float(0.5)));
+    }
+
+    #[test]
+    fn lays_out_bitfields_by_declared_type() {
+        let def = ParamDefinition::from_xml(
+            r#"<PARAMDEF>
+  <ParamType>BITS_PARAM_ST</ParamType>
+  <Fields>
+    <Field Def="u8 flag:1" />
+    <Field Def="u16 low:10" />
+    <Field Def="u16 high:6" />
+    <Field Def="u32 wide:20" />
+    <Field Def="u8 after" />
+  </Fields>
+</PARAMDEF>"#,
+        )
+        .unwrap();
+
+        let layout = def
+            .fields
+            .iter()
+            .map(|field| (field.offset, field.bits))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            layout,
+            [
+                (0, Some((0, 1))),
+                (2, Some((0, 10))),
+                (2, Some((10, 6))),
+                (4, Some((0, 20))),
+                (8, None)
+            ]
+        );
+        assert_eq!(def.row_size(), 9);
+
+        let mut row = [0u8; 9];
+        assert!(
+            def.field("high")
+                .unwrap()
+                .write(&mut row, &ParamValue::Unsigned(0b111111))
+        );
+        assert!(
+            def.field("wide")
+                .unwrap()
+                .write(&mut row, &ParamValue::Unsigned(0xFFFFF))
+        );
+        assert_eq!(row[2..8], [0x00, 0xFC, 0xFF, 0xFF, 0x0F, 0x00]);
+        assert_eq!(
+            def.field("low").unwrap().read(&row),
+            Some(ParamValue::Unsigned(0))
+        );
+        assert_eq!(
+            def.field("high").unwrap().read(&row),
+            Some(ParamValue::Unsigned(0b111111))
+        );
+    }
+
+    #[test]
+    fn rejects_truncated_data() {
+        let bytes = build_param(&[(10, [0u8; 12])]);
+        assert_eq!(
+            ParamTable::parse(&bytes[..0x30]).unwrap_err(),
+            ParamTableError::InvalidName(0x64)
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use thiserror::Error;
+
+use super::xml::{attribute, element_text, tags};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParamDefinitionError {
+    #[error("Paramdef is missing its <ParamType>")]
+    MissingParamType,
+    #[error("Field definition \"{0}\" could not be parsed")]
+    InvalidField(String),
+    #[error("Unknown field type \"{0}\"")]
+    UnknownType(String),
+}
+
+/// Primitive type of a paramdef field as it's spelled in the Paramdex XMLs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParamFieldType {
+    S8,
+    U8,
+    S16,
+    U16,
+    S32,
+    U32,
+    F32,
+    Dummy8,
+    /// Fixed length Shift-JIS string.
+    FixStr,
+    /// Fixed length UTF-16 string.
+    FixStrW,
+}
+
+impl ParamFieldType {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "s8" => Self::S8,
+            "u8" => Self::U8,
+            "s16" => Self::S16,
+            "u16" => Self::U16,
+            "s32" => Self::S32,
+            "u32" => Self::U32,
+            "f32" => Self::F32,
+            "dummy8" => Self::Dummy8,
+            "fixstr" => Self::FixStr,
+            "fixstrW" => Self::FixStrW,
+            _ => return None,
+        })
+    }
+
+    /// Size in bytes of a single element, which is also its alignment.
+    pub fn size(&self) -> usize {
+        match self {
+            Self::S8 | Self::U8 | Self::Dummy8 | Self::FixStr => 1,
+            Self::S16 | Self::U16 | Self::FixStrW => 2,
+            Self::S32 | Self::U32 | Self::F32 => 4,
+        }
+    }
+}
+
+/// A value read from a row through a [ParamDefinition].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamValue {
+    Signed(i32),
+    Unsigned(u32),
+    Float(f32),
+    String(String),
+    Array(Vec<ParamValue>),
+}
+
+/// A single field with its computed location inside of a row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamField {
+    pub name: String,
+    pub field_type: ParamFieldType,
+    /// Byte offset of the field inside of the row.
+    pub offset: usize,
+    /// Amount of elements for arrays and strings, 1 otherwise.
+    pub count: usize,
+    /// Bit offset and width for bitfields. Bitfields are stored in a unit of
+    /// their declared type at [ParamField::offset].
+    pub bits: Option<(u8, u8)>,
+}
+
+impl ParamField {
+    /// Reads the storage unit of a bitfield.
+    fn read_unit(&self, row: &[u8]) -> Option<u32> {
+        let data = row.get(self.offset..self.offset + self.field_type.size())?;
+        let mut bytes = [0; 4];
+        bytes[..data.len()].copy_from_slice(data);
+        Some(u32::from_le_bytes(bytes))
+    }
+
+    /// Reads the field from a row's raw bytes.
+    pub fn read(&self, row: &[u8]) -> Option<ParamValue> {
+        if let Some((bit_offset, width)) = self.bits {
+            let unit = self.read_unit(row)?;
+            let mask = bit_mask(width);
+            return Some(ParamValue::Unsigned((unit >> bit_offset) & mask));
+        }
+
+        let size = self.field_type.size();
+        let data = row.get(self.offset..self.offset + size * self.count)?;
+
+        match self.field_type {
+            ParamFieldType::FixStr => {
+                let end = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+                let (text, _, _) = encoding_rs::SHIFT_JIS.decode(&data[..end]);
+                Some(ParamValue::String(text.into_owned()))
+            }
+            ParamFieldType::FixStrW => {
+                let units = data
+                    .chunks_exact(2)
+                    .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                    .take_while(|u| *u != 0)
+                    .collect::<Vec<_>>();
+                Some(ParamValue::String(String::from_utf16_lossy(&units)))
+            }
+            _ if self.count > 1 => Some(ParamValue::Array(
+                data.chunks_exact(size)
+                    .map(|c| read_scalar(self.field_type, c))
+                    .collect(),
+            )),
+            _ => Some(read_scalar(self.field_type, data)),
+        }
+    }
+
+    /// Writes a single value into a row's raw bytes. Returns false if the
+    /// value doesn't fit the field, ex. for strings, arrays or a bitfield
+    /// too narrow for it.
+    pub fn write(&self, row: &mut [u8], value: &ParamValue) -> bool {
+        if let Some((bit_offset, width)) = self.bits {
+            let ParamValue::Unsigned(value) = value else {
+                return false;
+            };
+            let mask = bit_mask(width);
+            let Some(unit) = self.read_unit(row) else {
+                return false;
+            };
+            if *value > mask {
+                return false;
+            }
+
+            let unit = (unit & !(mask << bit_offset)) | (value << bit_offset);
+            let size = self.field_type.size();
+            row[self.offset..self.offset + size].copy_from_slice(&unit.to_le_bytes()[..size]);
+            return true;
+        }
+
+        if self.count != 1 {
+            return false;
+        }
+
+        let size = self.field_type.size();
+        let Some(data) = row.get_mut(self.offset..self.offset + size) else {
+            return false;
+        };
+
+        let bytes = match (self.field_type, value) {
+            (ParamFieldType::S8, ParamValue::Signed(v)) => {
+                i8::try_from(*v).map(|v| v.to_le_bytes().to_vec()).ok()
+            }
+            (ParamFieldType::U8 | ParamFieldType::Dummy8, ParamValue::Unsigned(v)) => {
+                u8::try_from(*v).map(|v| v.to_le_bytes().to_vec()).ok()
+            }
+            (ParamFieldType::S16, ParamValue::Signed(v)) => {
+                i16::try_from(*v).map(|v| v.to_le_bytes().to_vec()).ok()
+            }
+            (ParamFieldType::U16, ParamValue::Unsigned(v)) => {
+                u16::try_from(*v).map(|v| v.to_le_bytes().to_vec()).ok()
+            }
+            (ParamFieldType::S32, ParamValue::Signed(v)) => Some(v.to_le_bytes().to_vec()),
+            (ParamFieldType::U32, ParamValue::Unsigned(v)) => Some(v.to_le_bytes().to_vec()),
+            (ParamFieldType::F32, ParamValue::Float(v)) => Some(v.to_le_bytes().to_vec()),
+            _ => None,
+        };
+
+        match bytes {
+            Some(bytes) => {
+                data.copy_from_slice(&bytes);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+fn bit_mask(width: u8) -> u32 {
+    u32::MAX >> (32 - width as u32)
+}
+
+fn read_scalar(field_type: ParamFieldType, data: &[u8]) -> ParamValue {
+    match field_type {
+        ParamFieldType::S8 => ParamValue::Signed(data[0] as i8 as i32),
+        ParamFieldType::U8 | ParamFieldType::Dummy8 | ParamFieldType::FixStr => {
+            ParamValue::Unsigned(data[0] as u32)
+        }
+        ParamFieldType::S16 => ParamValue::Signed(i16::from_le_bytes([data[0], data[1]]) as i32),
+        ParamFieldType::U16 | ParamFieldType::FixStrW => {
+            ParamValue::Unsigned(u16::from_le_bytes([data[0], data[1]]) as u32)
+        }
+        ParamFieldType::S32 => ParamValue::Signed(i32::from_le_bytes(data.try_into().unwrap())),
+        ParamFieldType::U32 => ParamValue::Unsigned(u32::from_le_bytes(data.try_into().unwrap())),
+        ParamFieldType::F32 => ParamValue::Float(f32::from_le_bytes(data.try_into().unwrap())),
+    }
+}
+
+/// Row layout of a param type, loaded at runtime from a Paramdex XML.
+///
+/// The layout rules match the ones used by the param generator, so a
+/// definition loaded here lines up with the structs in [crate::param].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParamDefinition {
+    pub param_type: String,
+    pub fields: Vec<ParamField>,
+    row_size: usize,
+}
+
+impl ParamDefinition {
+    /// Parses a Paramdex style paramdef XML. Fields that have been removed in
+    /// a later version of the game are skipped.
+    pub fn from_xml(xml: &str) -> Result<Self, ParamDefinitionError> {
+        let param_type = element_text(xml, "ParamType")
+            .ok_or(ParamDefinitionError::MissingParamType)?
+            .to_string();
+
+        let mut fields = Vec::new();
+        let mut offset: usize = 0;
+        // Offset and size of the storage unit being filled with bitfields, and
+        // how many of its bits are used.
+        let mut bit_cursor: Option<(usize, usize, u8)> = None;
+
+        for tag in tags(xml, "Field") {
+            if attribute(tag, "RemovedVersion").is_some() {
+                continue;
+            }
+
+            let def = attribute(tag, "Def")
+                .ok_or_else(|| ParamDefinitionError::InvalidField(tag.to_string()))?;
+            let (type_name, name, bit_width, count) = parse_def(def)?;
+            let field_type = ParamFieldType::from_name(type_name)
+                .ok_or_else(|| ParamDefinitionError::UnknownType(type_name.to_string()))?;
+
+            let size = field_type.size();
+            match bit_width {
+                Some(width) => {
+                    if width as usize > size * 8 {
+                        return Err(ParamDefinitionError::InvalidField(def.to_string()));
+                    }
+
+                    // Like MSVC and the param generator, consecutive bitfields
+                    // share a storage unit as long as their declared types have
+                    // the same size and there are bits left, otherwise a new
+                    // unit of the declared type starts.
+                    let (unit_offset, used) = match bit_cursor {
+                        Some((unit_offset, unit_size, used))
+                            if unit_size == size && used as usize + width as usize <= size * 8 =>
+                        {
+                            (unit_offset, used)
+                        }
+                        Some((unit_offset, unit_size, _)) => {
+                            ((unit_offset + unit_size).next_multiple_of(size), 0)
+                        }
+                        None => (offset.next_multiple_of(size), 0),
+                    };
+
+                    fields.push(ParamField {
+                        name: name.to_string(),
+                        field_type,
+                        offset: unit_offset,
+                        count: 1,
+                        bits: Some((used, width)),
+                    });
+                    bit_cursor = Some((unit_offset, size, used + width));
+                }
+                None => {
+                    // Close the storage unit of any bitfields before this
+                    // field.
+                    if let Some((unit_offset, unit_size, _)) = bit_cursor.take() {
+                        offset = unit_offset + unit_size;
+                    }
+
+                    offset = offset.next_multiple_of(size);
+                    fields.push(ParamField {
+                        name: name.to_string(),
+                        field_type,
+                        offset,
+                        count,
+                        bits: None,
+                    });
+                    offset += size * count;
+                }
+            }
+        }
+
+        if let Some((unit_offset, unit_size, _)) = bit_cursor {
+            offset = unit_offset + unit_size;
+        }
+
+        Ok(Self {
+            param_type,
+            fields,
+            row_size: offset,
+        })
+    }
+
+    /// Size of a single row in bytes.
+    pub fn row_size(&self) -> usize {
+        self.row_size
+    }
+
+    /// Looks up a field by its name as spelled in the paramdef.
+    pub fn field(&self, name: &str) -> Option<&ParamField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+}
+
+/// Splits a field definition like `u8 name:1`, `dummy8 pad[3]` or
+/// `s32 id = -1` into its type, name, bit width and element count.
+fn parse_def(def: &str) -> Result<(&str, &str, Option<u8>, usize), ParamDefinitionError> {
+    let invalid = || ParamDefinitionError::InvalidField(def.to_string());
+
+    let main = def.split('=').next().unwrap_or(def).trim();
+    let (type_name, rest) = main.split_once(char::is_whitespace).ok_or_else(invalid)?;
+    let rest = rest.trim();
+
+    if let Some((name, width)) = rest.split_once(':') {
+        let width = width.trim().parse::<u8>().map_err(|_| invalid())?;
+        if width == 0 || width > 32 {
+            return Err(invalid());
+        }
+        return Ok((type_name, name.trim(), Some(width), 1));
+    }
+
+    if let Some((name, count)) = rest.split_once('[') {
+        let count = count
+            .trim_end_matches(']')
+            .parse::<usize>()
+            .map_err(|_| invalid())?;
+        return Ok((type_name, name.trim(), None, count));
+    }
+
+    Ok((type_name, rest, None, 1))
+}
@@ -0,0 +1,169 @@
+use md5::{Digest, Md5};
+use thiserror::Error;
+
+/// Each character slot in the save file starts with an MD5 checksum of the
+/// rest of the slot.
+pub const SAVE_SLOT_CHECKSUM_LEN: usize = 0x10;
+/// Largest blob [write_save_blob] accepts.
+pub const MAX_SAVE_BLOB_LEN: usize = 0x10000;
+
+const MAGIC: &[u8; 8] = b"MODBLOB\0";
+/// Blob length, blob checksum and magic, stored after the blob itself.
+const FOOTER_LEN: usize = 4 + 0x10 + MAGIC.len();
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SaveBlobError {
+    #[error("Save slot of {0:#x} bytes is too small")]
+    SlotTooSmall(usize),
+    #[error("Blob of {0:#x} bytes is larger than the maximum")]
+    BlobTooLarge(usize),
+    #[error("Save slot data at offset {0:#x} is in use")]
+    RegionInUse(usize),
+    #[error("Blob checksum doesn't match its data")]
+    Corrupted,
+}
+
+/// Reads the blob stored at the end of a character slot, None if there is
+/// none.
+///
+/// `slot` is a whole character slot as stored in the save file, checksum
+/// included. Slots are fixed size and the game's data is followed by zero
+/// padding, which is where the blob goes. The game writes the slot from
+/// scratch whenever it saves, so the blob has to be written again after every
+/// save.
+pub fn read_save_blob(slot: &[u8]) -> Result<Option<&[u8]>, SaveBlobError> {
+    let Some(footer_start) = slot.len().checked_sub(FOOTER_LEN) else {
+        return Err(SaveBlobError::SlotTooSmall(slot.len()));
+    };
+    let footer = &slot[footer_start..];
+    if &footer[0x14..] != MAGIC {
+        return Ok(None);
+    }
+
+    let len = u32::from_le_bytes(footer[..0x4].try_into().unwrap()) as usize;
+    let blob = footer_start
+        .checked_sub(len)
+        .filter(|start| *start >= SAVE_SLOT_CHECKSUM_LEN)
+        .map(|start| &slot[start..footer_start])
+        .ok_or(SaveBlobError::Corrupted)?;
+
+    if md5(blob) != footer[0x4..0x14] {
+        return Err(SaveBlobError::Corrupted);
+    }
+    Ok(Some(blob))
+}
+
+/// Stores `blob` at the end of a character slot, replacing any previous blob,
+/// and updates the slot's checksum. Fails without changing the slot if the
+/// game's data reaches into the space the blob needs.
+pub fn write_save_blob(slot: &mut [u8], blob: &[u8]) -> Result<(), SaveBlobError> {
+    if blob.len() > MAX_SAVE_BLOB_LEN {
+        return Err(SaveBlobError::BlobTooLarge(blob.len()));
+    }
+
+    let needed = blob.len() + FOOTER_LEN;
+    let start = slot
+        .len()
+        .checked_sub(needed)
+        .filter(|start| *start >= SAVE_SLOT_CHECKSUM_LEN)
+        .ok_or(SaveBlobError::SlotTooSmall(slot.len()))?;
+
+    // Whatever part of the region the previous blob doesn't cover has to be
+    // padding.
+    let free_from = match read_save_blob(slot) {
+        Ok(Some(previous)) => slot.len() - previous.len() - FOOTER_LEN,
+        _ => slot.len(),
+    };
+    if let Some(offset) = (start..free_from).find(|offset| slot[*offset] != 0) {
+        return Err(SaveBlobError::RegionInUse(offset));
+    }
+
+    slot[free_from.min(start)..].fill(0);
+    let footer_start = start + blob.len();
+    slot[start..footer_start].copy_from_slice(blob);
+    slot[footer_start..footer_start + 0x4].copy_from_slice(&(blob.len() as u32).to_le_bytes());
+    slot[footer_start + 0x4..footer_start + 0x14].copy_from_slice(&md5(blob));
+    slot[footer_start + 0x14..].copy_from_slice(MAGIC);
+
+    update_save_slot_checksum(slot);
+    Ok(())
+}
+
+/// Removes the blob from a character slot, returning whether there was one.
+pub fn remove_save_blob(slot: &mut [u8]) -> Result<bool, SaveBlobError> {
+    let Some(len) = read_save_blob(slot)?.map(|blob| blob.len()) else {
+        return Ok(false);
+    };
+
+    let start = slot.len() - len - FOOTER_LEN;
+    slot[start..].fill(0);
+    update_save_slot_checksum(slot);
+    Ok(true)
+}
+
+pub fn save_slot_checksum_valid(slot: &[u8]) -> bool {
+    slot.len() >= SAVE_SLOT_CHECKSUM_LEN
+        && md5(&slot[SAVE_SLOT_CHECKSUM_LEN..]) == slot[..SAVE_SLOT_CHECKSUM_LEN]
+}
+
+pub fn update_save_slot_checksum(slot: &mut [u8]) {
+    let checksum = md5(&slot[SAVE_SLOT_CHECKSUM_LEN..]);
+    slot[..SAVE_SLOT_CHECKSUM_LEN].copy_from_slice(&checksum);
+}
+
+fn md5(data: &[u8]) -> [u8; 16] {
+    Md5::digest(data).into()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn md5_matches_reference() {
+        let hex = |digest: [u8; 16]| {
+            digest
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<String>()
+        };
+        assert_eq!(hex(md5(b"")), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(
+            hex(md5(b"The quick brown fox jumps over the lazy dog")),
+            "9e107d9d372bb6826bd81d3542a419d6"
+        );
+    }
+
+    #[test]
+    fn write_replace_and_remove() {
+        let mut slot = vec![0u8; 0x200];
+        slot[0x10..0x40].fill(0xAB);
+        update_save_slot_checksum(&mut slot);
+
+        assert_eq!(read_save_blob(&slot), Ok(None));
+        write_save_blob(&mut slot, b"seed=1234").unwrap();
+        assert_eq!(read_save_blob(&slot), Ok(Some(&b"seed=1234"[..])));
+        assert!(save_slot_checksum_valid(&slot));
+
+        write_save_blob(&mut slot, b"seed=5").unwrap();
+        assert_eq!(read_save_blob(&slot), Ok(Some(&b"seed=5"[..])));
+
+        assert_eq!(remove_save_blob(&mut slot), Ok(true));
+        assert_eq!(read_save_blob(&slot), Ok(None));
+        assert!(slot[0x40..].iter().all(|b| *b == 0));
+        assert!(save_slot_checksum_valid(&slot));
+    }
+
+    #[test]
+    fn refuses_to_overwrite_game_data() {
+        let mut slot = vec![0u8; 0x80];
+        slot[0x10..0x70].fill(0xAB);
+        let before = slot.clone();
+
+        assert_eq!(
+            write_save_blob(&mut slot, b"seed"),
+            Err(SaveBlobError::RegionInUse(0x60))
+        );
+        assert_eq!(slot, before);
+    }
+}
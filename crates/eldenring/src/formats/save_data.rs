@@ -0,0 +1,209 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+use toml::{Table, Value};
+
+use super::{SaveBlobError, read_save_blob, write_save_blob};
+
+#[derive(Error, Debug)]
+pub enum SaveDataError {
+    #[error(transparent)]
+    Blob(#[from] SaveBlobError),
+    #[error("Save data is not valid UTF-8")]
+    Encoding,
+    #[error("Could not parse save data: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Could not serialize save data: {0}")]
+    Serialize(#[from] toml::ser::Error),
+    #[error("Entry {0} is malformed")]
+    InvalidEntry(String),
+    #[error("Entry {namespace} was written by version {version}, which is newer than this mod")]
+    NewerVersion { namespace: String, version: u32 },
+    #[error("Entry {namespace} can't be migrated from version {version}")]
+    Migration { namespace: String, version: u32 },
+}
+
+/// Data a mod keeps in the save file under its own namespace.
+pub trait SaveSchema: Serialize + DeserializeOwned {
+    /// Unique name of the entry, ex. the mod's crate name.
+    const NAMESPACE: &'static str;
+    /// Bumped whenever the layout of the type changes.
+    const VERSION: u32;
+
+    /// Upgrades an entry written by an older version, one version at a time.
+    /// `value` is in the layout of `version` and has to be returned in the
+    /// layout of `version + 1`. Entries can't be migrated by default.
+    fn migrate(version: u32, value: Value) -> Result<Value, SaveDataError> {
+        let _ = value;
+        Err(SaveDataError::Migration {
+            namespace: Self::NAMESPACE.to_string(),
+            version,
+        })
+    }
+}
+
+/// Entries of every mod sharing a character slot's [save blob](super::read_save_blob).
+///
+/// Each entry is stored with the version that wrote it, and [SaveData::get]
+/// brings older entries up to date through [SaveSchema::migrate]. Entries of
+/// other mods are kept as they are, so mods only ever replace their own.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SaveData {
+    entries: Table,
+}
+
+impl SaveData {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Self, SaveDataError> {
+        let text = std::str::from_utf8(bytes).map_err(|_| SaveDataError::Encoding)?;
+        Ok(Self {
+            entries: toml::from_str(text)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SaveDataError> {
+        Ok(toml::to_string(&self.entries)?.into_bytes())
+    }
+
+    /// Reads the entries from a character slot, empty if it has no blob.
+    pub fn from_slot(slot: &[u8]) -> Result<Self, SaveDataError> {
+        match read_save_blob(slot)? {
+            Some(blob) => Self::parse(blob),
+            None => Ok(Self::new()),
+        }
+    }
+
+    pub fn write_to_slot(&self, slot: &mut [u8]) -> Result<(), SaveDataError> {
+        Ok(write_save_blob(slot, &self.to_bytes()?)?)
+    }
+
+    /// Reads the entry of `T`, migrating it if it was written by an older
+    /// version. None if there is no entry.
+    pub fn get<T: SaveSchema>(&self) -> Result<Option<T>, SaveDataError> {
+        let Some(entry) = self.entries.get(T::NAMESPACE) else {
+            return Ok(None);
+        };
+        let invalid = || SaveDataError::InvalidEntry(T::NAMESPACE.to_string());
+
+        let mut version = entry
+            .get("version")
+            .and_then(Value::as_integer)
+            .and_then(|version| u32::try_from(version).ok())
+            .ok_or_else(invalid)?;
+        if version > T::VERSION {
+            return Err(SaveDataError::NewerVersion {
+                namespace: T::NAMESPACE.to_string(),
+                version,
+            });
+        }
+
+        let mut value = entry.get("data").cloned().ok_or_else(invalid)?;
+        while version < T::VERSION {
+            value = T::migrate(version, value)?;
+            version += 1;
+        }
+
+        Ok(Some(value.try_into()?))
+    }
+
+    /// Replaces the entry of `T` with `value` at the current version.
+    pub fn set<T: SaveSchema>(&mut self, value: &T) -> Result<(), SaveDataError> {
+        let mut entry = Table::new();
+        entry.insert("version".to_string(), Value::Integer(T::VERSION.into()));
+        entry.insert("data".to_string(), Value::try_from(value)?);
+
+        self.entries
+            .insert(T::NAMESPACE.to_string(), Value::Table(entry));
+        Ok(())
+    }
+
+    /// Removes an entry, returning whether there was one.
+    pub fn remove(&mut self, namespace: &str) -> bool {
+        self.entries.remove(namespace).is_some()
+    }
+
+    pub fn namespaces(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Version the entry was written by, None if there is no entry.
+    pub fn version(&self, namespace: &str) -> Option<u32> {
+        self.entries
+            .get(namespace)?
+            .get("version")?
+            .as_integer()
+            .and_then(|version| u32::try_from(version).ok())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{Deserialize, Serialize};
+    use toml::Value;
+
+    use super::{SaveData, SaveDataError, SaveSchema};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct SeedV1 {
+        seed: u64,
+    }
+
+    impl SaveSchema for SeedV1 {
+        const NAMESPACE: &'static str = "randomizer";
+        const VERSION: u32 = 1;
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct SeedV2 {
+        seed: u64,
+        key_items_only: bool,
+    }
+
+    impl SaveSchema for SeedV2 {
+        const NAMESPACE: &'static str = "randomizer";
+        const VERSION: u32 = 2;
+
+        fn migrate(version: u32, mut value: Value) -> Result<Value, SaveDataError> {
+            assert_eq!(version, 1);
+            value
+                .as_table_mut()
+                .unwrap()
+                .insert("key_items_only".to_string(), Value::Boolean(false));
+            Ok(value)
+        }
+    }
+
+    #[test]
+    fn migrates_older_entries() {
+        let mut data = SaveData::new();
+        data.set(&SeedV1 { seed: 1234 }).unwrap();
+        let data = SaveData::parse(&data.to_bytes().unwrap()).unwrap();
+
+        assert_eq!(data.version("randomizer"), Some(1));
+        assert_eq!(
+            data.get::<SeedV2>().unwrap(),
+            Some(SeedV2 {
+                seed: 1234,
+                key_items_only: false
+            })
+        );
+    }
+
+    #[test]
+    fn refuses_newer_entries() {
+        let mut data = SaveData::new();
+        data.set(&SeedV2 {
+            seed: 1,
+            key_items_only: true,
+        })
+        .unwrap();
+
+        assert!(matches!(
+            data.get::<SeedV1>(),
+            Err(SaveDataError::NewerVersion { version: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,182 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum SoundBankError {
+    #[error("Unexpected end of data at offset {0:#x}")]
+    UnexpectedEof(usize),
+    #[error("Soundbank doesn't start with a BKHD section")]
+    MissingHeader,
+}
+
+/// Type of a hierarchy object in a soundbank's HIRC section.
+const HIRC_EVENT: u8 = 4;
+
+/// An embedded sound inside of a soundbank's DATA section.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmbeddedSound {
+    pub id: u32,
+    pub offset: usize,
+    pub size: usize,
+}
+
+/// A hierarchy object (event, action, sound, container, ...) in a soundbank.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HierarchyObject {
+    pub object_type: u8,
+    pub id: u32,
+}
+
+/// A Wwise soundbank (.bnk) read from a file. Only the parts needed to find
+/// out what a bank contains are read, the objects themselves are left to
+/// Wwise. This doesn't touch the banks the game has loaded and can't load
+/// more of them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoundBank {
+    /// Version of the bank format, which is tied to the Wwise version it was
+    /// built with. Banks have to match the version the game was built with.
+    pub version: u32,
+    /// ID of the bank, the hash of its name.
+    pub id: u32,
+    pub objects: Vec<HierarchyObject>,
+    pub embedded_sounds: Vec<EmbeddedSound>,
+}
+
+impl SoundBank {
+    pub fn parse(bytes: &[u8]) -> Result<Self, SoundBankError> {
+        let mut bank = None;
+        let mut objects = Vec::new();
+        let mut embedded_sounds = Vec::new();
+
+        let mut offset = 0;
+        while offset < bytes.len() {
+            let tag = read_bytes::<4>(bytes, offset)?;
+            let size = read_u32(bytes, offset + 4)? as usize;
+            let start = offset + 8;
+            let section = bytes
+                .get(start..start + size)
+                .ok_or(SoundBankError::UnexpectedEof(start + size))?;
+
+            match (&tag, bank.is_some()) {
+                (b"BKHD", _) => {
+                    bank = Some((read_u32(section, 0)?, read_u32(section, 4)?));
+                }
+                (_, false) => return Err(SoundBankError::MissingHeader),
+                (b"DIDX", _) => {
+                    for entry in section.chunks_exact(12) {
+                        embedded_sounds.push(EmbeddedSound {
+                            id: read_u32(entry, 0)?,
+                            offset: read_u32(entry, 4)? as usize,
+                            size: read_u32(entry, 8)? as usize,
+                        });
+                    }
+                }
+                (b"HIRC", _) => {
+                    let count = read_u32(section, 0)? as usize;
+                    let mut object_offset = 4;
+                    for _ in 0..count {
+                        let object_type = read_bytes::<1>(section, object_offset)?[0];
+                        let object_size = read_u32(section, object_offset + 1)? as usize;
+                        let id = read_u32(section, object_offset + 5)?;
+                        objects.push(HierarchyObject { object_type, id });
+                        object_offset += 5 + object_size;
+                    }
+                }
+                _ => {}
+            }
+
+            offset = start + size;
+        }
+
+        let (version, id) = bank.ok_or(SoundBankError::MissingHeader)?;
+        Ok(Self {
+            version,
+            id,
+            objects,
+            embedded_sounds,
+        })
+    }
+
+    /// IDs of the events defined in this bank, which is what the game posts
+    /// to play sounds.
+    pub fn event_ids(&self) -> impl Iterator<Item = u32> + '_ {
+        self.objects
+            .iter()
+            .filter(|o| o.object_type == HIRC_EVENT)
+            .map(|o| o.id)
+    }
+}
+
+fn read_bytes<const N: usize>(bytes: &[u8], offset: usize) -> Result<[u8; N], SoundBankError> {
+    bytes
+        .get(offset..offset + N)
+        .and_then(|b| b.try_into().ok())
+        .ok_or(SoundBankError::UnexpectedEof(offset))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, SoundBankError> {
+    Ok(u32::from_le_bytes(read_bytes(bytes, offset)?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EmbeddedSound, SoundBank, SoundBankError};
+
+    fn section(tag: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut bytes = tag.to_vec();
+        bytes.extend((data.len() as u32).to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    fn object(object_type: u8, id: u32, data: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![object_type];
+        bytes.extend((data.len() as u32 + 4).to_le_bytes());
+        bytes.extend(id.to_le_bytes());
+        bytes.extend(data);
+        bytes
+    }
+
+    #[test]
+    fn reads_events_and_embedded_sounds() {
+        let mut header = 145u32.to_le_bytes().to_vec();
+        header.extend(0x1234u32.to_le_bytes());
+
+        let mut didx = Vec::new();
+        for value in [100u32, 0, 16] {
+            didx.extend(value.to_le_bytes());
+        }
+
+        let mut hirc = 3u32.to_le_bytes().to_vec();
+        hirc.extend(object(2, 10, &[0; 6]));
+        hirc.extend(object(4, 20, &[0; 3]));
+        hirc.extend(object(3, 30, &[]));
+
+        let bytes = [
+            section(b"BKHD", &header),
+            section(b"DIDX", &didx),
+            section(b"DATA", &[0; 16]),
+            section(b"HIRC", &hirc),
+        ]
+        .concat();
+
+        let bank = SoundBank::parse(&bytes).unwrap();
+        assert_eq!((bank.version, bank.id), (145, 0x1234));
+        assert_eq!(bank.event_ids().collect::<Vec<_>>(), [20]);
+        assert_eq!(
+            bank.embedded_sounds,
+            [EmbeddedSound {
+                id: 100,
+                offset: 0,
+                size: 16
+            }]
+        );
+    }
+
+    #[test]
+    fn requires_header() {
+        assert_eq!(
+            SoundBank::parse(&section(b"HIRC", &0u32.to_le_bytes())),
+            Err(SoundBankError::MissingHeader)
+        );
+    }
+}
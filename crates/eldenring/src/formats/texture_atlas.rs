@@ -0,0 +1,102 @@
+use super::xml::{attribute, tags};
+
+/// A single image inside of an atlas page, in pixels.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubTexture {
+    /// Name of the image, e.g. `MENU_ItemIcon_01234.png`.
+    pub name: String,
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl SubTexture {
+    /// Extracts the icon ID from names like `MENU_ItemIcon_01234.png`, which
+    /// is the ID referenced by the `iconId` fields in params.
+    pub fn icon_id(&self) -> Option<u32> {
+        let stem = self
+            .name
+            .rsplit_once('.')
+            .map_or(self.name.as_str(), |(s, _)| s);
+        let digits = stem.rsplit('_').next()?;
+        digits.parse().ok()
+    }
+
+    /// Normalized `[u0, v0, u1, v1]` coordinates given the page's size.
+    pub fn uv(&self, page_width: u32, page_height: u32) -> [f32; 4] {
+        let (w, h) = (page_width as f32, page_height as f32);
+        [
+            self.x as f32 / w,
+            self.y as f32 / h,
+            (self.x + self.width) as f32 / w,
+            (self.y + self.height) as f32 / h,
+        ]
+    }
+}
+
+/// A menu texture atlas layout, as found in the `.layout` files inside of
+/// the game's sblytbnd archives. Each layout describes a single atlas page.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextureAtlas {
+    /// Path of the page's texture as stored in the layout. Only the file
+    /// stem is meaningful at runtime.
+    pub image_path: String,
+    pub sub_textures: Vec<SubTexture>,
+}
+
+impl TextureAtlas {
+    /// Parses a `.layout` file. Returns None if it doesn't describe an atlas.
+    pub fn from_xml(xml: &str) -> Option<Self> {
+        let atlas = tags(xml, "TextureAtlas").into_iter().next()?;
+        let image_path = attribute(atlas, "imagePath")?.to_string();
+
+        let sub_textures = tags(xml, "SubTexture")
+            .into_iter()
+            .filter_map(|tag| {
+                Some(SubTexture {
+                    name: attribute(tag, "name")?.to_string(),
+                    x: attribute(tag, "x")?.parse().ok()?,
+                    y: attribute(tag, "y")?.parse().ok()?,
+                    width: attribute(tag, "width")?.parse().ok()?,
+                    height: attribute(tag, "height")?.parse().ok()?,
+                })
+            })
+            .collect();
+
+        Some(Self {
+            image_path,
+            sub_textures,
+        })
+    }
+
+    /// The page's texture name without directories or extension, which is
+    /// what the game uses to look the texture up.
+    pub fn page_name(&self) -> &str {
+        let file = self
+            .image_path
+            .rsplit(['\\', '/'])
+            .next()
+            .unwrap_or_default();
+        file.rsplit_once('.').map_or(file, |(stem, _)| stem)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TextureAtlas;
+
+    #[test]
+    fn parses_layout() {
+        let atlas = TextureAtlas::from_xml(
+            r#"<TextureAtlas imagePath="W:\CL\data\Target\INTERROOT_win64\menu\00_Solo\SB_Icon_03.png">
+    <SubTexture name="MENU_ItemIcon_03001.png" x="0" y="160" width="160" height="160" half="0"/>
+</TextureAtlas>"#,
+        )
+        .unwrap();
+
+        assert_eq!(atlas.page_name(), "SB_Icon_03");
+        assert_eq!(atlas.sub_textures[0].icon_id(), Some(3001));
+        assert_eq!(atlas.sub_textures[0].uv(320, 320), [0.0, 0.5, 0.5, 1.0]);
+    }
+}
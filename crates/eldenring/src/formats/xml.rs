@@ -0,0 +1,30 @@
+//! Just enough XML handling for the flat, tool generated XML files the game
+//! and the community tooling use. This doesn't handle entities, comments or
+//! CDATA and isn't meant to.
+
+/// Returns the trimmed text of the first `<element>` in `xml`.
+pub(super) fn element_text<'a>(xml: &'a str, element: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{element}>"))? + element.len() + 2;
+    let end = start + xml[start..].find(&format!("</{element}>"))?;
+    Some(xml[start..end].trim())
+}
+
+/// Returns the value of an attribute inside of a single tag's text.
+pub(super) fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!(" {name}=\""))? + name.len() + 3;
+    let end = start + tag[start..].find('"')?;
+    Some(&tag[start..end])
+}
+
+/// Returns the attribute text of every `<element ...>` tag in `xml`, for use
+/// with [attribute].
+pub(super) fn tags<'a>(xml: &'a str, element: &str) -> Vec<&'a str> {
+    let open = format!("<{element}");
+    xml.split(open.as_str())
+        .skip(1)
+        // Make sure not to match elements that share a prefix, e.g. <Fields>
+        // when looking for <Field>.
+        .filter(|tag| tag.starts_with(char::is_whitespace))
+        .map(|tag| &tag[..tag.find('>').unwrap_or(tag.len())])
+        .collect()
+}
@@ -1,6 +1,8 @@
 extern crate fromsoftware_shared as shared;
 
+mod error;
 mod stl;
+pub use error::*;
 pub use stl::*;
 
 pub(crate) mod rva;
@@ -16,8 +18,10 @@ pub mod dlut;
 pub mod ez_state;
 pub mod fd4;
 pub mod ffx;
+pub mod formats;
 pub mod gxffx;
 pub mod param;
 pub mod position;
 pub mod rotation;
 pub mod util;
+pub mod version;
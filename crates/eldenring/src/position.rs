@@ -129,6 +129,77 @@ pub struct PositionDelta(pub f32, pub f32, pub f32);
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DirectionalVector(pub f32, pub f32, pub f32, pub f32);
 
+impl From<HavokPosition> for glam::Vec3A {
+    #[inline]
+    fn from(HavokPosition(x, y, z, _): HavokPosition) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<HavokPosition> for glam::Vec3 {
+    #[inline]
+    fn from(HavokPosition(x, y, z, _): HavokPosition) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<glam::Vec3A> for HavokPosition {
+    #[inline]
+    fn from(v: glam::Vec3A) -> Self {
+        Self::from_xyz(v.x, v.y, v.z)
+    }
+}
+
+impl From<glam::Vec3> for HavokPosition {
+    #[inline]
+    fn from(v: glam::Vec3) -> Self {
+        Self::from_xyz(v.x, v.y, v.z)
+    }
+}
+
+impl From<BlockPosition> for glam::Vec3 {
+    /// Drops the yaw, use [BlockPosition::yaw] separately if it's needed.
+    #[inline]
+    fn from(BlockPosition { x, y, z, .. }: BlockPosition) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<glam::Vec3> for BlockPosition {
+    #[inline]
+    fn from(v: glam::Vec3) -> Self {
+        Self::from_xyz(v.x, v.y, v.z)
+    }
+}
+
+impl From<PositionDelta> for glam::Vec3 {
+    #[inline]
+    fn from(PositionDelta(x, y, z): PositionDelta) -> Self {
+        Self::new(x, y, z)
+    }
+}
+
+impl From<glam::Vec3> for PositionDelta {
+    #[inline]
+    fn from(v: glam::Vec3) -> Self {
+        Self(v.x, v.y, v.z)
+    }
+}
+
+impl From<DirectionalVector> for glam::Vec4 {
+    #[inline]
+    fn from(DirectionalVector(x, y, z, w): DirectionalVector) -> Self {
+        Self::new(x, y, z, w)
+    }
+}
+
+impl From<glam::Vec4> for DirectionalVector {
+    #[inline]
+    fn from(v: glam::Vec4) -> Self {
+        Self(v.x, v.y, v.z, v.w)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::position::{BlockPosition, PositionDelta};
@@ -172,4 +243,17 @@ mod test {
             BlockPosition::from_xyz(2.0, 2.0, 2.0) - delta,
         );
     }
+
+    #[test]
+    fn glam_conversions_round_trip() {
+        let position = HavokPosition::from_xyz(1.0, 2.0, 3.0);
+        let delta = PositionDelta(0.5, 0.5, 0.5);
+
+        let moved = glam::Vec3A::from(position) + glam::Vec3A::from(glam::Vec3::from(delta));
+        assert_eq!(HavokPosition::from(moved), position + delta);
+        assert_eq!(
+            PositionDelta::from(glam::Vec3::from(delta) * 2.0),
+            PositionDelta(1.0, 1.0, 1.0)
+        );
+    }
 }
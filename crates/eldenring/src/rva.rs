@@ -1,83 +1,81 @@
-use pelite::pe64::{Pe, PeView};
 use std::sync::LazyLock;
-use windows::Win32::System::LibraryLoader::GetModuleHandleA;
-use windows::core::PCSTR;
+
+use shared::Program;
+
+use crate::Error;
+use crate::version::{GameVersion, game_version};
 
 mod bundle;
 mod rva_jp;
 mod rva_ww;
+mod scan;
 
 pub use bundle::RvaBundle;
 
-const LANG_ID_EN: u16 = 0x0009;
-const LANG_ID_JP: u16 = 0x0011;
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum GameVersion {
-    Ww261,
-    Jp2611,
-}
-
-impl GameVersion {
-    fn from_metadata(product: &str, lang_id: u16, version: &str) -> Option<Self> {
-        match (product, lang_id, version) {
-            ("ELDEN RING™", LANG_ID_EN, "2.6.1.0") => Some(Self::Ww261),
-            ("ELDEN RING", LANG_ID_JP, "2.6.1.1") => Some(Self::Jp2611),
-            _ => None,
-        }
-    }
-}
-
 /// Returns the RVA bundle for the current executable region and version.
 ///
 /// This will panic if the current executable isn't supported by this package.
 pub fn get() -> &'static RvaBundle {
-    static RVAS: LazyLock<RvaBundle> = LazyLock::new(|| {
-        let module = unsafe {
-            PeView::module(GetModuleHandleA(PCSTR(std::ptr::null())).unwrap().0 as *const u8)
-        };
-        detect_version_and_get_rvas(&module)
-            .expect("This game version or distribution is not supported")
-    });
-
-    &RVAS
+    try_get().expect("This game version or distribution is not supported")
 }
 
-/// Determines the region and version of the current executable and, if it's
-/// known, returns the [RvaBundle] for it.
-fn detect_version_and_get_rvas(module: &PeView) -> Option<RvaBundle> {
-    let resources = module.resources().ok()?;
-    let info = resources.version_info().ok()?;
-
-    // Extract version info
-    let product_version = info.fixed()?.dwProductVersion;
-    let version = format!(
-        "{}.{}.{}.{}",
-        product_version.Major, product_version.Minor, product_version.Patch, product_version.Build,
-    );
-
-    // Extract product name
-    let language = *info.translation().first()?;
-    let mut product_name: Option<String> = None;
-    info.strings(language, |k, v| {
-        if k == "ProductName" {
-            product_name = Some(v.to_string());
-        }
-    });
+/// Returns the RVA bundle for the current executable region and version, or
+/// [Error::UnknownVersion] if it isn't supported by this package.
+///
+/// Versions without generated RVAs are scanned for the patterns they were
+/// generated from instead, see [RvaBundle::scan]. Only if that fails too is
+/// the version unsupported.
+///
+/// The RVAs of the running executable are only looked up once. Those of a
+/// program installed by a fixture are looked up on every call, and scanned
+/// bundles leaked.
+pub fn try_get() -> Result<&'static RvaBundle, Error> {
+    static RVAS: LazyLock<Option<&'static RvaBundle>> = LazyLock::new(resolve);
 
-    let product = product_name?;
-    let lang_id_base = language.lang_id & 0x03FF;
+    let rvas = if Program::current_is_fixture() {
+        resolve()
+    } else {
+        *RVAS
+    };
+    rvas.ok_or(Error::UnknownVersion)
+}
 
-    // Detect version and return appropriate RVAs
-    let version = GameVersion::from_metadata(&product, lang_id_base, &version)?;
-    Some(RvaBundle::for_version(version))
+fn resolve() -> Option<&'static RvaBundle> {
+    let version = game_version();
+    version.and_then(RvaBundle::for_version).or_else(|| {
+        tracing::warn!(
+            "Unsupported game version {:?}, scanning for RVAs instead. Struct layouts may not match.",
+            version.map(|v| v.product_version().to_string())
+        );
+        RvaBundle::scan()
+            .inspect_err(|e| tracing::error!("Could not scan for RVAs: {e}"))
+            .ok()
+            .map(|rvas| &*Box::leak(Box::new(rvas)))
+    })
 }
 
 impl RvaBundle {
-    fn for_version(version: GameVersion) -> Self {
+    fn for_version(version: GameVersion) -> Option<&'static Self> {
         match version {
-            GameVersion::Ww261 => rva_ww::RVAS,
-            GameVersion::Jp2611 => rva_jp::RVAS,
+            GameVersion::Ww261 => Some(&rva_ww::RVAS),
+            GameVersion::Jp2611 => Some(&rva_jp::RVAS),
+            GameVersion::Unsupported(_) => None,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use shared::fixture::{empty_program, install_program};
+
+    use super::try_get;
+    use crate::version::game_version;
+
+    #[test]
+    fn unknown_fixture_is_unsupported() {
+        let _guard = install_program(empty_program());
+
+        assert_eq!(game_version(), None);
+        assert!(try_get().is_err());
+    }
+}
@@ -0,0 +1,77 @@
+//! Generated runtime lookups of the RVAs.
+
+// DO NOT EDIT THIS FILE DIRECTLY.
+
+use shared::scan::{ScanError, find_pattern, find_vftable};
+
+use super::RvaBundle;
+
+impl RvaBundle {
+    /// Looks up every RVA in the current executable, the same way they're
+    /// found by `tools/binary-mapper` using `mapper-profile.toml` in the
+    /// root of this package. This is slow, so it's only done for versions
+    /// without generated RVAs.
+    pub fn scan() -> Result<Self, ScanError> {
+        let pattern_0 = find_pattern(
+            "40 57 48 83 ec 40 48 c7 44 24 20 fe ff ff ff 48 89 5c 24 50 48 89 6c 24 58 48 89 74 24 60 49 8b f0 48 8b fa 48 8b d9 48 8d 69 28",
+        )?;
+        let pattern_1 = find_pattern(
+            "48 8b c4 55 48 8d 68 88 48 81 ec 70 01 00 00 0f 29 70 e8 0f 28 35 $ {} 0f 29 78 d8",
+        )?;
+        let pattern_2 = find_pattern(
+            "48 8b c4 55 48 8d 68 a1 48 81 ec b0 00 00 00 0f 28 2d $ {} 0f 28 25 $ {} 0f 28 1d $ {}",
+        )?;
+        let pattern_3 = find_pattern(
+            "f3 44 0f 11 6c 24 20 41 0f 28 df 4c 8d 45 e0 48 8d 55 90 48 8b cb e8 $ { ' }",
+        )?;
+        let pattern_4 =
+            find_pattern("0f c6 c4 e8 0f c6 d0 d4 0f 29 55 20 ? 8d 55 00 ? 8b ce e8 $ { ' }")?;
+        let pattern_5 = find_pattern("0f 28 df ? 8d 45 07 ? 8d 55 f7 ? 8b cb e8 $ { ' }")?;
+        let pattern_6 = find_pattern("b2 08 48 8d 4d 00 e8 $ { ' }")?;
+        let pattern_7 = find_pattern("8b 01 89 85 d8 00 00 00 48 8d 55 00 49 8b ce e8 $ { ' }")?;
+        let pattern_8 = find_pattern("ba 58 00 00 02 e8 $ { ' }")?;
+        let pattern_9 =
+            find_pattern("40 53 55 56 57 48 81 ec 98 07 00 00 48 c7 44 24 50 fe ff ff ff")?;
+        let pattern_10 = find_pattern("48 8b c8 45 33 c0 ba 7f 14 00 00 e8 $ { ' }")?;
+        let pattern_11 = find_pattern("ba 1c 00 00 00 48 8b ce 84 c0 74 ? e8 $ { ' } eb ?")?;
+        let pattern_12 =
+            find_pattern("88 44 24 28 0f b6 84 24 c0 00 00 00 88 44 24 20 e8 $ { ' }")?;
+        let pattern_13 = find_pattern("ba d0 07 00 00 48 83 c4 28 e9 $ { ' }")?;
+        let pattern_14 = find_pattern("48 8b ce 48 8b f8 e8 $ { 48 89 0d $ { ' } c3 }")?;
+        let pattern_15 = find_pattern("e8 ? ? ? ? 48 8b 0d ? ? ? ? 4c 8b c7 8b d3 e8 $ { ' }")?;
+        let pattern_16 = find_pattern(
+            "c7 44 ? ? 01 00 00 00 e9 ? ? ? ? ? 8b cc e8 $ { 48 0f be 01 48 8d 0d $ { ' } }",
+        )?;
+        let pattern_17 = find_pattern("48 8d 0c 80 48 8d 05 $ { ' } 0f b6 04 88")?;
+        let pattern_18 = find_pattern("48 8b 05 $ { ' } 48 8b 80 90 0d 00 00 c3")?;
+        Ok(Self {
+            character_type_properties: pattern_17[1],
+            chr_ins_apply_speffect: pattern_10[1],
+            chr_ins_remove_speffect: pattern_11[1],
+            cs_action_button_man_execute_action_button: pattern_12[1],
+            cs_bullet_manager_spawn_bullet: pattern_9[0],
+            cs_ez_draw_draw_capsule: pattern_1[0],
+            cs_ez_draw_draw_dodecadron: pattern_5[1],
+            cs_ez_draw_draw_line: pattern_0[0],
+            cs_ez_draw_draw_sphere: pattern_2[0],
+            cs_ez_draw_draw_triangle: pattern_4[1],
+            cs_ez_draw_draw_wedge: pattern_3[1],
+            cs_ez_state_talk_env_vmt: find_vftable("CS::CSEzStateTalkEnv")?,
+            cs_ez_state_talk_event_vmt: find_vftable("CS::CSEzStateTalkEvent")?,
+            cs_menu_man_imp_display_status_message: pattern_13[1],
+            cs_phys_world_cast_ray: pattern_8[1],
+            ez_state_detail_external_event_temp_vmt: find_vftable(
+                "EzState::detail::EzStateExternalEventTemp",
+            )?,
+            ez_state_environment_query_impl_vmt: find_vftable(
+                "EzState::EzStateEnvironmentQueryImpl",
+            )?,
+            game_man: pattern_18[1],
+            global_hinstance: pattern_14[1],
+            initialize_spawn_geometry_request: pattern_6[1],
+            multiplay_properties: pattern_16[1],
+            register_task: pattern_15[1],
+            spawn_geometry: pattern_7[1],
+        })
+    }
+}
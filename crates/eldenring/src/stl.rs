@@ -1,8 +1,13 @@
+use std::alloc::{GlobalAlloc, Layout};
+use std::cmp::Ordering;
 use std::ptr::NonNull;
 
-use crate::dlkr::DLAllocatorBase;
+use crate::Error;
+use crate::dlkr::{DLAllocatorBase, DLAllocatorRef};
 use shared::OwnedPtr;
 
+pub use eldenring_core::ContainerError;
+
 #[repr(C)]
 pub struct DoublyLinkedListNode<T> {
     pub next: NonNull<DoublyLinkedListNode<T>>,
@@ -33,6 +38,44 @@ impl<T> DoublyLinkedList<T> {
         })
     }
 
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        let mut count = self.count;
+        let mut current = self.head;
+
+        std::iter::from_fn(move || {
+            current = unsafe { current.as_ref().next };
+            if count == 0 {
+                None
+            } else {
+                count -= 1;
+                Some(unsafe { &mut current.as_mut().value })
+            }
+        })
+    }
+
+    /// Walks the list once to check that every node is aligned and linked
+    /// back to the one before it, and that the list ends after `count` nodes,
+    /// before iterating over it.
+    pub fn try_iter(&self) -> Result<impl Iterator<Item = &T>, ContainerError> {
+        let mut previous = self.head;
+        for index in 0..=self.count as usize {
+            let current = unsafe { previous.as_ref().next };
+            if !current.is_aligned() {
+                return Err(ContainerError::Misaligned(current.as_ptr() as usize));
+            }
+            if unsafe { current.as_ref().previous } != previous {
+                return Err(ContainerError::BrokenLink(index));
+            }
+            if (current == self.head) != (index == self.count as usize) {
+                return Err(ContainerError::LengthMismatch(self.count as usize));
+            }
+
+            previous = current;
+        }
+
+        Ok(self.iter())
+    }
+
     pub fn len(&self) -> usize {
         self.count as usize
     }
@@ -62,7 +105,7 @@ where
         };
 
         let end = self.end.unwrap();
-        let count = (end.as_ptr() as usize - start.as_ptr() as usize) / size_of::<T>();
+        let count = (end.as_ptr() as usize - start.as_ptr() as usize) / size_of::<T>().max(1);
 
         unsafe { std::slice::from_raw_parts(start.as_ptr(), count) }
     }
@@ -73,11 +116,62 @@ where
         };
 
         let end = self.end.unwrap();
-        let count = (end.as_ptr() as usize - start.as_ptr() as usize) / size_of::<T>();
+        let count = (end.as_ptr() as usize - start.as_ptr() as usize) / size_of::<T>().max(1);
 
         unsafe { std::slice::from_raw_parts_mut(start.as_ptr(), count) }
     }
 
+    /// Checks that the begin, end and capacity pointers are aligned and in
+    /// order, and returns the number of elements between begin and end.
+    fn checked_len(&self) -> Result<usize, ContainerError> {
+        let (begin, end, capacity) = match (self.begin, self.end, self.capacity) {
+            (None, None, None) => return Ok(0),
+            (Some(begin), Some(end), Some(capacity)) => (begin, end, capacity),
+            _ => return Err(ContainerError::OutOfOrder),
+        };
+
+        if !begin.is_aligned() {
+            return Err(ContainerError::Misaligned(begin.as_ptr() as usize));
+        }
+        if begin > end || end > capacity {
+            return Err(ContainerError::OutOfOrder);
+        }
+
+        let size = end.as_ptr() as usize - begin.as_ptr() as usize;
+        if size % size_of::<T>().max(1) != 0
+            || (capacity.as_ptr() as usize - begin.as_ptr() as usize) % size_of::<T>().max(1) != 0
+        {
+            return Err(ContainerError::PartialElement);
+        }
+
+        Ok(size / size_of::<T>().max(1))
+    }
+
+    /// Like [BasicVector::items], but validates the vector's pointers first.
+    pub fn try_as_slice(&self) -> Result<&[T], ContainerError> {
+        let len = self.checked_len()?;
+        let Some(begin) = self.begin else {
+            return Ok(&[]);
+        };
+
+        Ok(unsafe { std::slice::from_raw_parts(begin.as_ptr(), len) })
+    }
+
+    /// Like [BasicVector::items_mut], but validates the vector's pointers
+    /// first.
+    pub fn try_as_mut_slice(&mut self) -> Result<&mut [T], ContainerError> {
+        let len = self.checked_len()?;
+        let Some(begin) = self.begin else {
+            return Ok(&mut []);
+        };
+
+        Ok(unsafe { std::slice::from_raw_parts_mut(begin.as_ptr(), len) })
+    }
+
+    pub fn try_iter(&self) -> Result<std::slice::Iter<'_, T>, ContainerError> {
+        self.try_as_slice().map(|items| items.iter())
+    }
+
     pub fn len(&self) -> usize {
         let Some(end) = self.end else {
             return 0;
@@ -87,7 +181,7 @@ where
             return 0;
         };
 
-        (end.as_ptr() as usize - start.as_ptr() as usize) / size_of::<T>()
+        (end.as_ptr() as usize - start.as_ptr() as usize) / size_of::<T>().max(1)
     }
 
     pub fn is_empty(&self) -> bool {
@@ -113,6 +207,18 @@ impl<T> Vector<T> {
         self.base.items_mut()
     }
 
+    pub fn try_as_slice(&self) -> Result<&[T], ContainerError> {
+        self.base.try_as_slice()
+    }
+
+    pub fn try_as_mut_slice(&mut self) -> Result<&mut [T], ContainerError> {
+        self.base.try_as_mut_slice()
+    }
+
+    pub fn try_iter(&self) -> Result<std::slice::Iter<'_, T>, ContainerError> {
+        self.base.try_iter()
+    }
+
     pub fn len(&self) -> usize {
         self.base.len()
     }
@@ -120,6 +226,118 @@ impl<T> Vector<T> {
     pub fn is_empty(&self) -> bool {
         self.base.is_empty()
     }
+
+    pub fn capacity(&self) -> usize {
+        match (self.base.begin, self.base.capacity) {
+            (Some(begin), Some(capacity)) => {
+                (capacity.as_ptr() as usize - begin.as_ptr() as usize) / size_of::<T>().max(1)
+            }
+            _ => 0,
+        }
+    }
+
+    /// Makes room for at least `additional` more elements. Like the rest of
+    /// the mutating methods, this allocates with the allocator the vector was
+    /// created with, so the game can keep growing and freeing it.
+    ///
+    /// Vectors of zero-sized elements, like the `Vector<()>` placeholders for
+    /// unmapped element types, can't be grown and fail to compile here.
+    pub fn reserve(&mut self, additional: usize) -> Result<(), Error> {
+        const {
+            assert!(
+                size_of::<T>() != 0,
+                "vectors of zero-sized elements can't be grown"
+            )
+        };
+
+        let len = self.len();
+        let required = len.checked_add(additional).ok_or(Error::CapacityOverflow)?;
+        if required <= self.capacity() {
+            return Ok(());
+        }
+
+        let old_capacity = self.capacity();
+        let capacity = grown_capacity(old_capacity, required);
+        let layout = Layout::array::<T>(capacity).map_err(|_| Error::CapacityOverflow)?;
+        let allocator = DLAllocatorRef::from(self.allocator);
+        let items = unsafe { allocator.try_alloc(layout) }?.cast::<T>();
+
+        if let Some(begin) = self.base.begin {
+            unsafe {
+                std::ptr::copy_nonoverlapping(begin.as_ptr(), items.as_ptr(), len);
+                let old_layout = Layout::array::<T>(old_capacity).unwrap();
+                allocator.dealloc(begin.as_ptr() as *mut u8, old_layout);
+            }
+        }
+
+        self.base.begin = Some(items);
+        self.base.end = Some(unsafe { items.add(len) });
+        self.base.capacity = Some(unsafe { items.add(capacity) });
+        Ok(())
+    }
+
+    pub fn push(&mut self, value: T) -> Result<(), Error> {
+        self.insert(self.len(), value)
+    }
+
+    /// Inserts `value` at `index`, shifting the elements after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is past the end of the vector.
+    pub fn insert(&mut self, index: usize, value: T) -> Result<(), Error> {
+        let len = self.len();
+        assert!(
+            index <= len,
+            "insertion index {index} is past the end ({len})"
+        );
+        self.reserve(1)?;
+
+        let begin = self.base.begin.unwrap();
+        unsafe {
+            let slot = begin.add(index);
+            std::ptr::copy(slot.as_ptr(), slot.as_ptr().add(1), len - index);
+            slot.write(value);
+            self.base.end = Some(begin.add(len + 1));
+        }
+        Ok(())
+    }
+
+    /// Removes and returns the element at `index`, shifting the elements after
+    /// it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn remove(&mut self, index: usize) -> T {
+        let len = self.len();
+        assert!(
+            index < len,
+            "removal index {index} is out of bounds ({len})"
+        );
+
+        let begin = self.base.begin.unwrap();
+        unsafe {
+            let slot = begin.add(index);
+            let value = slot.read();
+            std::ptr::copy(slot.as_ptr().add(1), slot.as_ptr(), len - index - 1);
+            self.base.end = Some(begin.add(len - 1));
+            value
+        }
+    }
+
+    /// Drops every element, keeping the allocation.
+    pub fn clear(&mut self) {
+        let items: *mut [T] = self.items_mut();
+        self.base.end = self.base.begin;
+        unsafe { std::ptr::drop_in_place(items) };
+    }
+}
+
+/// Grows by half the current capacity like MSVC's `std::vector`, or to
+/// `required` if that's more.
+fn grown_capacity(capacity: usize, required: usize) -> usize {
+    capacity.saturating_add(capacity / 2).max(required)
 }
 
 #[repr(C)]
@@ -160,6 +378,24 @@ impl<T> Tree<T> {
         })
     }
 
+    /// Binary searches the tree. `compare` orders a node's value against the
+    /// one being looked for, the same way the tree is sorted.
+    pub fn find_by(&self, compare: impl Fn(&T) -> Ordering) -> Option<&T> {
+        let mut node = unsafe { self.head.as_ref().parent };
+        loop {
+            let node_ref = unsafe { node.as_ref() };
+            if node_ref.is_nil != 0 {
+                return None;
+            }
+
+            node = match compare(&node_ref.value) {
+                Ordering::Less => node_ref.right,
+                Ordering::Greater => node_ref.left,
+                Ordering::Equal => return Some(&node_ref.value),
+            };
+        }
+    }
+
     /// Finds the minimum (leftmost) node in a subtree.
     unsafe fn min_node(mut node: NonNull<TreeNode<T>>) -> NonNull<TreeNode<T>> {
         unsafe {
@@ -241,6 +477,24 @@ impl<K, V> ChainingTree<K, V> {
         })
     }
 
+    /// The collision chain stored for `key`.
+    pub fn get_chain(&self, key: &K) -> Option<&ChainingMapBucketEntry<V>>
+    where
+        K: Ord,
+    {
+        self.base
+            .find_by(|pair| pair.key.cmp(key))
+            .map(|pair| &pair.value)
+    }
+
+    /// The first value stored for `key`.
+    pub fn get(&self, key: &K) -> Option<&V>
+    where
+        K: Ord,
+    {
+        self.get_chain(key).map(|chain| &chain.data)
+    }
+
     /// Iterates over keys and their collision chain heads.
     /// Use this if you need to iterate collision chains separately.
     pub fn iter_chains(&self) -> impl Iterator<Item = (&K, &ChainingMapBucketEntry<V>)> {
@@ -345,6 +599,23 @@ impl<T> ArrayWithHeader<T> {
         unsafe { std::slice::from_raw_parts_mut(&mut self.first_item as *mut T, self.len()) }
     }
 
+    /// Like [ArrayWithHeader::as_slice], but checks the allocation header
+    /// first.
+    ///
+    /// # Safety
+    ///
+    /// The array must have a header at negative offset, which may be invalid.
+    pub unsafe fn try_as_slice(&self) -> Result<&[T], ContainerError> {
+        if !(self as *const Self).is_aligned() {
+            return Err(ContainerError::Misaligned(self as *const Self as usize));
+        }
+        if unsafe { !self.is_valid() } {
+            return Err(ContainerError::InvalidHeader);
+        }
+
+        Ok(unsafe { self.as_slice() })
+    }
+
     /// Returns the allocation header stored before this array.
     ///
     /// # Safety
@@ -404,3 +675,173 @@ impl AllocationHeader {
         std::ptr::eq(self.self_ptr.as_ptr(), self)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::ptr::NonNull;
+
+    use shared::fixture::FixtureMemory;
+
+    use super::{
+        BasicVector, ContainerError, DoublyLinkedList, DoublyLinkedListNode, Tree, TreeNode,
+        Vector, grown_capacity,
+    };
+    use crate::Error;
+
+    fn vector(items: &mut [u32], len: usize) -> BasicVector<u32> {
+        let begin = NonNull::new(items.as_mut_ptr()).unwrap();
+        BasicVector {
+            begin: Some(begin),
+            end: Some(unsafe { begin.add(len) }),
+            capacity: Some(unsafe { begin.add(items.len()) }),
+        }
+    }
+
+    #[test]
+    fn try_as_slice_accepts_valid_vector() {
+        let mut items = [1, 2, 3, 4];
+        assert_eq!(vector(&mut items, 3).try_as_slice(), Ok(&[1, 2, 3][..]));
+
+        let empty = BasicVector::<u32> {
+            begin: None,
+            end: None,
+            capacity: None,
+        };
+        assert_eq!(empty.try_as_slice(), Ok(&[][..]));
+    }
+
+    #[test]
+    fn try_as_slice_rejects_broken_vector() {
+        let mut items = [1, 2, 3, 4];
+
+        let mut past_capacity = vector(&mut items, 2);
+        past_capacity.capacity = past_capacity.begin;
+        assert_eq!(
+            past_capacity.try_as_slice(),
+            Err(ContainerError::OutOfOrder)
+        );
+
+        let mut partial = vector(&mut items, 2);
+        partial.end = partial.end.map(|end| unsafe { end.byte_add(1) });
+        assert_eq!(partial.try_as_slice(), Err(ContainerError::PartialElement));
+
+        let mut misaligned = vector(&mut items, 2);
+        misaligned.begin = misaligned.begin.map(|begin| unsafe { begin.byte_add(1) });
+        assert!(matches!(
+            misaligned.try_as_slice(),
+            Err(ContainerError::Misaligned(_))
+        ));
+    }
+
+    #[test]
+    fn reserve_reports_capacity_overflow() {
+        let mut items = [1, 2, 3, 4];
+        let mut vector = Vector {
+            allocator: NonNull::dangling(),
+            base: vector(&mut items, 2),
+        };
+        assert_eq!(vector.capacity(), 4);
+        assert!(matches!(
+            vector.reserve(usize::MAX),
+            Err(Error::CapacityOverflow)
+        ));
+        assert!(matches!(
+            vector.reserve(usize::MAX / 2),
+            Err(Error::CapacityOverflow)
+        ));
+        assert!(vector.reserve(2).is_ok());
+
+        let placeholders = Vector::<()> {
+            allocator: NonNull::dangling(),
+            base: BasicVector {
+                begin: None,
+                end: None,
+                capacity: None,
+            },
+        };
+        assert_eq!(placeholders.len(), 0);
+        assert_eq!(placeholders.capacity(), 0);
+    }
+
+    #[test]
+    fn walks_list_in_fixture_memory() {
+        let mut memory = FixtureMemory::new();
+        let head = memory.alloc_zeroed::<DoublyLinkedListNode<u32>>();
+        let first = memory.alloc_zeroed::<DoublyLinkedListNode<u32>>();
+        let second = memory.alloc_zeroed::<DoublyLinkedListNode<u32>>();
+        unsafe {
+            for (node, previous, next, value) in [
+                (head, second, first, 0),
+                (first, head, second, 1),
+                (second, first, head, 2),
+            ] {
+                (*node.as_ptr()).previous = previous;
+                (*node.as_ptr()).next = next;
+                (*node.as_ptr()).value = value;
+            }
+        }
+
+        let list = DoublyLinkedList {
+            allocator: 0,
+            head,
+            count: 2,
+        };
+        assert_eq!(
+            list.try_iter().unwrap().copied().collect::<Vec<_>>(),
+            [1, 2]
+        );
+
+        unsafe { (*second.as_ptr()).previous = head };
+        assert!(matches!(
+            list.try_iter(),
+            Err(ContainerError::BrokenLink(1))
+        ));
+    }
+
+    #[test]
+    fn grows_like_msvc() {
+        assert_eq!(grown_capacity(0, 1), 1);
+        assert_eq!(grown_capacity(4, 5), 6);
+        assert_eq!(grown_capacity(4, 10), 10);
+    }
+
+    #[test]
+    fn find_by_searches_tree() {
+        let nil = |value| TreeNode {
+            left: NonNull::dangling(),
+            parent: NonNull::dangling(),
+            right: NonNull::dangling(),
+            black_red: 0,
+            is_nil: 1,
+            value,
+        };
+        let mut head = nil(0);
+        let head_ptr = NonNull::from(&mut head);
+        let node = |value| TreeNode {
+            left: head_ptr,
+            parent: head_ptr,
+            right: head_ptr,
+            black_red: 0,
+            is_nil: 0,
+            value,
+        };
+
+        let (mut left, mut right) = (node(1), node(3));
+        let mut root = node(2);
+        root.left = NonNull::from(&mut left);
+        root.right = NonNull::from(&mut right);
+        // Written through the pointer the nodes got, so using `head` itself
+        // doesn't invalidate it.
+        unsafe { (*head_ptr.as_ptr()).parent = NonNull::from(&mut root) };
+
+        let tree = Tree {
+            allocator: 0,
+            head: head_ptr,
+            size: 3,
+        };
+        for value in 1..=3 {
+            assert_eq!(tree.find_by(|v| v.cmp(&value)), Some(&value));
+        }
+        assert_eq!(tree.find_by(|v| v.cmp(&4)), None);
+    }
+}
@@ -12,5 +12,69 @@
 //! so there's a higher risk that these APIs will break when new patches are
 //! released.
 
+pub mod anti_cheat;
+pub mod arena;
+#[cfg(feature = "audit")]
+pub mod audit;
+mod audited;
+pub mod banner;
+pub mod boss_rush;
+pub mod camera;
+#[cfg(all(windows, feature = "graphics"))]
+pub mod capture;
+pub mod chr;
+pub mod debug_draw;
+#[cfg(windows)]
+pub mod dlc;
+pub mod draw_distance;
+pub mod environment;
+pub mod equipment;
+#[cfg(feature = "event-flag-names")]
+pub mod event_flag_names;
+pub mod fall;
+pub mod fog_wall;
+pub mod frame_rate;
+pub mod gesture;
+pub mod gimmick;
+pub mod gparam;
+pub mod great_rune;
+pub mod icon;
+pub mod iframes;
 pub mod input;
+pub mod input_device;
+#[cfg(feature = "ipc")]
+pub mod ipc;
+pub mod job;
+pub mod journey;
+pub mod keybinds;
+#[cfg(feature = "livesplit")]
+pub mod livesplit;
+pub mod loot;
+pub mod magic;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod online_guard;
+pub mod param_patch;
+pub mod party;
+pub mod physics;
+pub mod picking;
+pub mod profiling;
+pub mod prompt;
+pub mod region;
+pub mod replay;
+pub mod resistance;
+pub mod resource;
+pub mod respawn;
+pub mod save;
+#[cfg(feature = "savestate")]
+pub mod savestate;
+pub mod scaling;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod selftest;
+pub mod streaming;
 pub mod system;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+pub mod text_metrics;
+pub mod throw;
@@ -0,0 +1,72 @@
+//! Detecting whether the game is protected by Easy Anti-Cheat, so mods that
+//! must never run in a protected session can refuse to.
+//!
+//! EAC counts as active if one of [EAC_MODULES], the names of EAC's client
+//! libraries, is loaded into the game's process. Which of them the game loads
+//! and how early during startup hasn't been confirmed, so this is best
+//! checked once the game has finished initializing, and not finding them
+//! doesn't prove EAC isn't running. That's why [refuse_protected_session]
+//! also refuses whenever the game is online, which it can't be without EAC.
+//!
+//! To keep the crate's own helpers from modifying the game while online, see
+//! [online_guard](crate::util::online_guard).
+use shared::{FromStatic, InstanceError};
+use thiserror::Error;
+
+use crate::cs::CSNetMan;
+use crate::util::online_guard::online_session;
+
+/// Module names that mean EAC is loaded.
+pub const EAC_MODULES: &[&str] = &["EasyAntiCheat_EOS.dll", "EasyAntiCheat_x64.dll"];
+
+#[derive(Error, Debug)]
+pub enum ProtectedSessionError {
+    #[error("Could not get instance: {0}")]
+    Instance(#[from] InstanceError),
+    #[error("Easy Anti-Cheat is active")]
+    EacActive,
+    #[error("The game is online")]
+    Online,
+}
+
+#[cfg(windows)]
+fn is_module_loaded(name: &str) -> bool {
+    use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+    use windows::core::PCWSTR;
+
+    let name: Vec<u16> = name.encode_utf16().chain([0]).collect();
+    unsafe { GetModuleHandleW(PCWSTR(name.as_ptr())) }.is_ok()
+}
+
+#[cfg(not(windows))]
+fn is_module_loaded(_name: &str) -> bool {
+    false
+}
+
+/// Whether one of [EAC_MODULES] is loaded into the game's process.
+pub fn is_eac_active() -> bool {
+    EAC_MODULES.iter().any(|name| is_module_loaded(name))
+}
+
+/// Whether the game is offline: it isn't in a session and [CSNetMan] lost,
+/// or never had, its connection to the game's servers.
+pub fn is_offline() -> Result<bool, InstanceError> {
+    if online_session()?.is_some() {
+        return Ok(false);
+    }
+
+    let net_man = unsafe { CSNetMan::instance() }?;
+    Ok(net_man.server_connection_lost)
+}
+
+/// Fails if EAC is active or the game is online. Also fails if that can't be
+/// determined, so a mod can bail out on any error.
+pub fn refuse_protected_session() -> Result<(), ProtectedSessionError> {
+    if is_eac_active() {
+        return Err(ProtectedSessionError::EacActive);
+    }
+    if !is_offline()? {
+        return Err(ProtectedSessionError::Online);
+    }
+    Ok(())
+}
@@ -0,0 +1,60 @@
+//! Reads the state of the Colosseum quickmatch the local player is in.
+//!
+//! The game doesn't have a dedicated arena manager, matches are run by the
+//! [QuickmatchManager](crate::cs::QuickmatchManager) in
+//! [CSNetMan](crate::cs::CSNetMan), and [arena_state] reads the match it's
+//! running.
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{CSNetMan, CSQuickMatchingCtrlState, QuickmatchSettings};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArenaState {
+    pub ctrl_state: CSQuickMatchingCtrlState,
+    /// Selected arena, 1 to 3.
+    pub arena: u32,
+    /// Map ID of the arena as an integer, ex. 45000000.
+    pub match_map: u32,
+    /// Encodes the battle type, ex. 1v1 or 2v2.
+    pub match_settings: u32,
+    /// Players needed in the lobby before the match starts.
+    pub match_player_count: u32,
+    pub participant_count: usize,
+    pub error_state: u8,
+}
+
+impl ArenaState {
+    /// Whether the players are loaded into the arena and the match is running.
+    pub fn is_in_game(&self) -> bool {
+        matches!(
+            self.ctrl_state,
+            CSQuickMatchingCtrlState::HostInGame | CSQuickMatchingCtrlState::GuestInGame
+        )
+    }
+
+    pub fn spirit_ashes_allowed(&self) -> bool {
+        QuickmatchSettings(self.match_settings).spirit_ashes_allowed()
+    }
+}
+
+/// Returns the state of the current quickmatch, None if the player isn't in
+/// one.
+pub fn arena_state() -> Result<Option<ArenaState>, InstanceError> {
+    let net_man = unsafe { CSNetMan::instance() }?;
+    let quickmatch_manager = &net_man.quickmatch_manager;
+    let ctrl_state = quickmatch_manager.quickmatching_ctrl.current_state;
+    if ctrl_state == CSQuickMatchingCtrlState::None {
+        return Ok(None);
+    }
+
+    let context = &quickmatch_manager.battle_royal_context;
+    Ok(Some(ArenaState {
+        ctrl_state,
+        arena: context.arena,
+        match_map: context.quickmatch_context.match_map,
+        match_settings: context.quickmatch_context.match_settings,
+        match_player_count: context.match_player_count,
+        participant_count: context.quickmatch_context.participants.iter().count(),
+        error_state: context.quickmatch_context.error_state,
+    }))
+}
@@ -0,0 +1,183 @@
+//! A record of what the crate writes into the game's memory, enabled with the
+//! `audit` feature, for debugging and for reviewing what a mod touches.
+//!
+//! Recorded are the allocations made through
+//! [DLAllocatorRef](crate::dlkr::DLAllocatorRef), which covers the crate's
+//! containers and strings, the edits made through
+//! [ParamPatcher](crate::util::param_patch::ParamPatcher), and the fields and
+//! event flags the util modules set, ex. HP from boss rush, positions from
+//! teleports and teams from [chr](crate::util::chr). SpEffects applied or
+//! removed by the util modules go through the game's own functions and
+//! aren't recorded. Plain field assignments on the bindings can't be
+//! intercepted, so a mod's own writes only show up if they're reported with
+//! [record_write]. The most recent [AUDIT_LOG_LEN] entries are kept.
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::io::{self, Write};
+use std::panic::Location;
+use std::sync::Mutex;
+
+/// Entries kept by [audit_log], oldest first.
+pub const AUDIT_LOG_LEN: usize = 4096;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuditKind {
+    /// Bytes that were overwritten, and what they were overwritten with.
+    Write {
+        old: Vec<u8>,
+        new: Vec<u8>,
+    },
+    /// An event flag that was set or cleared. The entry's address is the
+    /// flag manager's
+    /// [CSFD4VirtualMemoryFlag](crate::cs::CSFD4VirtualMemoryFlag).
+    EventFlag {
+        flag: u32,
+        old: bool,
+        new: bool,
+    },
+    Alloc {
+        size: usize,
+    },
+    Dealloc,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// Increases by one for every entry, so gaps show where the log wrapped.
+    pub sequence: u64,
+    pub address: usize,
+    pub kind: AuditKind,
+    /// The code that asked for the change, if known. Allocations are usually
+    /// made deep inside the standard library, so they don't have one.
+    pub location: Option<&'static Location<'static>>,
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{} {:#x} ", self.sequence, self.address)?;
+        match &self.kind {
+            AuditKind::Write { old, new } => write!(f, "write {old:02x?} -> {new:02x?}")?,
+            AuditKind::EventFlag { flag, old, new } => write!(f, "flag {flag} {old} -> {new}")?,
+            AuditKind::Alloc { size } => write!(f, "alloc {size:#x} bytes")?,
+            AuditKind::Dealloc => write!(f, "dealloc")?,
+        }
+        match self.location {
+            Some(location) => write!(f, " at {location}"),
+            None => Ok(()),
+        }
+    }
+}
+
+struct AuditLog {
+    next_sequence: u64,
+    entries: VecDeque<AuditEntry>,
+}
+
+static LOG: Mutex<AuditLog> = Mutex::new(AuditLog {
+    next_sequence: 0,
+    entries: VecDeque::new(),
+});
+
+thread_local! {
+    static RECORDING: Cell<bool> = const { Cell::new(false) };
+}
+
+fn record(address: usize, kind: AuditKind, location: Option<&'static Location<'static>>) {
+    // Recording can allocate itself, which would deadlock if the log is
+    // backed by a DLAllocatorRef.
+    if RECORDING.replace(true) {
+        return;
+    }
+
+    let mut log = LOG.lock().unwrap();
+    let sequence = log.next_sequence;
+    log.next_sequence += 1;
+    if log.entries.len() == AUDIT_LOG_LEN {
+        log.entries.pop_front();
+    }
+    log.entries.push_back(AuditEntry {
+        sequence,
+        address,
+        kind,
+        location,
+    });
+    drop(log);
+    RECORDING.set(false);
+}
+
+/// Records that the bytes at `address` were changed from `old` to `new` by
+/// the caller.
+#[track_caller]
+pub fn record_write(address: usize, old: &[u8], new: &[u8]) {
+    let kind = AuditKind::Write {
+        old: old.to_vec(),
+        new: new.to_vec(),
+    };
+    record(address, kind, Some(Location::caller()));
+}
+
+/// Records that the caller changed `flag` from `old` to `new`.
+#[track_caller]
+pub fn record_flag(address: usize, flag: u32, old: bool, new: bool) {
+    record(
+        address,
+        AuditKind::EventFlag { flag, old, new },
+        Some(Location::caller()),
+    );
+}
+
+pub(crate) fn record_alloc(address: usize, size: usize) {
+    record(address, AuditKind::Alloc { size }, None);
+}
+
+pub(crate) fn record_dealloc(address: usize) {
+    record(address, AuditKind::Dealloc, None);
+}
+
+pub fn audit_log() -> Vec<AuditEntry> {
+    LOG.lock().unwrap().entries.iter().cloned().collect()
+}
+
+pub fn clear_audit_log() {
+    LOG.lock().unwrap().entries.clear();
+}
+
+/// Writes every entry in the log as a line of text.
+pub fn dump_audit_log(mut out: impl Write) -> io::Result<()> {
+    for entry in audit_log() {
+        writeln!(out, "{entry}")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AUDIT_LOG_LEN, AuditKind, audit_log, record_alloc, record_write};
+
+    #[test]
+    fn keeps_most_recent_entries() {
+        record_write(0x10, &[1, 2], &[3, 4]);
+        let entry = audit_log().pop().unwrap();
+        assert_eq!(
+            entry.kind,
+            AuditKind::Write {
+                old: vec![1, 2],
+                new: vec![3, 4]
+            }
+        );
+        assert_eq!(entry.location.unwrap().file(), file!());
+        assert!(
+            entry
+                .to_string()
+                .contains("0x10 write [01, 02] -> [03, 04]")
+        );
+
+        for i in 0..AUDIT_LOG_LEN {
+            record_alloc(i, 8);
+        }
+        let log = audit_log();
+        assert_eq!(log.len(), AUDIT_LOG_LEN);
+        assert_eq!(log.last().unwrap().address, AUDIT_LOG_LEN - 1);
+    }
+}
@@ -0,0 +1,46 @@
+//! Writes the util modules make to the game's state, recorded in the
+//! [audit](super::audit) log when the `audit` feature is enabled and plain
+//! writes otherwise.
+use crate::cs::CSFD4VirtualMemoryFlag;
+use crate::position::HavokPosition;
+use crate::rotation::Quaternion;
+
+/// Field types without padding, so their bytes can be recorded as they are.
+pub(crate) trait PlainField: Copy {}
+
+impl PlainField for bool {}
+impl PlainField for u8 {}
+impl PlainField for i32 {}
+impl PlainField for f32 {}
+impl PlainField for HavokPosition {}
+impl PlainField for Quaternion {}
+
+/// Assigns `value` to `target`.
+#[track_caller]
+pub(crate) fn set<T: PlainField>(target: &mut T, value: T) {
+    #[cfg(feature = "audit")]
+    let old = *target;
+    *target = value;
+    #[cfg(feature = "audit")]
+    super::audit::record_write(
+        target as *const T as usize,
+        bytes_of(&old),
+        bytes_of(target),
+    );
+}
+
+/// Sets or clears an event flag.
+#[track_caller]
+pub(crate) fn set_flag(flags: &mut CSFD4VirtualMemoryFlag, flag: u32, state: bool) {
+    #[cfg(feature = "audit")]
+    let old = flags.get_flag(flag);
+    flags.set_flag(flag, state);
+    #[cfg(feature = "audit")]
+    super::audit::record_flag(flags as *const _ as usize, flag, old, state);
+}
+
+#[cfg(feature = "audit")]
+fn bytes_of<T: PlainField>(value: &T) -> &[u8] {
+    // SAFETY: PlainField types have no padding, so every byte is initialized.
+    unsafe { std::slice::from_raw_parts(value as *const T as *const u8, size_of::<T>()) }
+}
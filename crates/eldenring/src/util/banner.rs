@@ -0,0 +1,55 @@
+//! Native looking notifications through the game's blinking message banner,
+//! the one map events use for messages like "Something approaches".
+//!
+//! The banner shows text from the game's messages, so custom text has to be
+//! added to the game's FMGs first, ex. with a text mod written through
+//! [Fmg](crate::formats::Fmg). Call [update_banner] every frame, e.g. from a
+//! recurring task, for the banner to be taken down again.
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::CSFeManImp;
+use crate::formats::MsgId;
+
+struct ActiveBanner {
+    message_id: i32,
+    hide_at: Instant,
+}
+
+static ACTIVE_BANNER: Mutex<Option<ActiveBanner>> = Mutex::new(None);
+
+/// Shows a message in the banner for `duration`, replacing any message that
+/// is currently shown.
+pub fn show_banner(message_id: impl Into<MsgId>, duration: Duration) -> Result<(), InstanceError> {
+    let MsgId(message_id) = message_id.into();
+    let fe_man = unsafe { CSFeManImp::instance() }?;
+    fe_man.blinking_message_id = message_id;
+
+    *ACTIVE_BANNER.lock().unwrap() = Some(ActiveBanner {
+        message_id,
+        hide_at: Instant::now() + duration,
+    });
+    Ok(())
+}
+
+/// Takes down a banner shown through [show_banner] once its time is up.
+/// Messages the game has put up in the meantime are left alone.
+pub fn update_banner() -> Result<(), InstanceError> {
+    let mut active_banner = ACTIVE_BANNER.lock().unwrap();
+    let Some(banner) = active_banner.as_ref() else {
+        return Ok(());
+    };
+
+    if Instant::now() < banner.hide_at {
+        return Ok(());
+    }
+
+    let fe_man = unsafe { CSFeManImp::instance() }?;
+    if fe_man.blinking_message_id == banner.message_id {
+        fe_man.blinking_message_id = -1;
+    }
+    *active_banner = None;
+    Ok(())
+}
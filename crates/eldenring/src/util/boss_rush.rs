@@ -0,0 +1,184 @@
+//! Running boss encounters back to back.
+//!
+//! Starting an encounter heals the player, clears the boss's defeated flag
+//! and whatever other flags its event scripts use, moves the player into the
+//! arena and spawns the boss. Every step goes through the helpers in
+//! [fog_wall](super::fog_wall) and [respawn](super::respawn), with the same
+//! limits:
+//!
+//! - Flags the map's event scripts only read on load, including whether the
+//!   map's own boss is placed, take effect the next time the map loads.
+//! - Bosses are spawned through the debug character creator, which doesn't
+//!   hook them up to the map's event scripts. The spawned character's handle
+//!   is picked up by [update_boss_rush] once the game processed the request,
+//!   so that [reset_encounter] can get rid of it again. Characters can't be
+//!   removed, it's killed instead.
+use std::sync::Mutex;
+
+use shared::{FromStatic, InstanceError};
+use thiserror::Error;
+
+use crate::cs::{CSEventFlagMan, ChrDebugSpawnRequest, FieldInsHandle, WorldChrMan};
+use crate::position::HavokPosition;
+
+use super::audited::{set, set_flag};
+use super::fog_wall::BossArena;
+use super::online_guard::{OnlineGuardError, check_online_guard};
+use super::respawn::RespawnPoint;
+
+#[derive(Error, Debug)]
+pub enum BossRushError {
+    #[error("Could not get instance: {0}")]
+    Instance(#[from] InstanceError),
+    #[error("No player is loaded")]
+    NoPlayer,
+    #[error("No encounter has been started")]
+    NoEncounter,
+    #[error(transparent)]
+    Online(#[from] OnlineGuardError),
+}
+
+/// A boss to spawn with the debug character creator.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BossSpawn {
+    /// Model ID, ex. 4810 for c4810.
+    pub chr_id: i32,
+    pub npc_param_id: i32,
+    pub npc_think_param_id: i32,
+    pub event_entity_id: i32,
+    /// Passed to the debug character creator as is, see
+    /// [ChrDebugSpawnRequest].
+    pub position: HavokPosition,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BossDef {
+    pub arena: BossArena,
+    /// Flags cleared along with the defeated flag, ex. ones remembering that
+    /// the boss's intro cutscene was seen.
+    pub reset_flags: Vec<u32>,
+    pub player_start: RespawnPoint,
+    /// None for bosses the map places itself.
+    pub boss: Option<BossSpawn>,
+}
+
+struct Encounter {
+    def: BossDef,
+    boss: Option<FieldInsHandle>,
+    /// Whether the boss spawn was requested and its handle not picked up yet.
+    awaiting_spawn: bool,
+}
+
+static ENCOUNTER: Mutex<Option<Encounter>> = Mutex::new(None);
+
+/// Ends the current encounter, if any, and starts `def`. Nothing is changed
+/// if the encounter can't be started, the previous one keeps running then.
+pub fn start_encounter(def: BossDef) -> Result<(), BossRushError> {
+    check_online_guard()?;
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+    let player = world_chr_man
+        .main_player
+        .as_mut()
+        .ok_or(BossRushError::NoPlayer)?;
+
+    // The first change, and it can only fail on the online guard which was
+    // checked above.
+    def.player_start.teleport(&mut player.chr_ins)?;
+
+    let data = &mut player.chr_ins.module_container.data;
+    set(&mut data.hp, data.max_hp);
+    set(&mut data.fp, data.max_fp);
+    set(&mut data.stamina, data.max_stamina);
+
+    let mut encounter = ENCOUNTER.lock().unwrap();
+    if let Some(previous) = encounter.take() {
+        end(world_chr_man, &previous);
+    }
+
+    let flags = &mut event_flag_man.virtual_memory_flag;
+    set_flag(flags, def.arena.defeated_flag, false);
+    for flag in &def.reset_flags {
+        set_flag(flags, *flag, false);
+    }
+
+    if let Some(boss) = def.boss {
+        let HavokPosition(x, y, z, _) = boss.position;
+        world_chr_man.spawn_debug_character(&ChrDebugSpawnRequest {
+            chr_id: boss.chr_id,
+            chara_init_param_id: -1,
+            npc_param_id: boss.npc_param_id,
+            npc_think_param_id: boss.npc_think_param_id,
+            event_entity_id: boss.event_entity_id,
+            talk_id: -1,
+            pos_x: x,
+            pos_y: y,
+            pos_z: z,
+        });
+    }
+
+    *encounter = Some(Encounter {
+        awaiting_spawn: def.boss.is_some(),
+        def,
+        boss: None,
+    });
+    Ok(())
+}
+
+/// Starts the current encounter over.
+pub fn reset_encounter() -> Result<(), BossRushError> {
+    let def = ENCOUNTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|encounter| encounter.def.clone())
+        .ok_or(BossRushError::NoEncounter)?;
+
+    start_encounter(def)
+}
+
+/// Kills the spawned boss.
+pub fn end_encounter() -> Result<(), BossRushError> {
+    if let Some(encounter) = ENCOUNTER.lock().unwrap().take() {
+        end(unsafe { WorldChrMan::instance() }?, &encounter);
+    }
+    Ok(())
+}
+
+pub fn current_encounter() -> Option<BossDef> {
+    ENCOUNTER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|encounter| encounter.def.clone())
+}
+
+/// Picks up the handle of the spawned boss. This needs to be called every
+/// frame while an encounter is running.
+pub fn update_boss_rush() -> Result<(), InstanceError> {
+    let mut encounter = ENCOUNTER.lock().unwrap();
+    let Some(encounter) = encounter.as_mut().filter(|e| e.awaiting_spawn) else {
+        return Ok(());
+    };
+
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let creator = &world_chr_man.debug_chr_creator;
+    if creator.spawn {
+        return Ok(());
+    }
+
+    encounter.awaiting_spawn = false;
+    encounter.boss = creator
+        .last_created_chr
+        .map(|chr_ins| unsafe { chr_ins.as_ref() }.field_ins_handle);
+    Ok(())
+}
+
+fn end(world_chr_man: &mut WorldChrMan, encounter: &Encounter) {
+    if let Some(boss) = encounter
+        .boss
+        .and_then(|handle| world_chr_man.chr_ins_by_handle(&handle))
+    {
+        set(&mut boss.module_container.data.hp, 0);
+    }
+}
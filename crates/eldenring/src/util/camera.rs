@@ -0,0 +1,23 @@
+//! Projecting world positions onto the screen for overlays.
+use shared::FromStatic;
+
+use crate::cs::{CSCamera, CSWindowImp};
+
+/// Projects a world space position to pixel coordinates on the game's
+/// window, with (0, 0) at the top left. Returns None if the position is
+/// behind the camera or if the camera isn't available, e.g. during loading
+/// screens.
+///
+/// This reads the camera the game renders with at the time of the call, so
+/// it should be called from the frame that is being drawn.
+pub fn world_to_screen(position: glam::Vec3) -> Option<(f32, f32)> {
+    let camera = unsafe { CSCamera::instance() }.ok()?;
+    let window = unsafe { CSWindowImp::instance() }.ok()?;
+
+    let ndc = camera.pers_cam_1.project(position)?;
+    let resolution = window.screen_resolution();
+    Some((
+        (ndc.x + 1.0) * 0.5 * resolution.width as f32,
+        (1.0 - ndc.y) * 0.5 * resolution.height as f32,
+    ))
+}
@@ -0,0 +1,205 @@
+//! Backbuffer readback for a swap chain and queue the caller already has.
+//!
+//! This crate doesn't hook the game's present call, so [capture_frame] takes
+//! the swap chain and the command queue it presents on from the caller, ex.
+//! from the present hook of an overlay library. It has to be called right
+//! before the present call and blocks until the GPU finished copying the
+//! image, which costs a frame's worth of GPU latency and shouldn't be done
+//! every frame.
+use std::mem::ManuallyDrop;
+
+use thiserror::Error;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::Graphics::Direct3D12::*;
+use windows::Win32::Graphics::Dxgi::Common::*;
+use windows::Win32::Graphics::Dxgi::IDXGISwapChain3;
+
+#[derive(Error, Debug)]
+pub enum CaptureError {
+    #[error("Direct3D call failed: {0}")]
+    Windows(#[from] windows::core::Error),
+    #[error("Unsupported backbuffer format {0:?}")]
+    UnsupportedFormat(DXGI_FORMAT),
+}
+
+/// An 8 bit per channel RGBA image, stored row by row without padding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RgbaImage {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+/// Copies the swap chain's current backbuffer into an [RgbaImage]. `queue`
+/// must be the command queue the swap chain presents on.
+pub fn capture_frame(
+    swap_chain: &IDXGISwapChain3,
+    queue: &ID3D12CommandQueue,
+) -> Result<RgbaImage, CaptureError> {
+    unsafe {
+        let backbuffer: ID3D12Resource =
+            swap_chain.GetBuffer(swap_chain.GetCurrentBackBufferIndex())?;
+        let desc = backbuffer.GetDesc();
+
+        let swap_channels = match desc.Format {
+            DXGI_FORMAT_R8G8B8A8_UNORM | DXGI_FORMAT_R8G8B8A8_UNORM_SRGB => false,
+            DXGI_FORMAT_B8G8R8A8_UNORM | DXGI_FORMAT_B8G8R8A8_UNORM_SRGB => true,
+            format => return Err(CaptureError::UnsupportedFormat(format)),
+        };
+
+        let mut device: Option<ID3D12Device> = None;
+        backbuffer.GetDevice(&mut device)?;
+        let device = device.expect("GetDevice succeeded without a device");
+
+        let mut footprint = D3D12_PLACED_SUBRESOURCE_FOOTPRINT::default();
+        let mut total_size = 0;
+        device.GetCopyableFootprints(
+            &desc,
+            0,
+            1,
+            0,
+            Some(&mut footprint),
+            None,
+            None,
+            Some(&mut total_size),
+        );
+
+        let readback = create_readback_buffer(&device, total_size)?;
+
+        let allocator: ID3D12CommandAllocator =
+            device.CreateCommandAllocator(D3D12_COMMAND_LIST_TYPE_DIRECT)?;
+        let list: ID3D12GraphicsCommandList =
+            device.CreateCommandList(0, D3D12_COMMAND_LIST_TYPE_DIRECT, &allocator, None)?;
+
+        list.ResourceBarrier(&[transition(
+            &backbuffer,
+            D3D12_RESOURCE_STATE_PRESENT,
+            D3D12_RESOURCE_STATE_COPY_SOURCE,
+        )]);
+        list.CopyTextureRegion(
+            &D3D12_TEXTURE_COPY_LOCATION {
+                pResource: std::mem::transmute_copy(&readback),
+                Type: D3D12_TEXTURE_COPY_TYPE_PLACED_FOOTPRINT,
+                Anonymous: D3D12_TEXTURE_COPY_LOCATION_0 {
+                    PlacedFootprint: footprint,
+                },
+            },
+            0,
+            0,
+            0,
+            &D3D12_TEXTURE_COPY_LOCATION {
+                pResource: std::mem::transmute_copy(&backbuffer),
+                Type: D3D12_TEXTURE_COPY_TYPE_SUBRESOURCE_INDEX,
+                Anonymous: D3D12_TEXTURE_COPY_LOCATION_0 {
+                    SubresourceIndex: 0,
+                },
+            },
+            None,
+        );
+        list.ResourceBarrier(&[transition(
+            &backbuffer,
+            D3D12_RESOURCE_STATE_COPY_SOURCE,
+            D3D12_RESOURCE_STATE_PRESENT,
+        )]);
+        list.Close()?;
+
+        queue.ExecuteCommandLists(&[Some(list.into())]);
+
+        // Without an event, SetEventOnCompletion blocks until the fence is
+        // signaled.
+        let fence: ID3D12Fence = device.CreateFence(0, D3D12_FENCE_FLAG_NONE)?;
+        queue.Signal(&fence, 1)?;
+        fence.SetEventOnCompletion(1, HANDLE::default())?;
+
+        let width = desc.Width as u32;
+        let height = desc.Height;
+        let row_pitch = footprint.Footprint.RowPitch as usize;
+
+        let mut mapped = std::ptr::null_mut();
+        readback.Map(
+            0,
+            Some(&D3D12_RANGE {
+                Begin: 0,
+                End: total_size as usize,
+            }),
+            Some(&mut mapped),
+        )?;
+        let data = std::slice::from_raw_parts(mapped as *const u8, total_size as usize);
+
+        let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+        for row in data.chunks(row_pitch).take(height as usize) {
+            for pixel in row[..width as usize * 4].chunks_exact(4) {
+                match swap_channels {
+                    true => pixels.extend([pixel[2], pixel[1], pixel[0], pixel[3]]),
+                    false => pixels.extend_from_slice(pixel),
+                }
+            }
+        }
+
+        readback.Unmap(0, Some(&D3D12_RANGE::default()));
+
+        Ok(RgbaImage {
+            width,
+            height,
+            pixels,
+        })
+    }
+}
+
+unsafe fn create_readback_buffer(
+    device: &ID3D12Device,
+    size: u64,
+) -> windows::core::Result<ID3D12Resource> {
+    let heap_properties = D3D12_HEAP_PROPERTIES {
+        Type: D3D12_HEAP_TYPE_READBACK,
+        ..Default::default()
+    };
+    let desc = D3D12_RESOURCE_DESC {
+        Dimension: D3D12_RESOURCE_DIMENSION_BUFFER,
+        Width: size,
+        Height: 1,
+        DepthOrArraySize: 1,
+        MipLevels: 1,
+        SampleDesc: DXGI_SAMPLE_DESC {
+            Count: 1,
+            Quality: 0,
+        },
+        Layout: D3D12_TEXTURE_LAYOUT_ROW_MAJOR,
+        ..Default::default()
+    };
+
+    let mut resource: Option<ID3D12Resource> = None;
+    unsafe {
+        device.CreateCommittedResource(
+            &heap_properties,
+            D3D12_HEAP_FLAG_NONE,
+            &desc,
+            D3D12_RESOURCE_STATE_COPY_DEST,
+            None,
+            &mut resource,
+        )?;
+    }
+
+    Ok(resource.expect("CreateCommittedResource succeeded without a resource"))
+}
+
+/// Builds a transition barrier without taking a reference on `resource`, the
+/// barrier never outlives the call it's recorded in.
+unsafe fn transition(
+    resource: &ID3D12Resource,
+    before: D3D12_RESOURCE_STATES,
+    after: D3D12_RESOURCE_STATES,
+) -> D3D12_RESOURCE_BARRIER {
+    D3D12_RESOURCE_BARRIER {
+        Type: D3D12_RESOURCE_BARRIER_TYPE_TRANSITION,
+        Flags: D3D12_RESOURCE_BARRIER_FLAG_NONE,
+        Anonymous: D3D12_RESOURCE_BARRIER_0 {
+            Transition: ManuallyDrop::new(D3D12_RESOURCE_TRANSITION_BARRIER {
+                pResource: unsafe { std::mem::transmute_copy(resource) },
+                Subresource: D3D12_RESOURCE_BARRIER_ALL_SUBRESOURCES,
+                StateBefore: before,
+                StateAfter: after,
+            }),
+        },
+    }
+}
@@ -0,0 +1,95 @@
+//! Helpers for changing how characters relate to each other.
+//!
+//! Writing to a [ChrIns] directly only changes the local copy of a
+//! character. To have other players in the session see a character being
+//! hidden as well, register a SpEffect row with [set_sp_effect_carriers].
+//! [set_hidden] then goes through the character's SpEffect module, which the
+//! game already syncs. Team changes can't be synced that way, since the team
+//! a SpEffect switches to is part of its shared param row.
+use std::sync::RwLock;
+
+use shared::{FromStatic, InstanceError, Program, vftable_classname};
+use thiserror::Error;
+
+use crate::cs::{ChrIns, EnemyIns, FieldInsHandle, WorldChrMan};
+use crate::util::audited::set;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+#[derive(Error, Debug)]
+pub enum ChrUtilError {
+    #[error("Could not get instance: {0}")]
+    Instance(#[from] InstanceError),
+    #[error("No character found for handle {0}")]
+    NotFound(FieldInsHandle),
+    #[error(transparent)]
+    Online(#[from] OnlineGuardError),
+}
+
+/// SpEffect rows that a mod has set aside for the helpers in this module.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SpEffectCarriers {
+    /// Row that makes the character invisible, e.g. through a camouflage
+    /// SpEffectVfx. It's applied as is.
+    pub hidden: Option<i32>,
+}
+
+static CARRIERS: RwLock<SpEffectCarriers> = RwLock::new(SpEffectCarriers { hidden: None });
+
+/// Registers the SpEffect rows used to replicate changes to other players.
+pub fn set_sp_effect_carriers(carriers: SpEffectCarriers) {
+    *CARRIERS.write().unwrap() = carriers;
+}
+
+fn chr_ins_by_handle(handle: &FieldInsHandle) -> Result<&'static mut ChrIns, ChrUtilError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    world_chr_man
+        .chr_ins_by_handle(handle)
+        .ok_or_else(|| ChrUtilError::NotFound(*handle))
+}
+
+/// Moves a character to another team, ex. a [TeamType](crate::cs::TeamType)
+/// or a raw value it doesn't have a variant for.
+///
+/// Only the local copy of the character changes. Other players in the
+/// session keep seeing it on its original team.
+pub fn set_team_type(
+    handle: &FieldInsHandle,
+    team_type: impl Into<u8>,
+) -> Result<(), ChrUtilError> {
+    check_online_guard()?;
+    set(&mut chr_ins_by_handle(handle)?.team_type, team_type.into());
+    Ok(())
+}
+
+/// Hides or reveals a character. Hidden characters keep updating and can
+/// still be hit, they just aren't drawn.
+pub fn set_hidden(handle: &FieldInsHandle, hidden: bool) -> Result<(), ChrUtilError> {
+    check_online_guard()?;
+    let chr_ins = chr_ins_by_handle(handle)?;
+
+    match CARRIERS.read().unwrap().hidden {
+        Some(carrier) if hidden => chr_ins.apply_speffect(carrier, false),
+        Some(carrier) => chr_ins.remove_speffect(carrier),
+        None => {
+            // Fade the same way the game does for characters being unloaded.
+            set(
+                &mut chr_ins.base_transparency_modifier,
+                if hidden { -1.0 } else { 1.0 },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the character as an [EnemyIns] if it is one, going by its RTTI.
+pub fn as_enemy_ins(chr_ins: &mut ChrIns) -> Option<&mut EnemyIns> {
+    // SAFETY: the vftable pointer is always the first field of a ChrIns.
+    let vftable = unsafe { *(chr_ins as *const ChrIns as *const usize) };
+    if !vftable_classname(&Program::current(), vftable)?.ends_with("EnemyIns") {
+        return None;
+    }
+
+    // SAFETY: EnemyIns starts with its ChrIns and the class was checked above.
+    Some(unsafe { &mut *(chr_ins as *mut ChrIns as *mut EnemyIns) })
+}
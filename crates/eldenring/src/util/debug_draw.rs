@@ -0,0 +1,313 @@
+//! Immediate mode drawing of debug shapes in world space.
+//!
+//! Lines, spheres and capsules go through the game's own debug draw
+//! ([CSEzDraw]), so they're depth tested against the scene and show up in
+//! the same frame. Everything has to be submitted again every frame, e.g.
+//! from a recurring task in the `ChrIns_PostPhysics` group so positions are
+//! already up to date.
+//!
+//! [draw_text_3d] queues the text for an overlay. Overlays drain the queue with
+//! [take_world_text] and can project the positions with
+//! [world_to_screen](super::camera::world_to_screen). Without an overlay
+//! draining it, the queue only keeps the latest [MAX_WORLD_TEXT] entries.
+//!
+//! Drawing thousands of shapes a frame, like the capsules of every loaded
+//! character, is better done with a [DebugDrawBatch]. Every shape is still
+//! its own draw command, but the batch keeps the overhead around them down:
+//! the debug draw is only looked up once, shapes are grouped by color
+//! so the draw state changes as rarely as possible, and shapes too far away
+//! or past a budget are dropped before they're submitted.
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use shared::{F32Vector4, FromStatic, InstanceError, Triangle};
+
+use crate::cs::{CSEzDraw, RendMan};
+use crate::position::HavokPosition;
+
+/// A piece of text anchored at a position in the world.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorldText {
+    pub position: HavokPosition,
+    pub text: String,
+    pub color: F32Vector4,
+}
+
+/// How many [draw_text_3d] calls are queued at most. Past that, the oldest
+/// text is dropped.
+pub const MAX_WORLD_TEXT: usize = 1024;
+
+static WORLD_TEXT: Mutex<VecDeque<WorldText>> = Mutex::new(VecDeque::new());
+
+fn ez_draw(color: &F32Vector4) -> Result<&'static mut CSEzDraw, InstanceError> {
+    let rend_man = unsafe { RendMan::instance() }?;
+    let ez_draw = rend_man.debug_ez_draw.as_mut();
+    ez_draw.set_color(color);
+    Ok(ez_draw)
+}
+
+pub fn draw_line(
+    from: &HavokPosition,
+    to: &HavokPosition,
+    color: &F32Vector4,
+) -> Result<(), InstanceError> {
+    ez_draw(color)?.draw_line(from, to);
+    Ok(())
+}
+
+pub fn draw_sphere(
+    center: &HavokPosition,
+    radius: f32,
+    color: &F32Vector4,
+) -> Result<(), InstanceError> {
+    ez_draw(color)?.draw_sphere(center, radius);
+    Ok(())
+}
+
+pub fn draw_capsule(
+    top: &HavokPosition,
+    bottom: &HavokPosition,
+    radius: f32,
+    color: &F32Vector4,
+) -> Result<(), InstanceError> {
+    ez_draw(color)?.draw_capsule(top, bottom, radius);
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DebugShape {
+    Line {
+        from: HavokPosition,
+        to: HavokPosition,
+    },
+    Sphere {
+        center: HavokPosition,
+        radius: f32,
+    },
+    Capsule {
+        top: HavokPosition,
+        bottom: HavokPosition,
+        radius: f32,
+    },
+    Triangle(Triangle),
+}
+
+impl DebugShape {
+    /// Position used to tell how far away the shape is.
+    fn anchor(&self) -> glam::Vec3 {
+        match self {
+            DebugShape::Line { from, to } => {
+                (glam::Vec3::from(*from) + glam::Vec3::from(*to)) / 2.0
+            }
+            DebugShape::Sphere { center, .. } => (*center).into(),
+            DebugShape::Capsule { top, bottom, .. } => {
+                (glam::Vec3::from(*top) + glam::Vec3::from(*bottom)) / 2.0
+            }
+            DebugShape::Triangle(triangle) => {
+                glam::Vec3::new(triangle.origin.0, triangle.origin.1, triangle.origin.2)
+            }
+        }
+    }
+}
+
+/// Shapes collected over a frame and drawn all at once.
+#[derive(Clone, Debug, Default)]
+pub struct DebugDrawBatch {
+    shapes: Vec<(F32Vector4, DebugShape)>,
+    /// Shapes further than this from the viewer are skipped.
+    pub max_distance: Option<f32>,
+    /// Most shapes drawn per submit, the closest ones are kept.
+    pub max_shapes: Option<usize>,
+}
+
+impl DebugDrawBatch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_max_distance(mut self, max_distance: f32) -> Self {
+        self.max_distance = Some(max_distance);
+        self
+    }
+
+    pub fn with_max_shapes(mut self, max_shapes: usize) -> Self {
+        self.max_shapes = Some(max_shapes);
+        self
+    }
+
+    pub fn push(&mut self, shape: DebugShape, color: &F32Vector4) {
+        self.shapes.push((*color, shape));
+    }
+
+    pub fn line(&mut self, from: &HavokPosition, to: &HavokPosition, color: &F32Vector4) {
+        self.push(
+            DebugShape::Line {
+                from: *from,
+                to: *to,
+            },
+            color,
+        );
+    }
+
+    pub fn sphere(&mut self, center: &HavokPosition, radius: f32, color: &F32Vector4) {
+        self.push(
+            DebugShape::Sphere {
+                center: *center,
+                radius,
+            },
+            color,
+        );
+    }
+
+    pub fn capsule(
+        &mut self,
+        top: &HavokPosition,
+        bottom: &HavokPosition,
+        radius: f32,
+        color: &F32Vector4,
+    ) {
+        self.push(
+            DebugShape::Capsule {
+                top: *top,
+                bottom: *bottom,
+                radius,
+            },
+            color,
+        );
+    }
+
+    pub fn triangle(&mut self, triangle: &Triangle, color: &F32Vector4) {
+        self.push(DebugShape::Triangle(*triangle), color);
+    }
+
+    pub fn len(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shapes.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.shapes.clear();
+    }
+
+    /// Drops the shapes that won't be drawn and orders the rest by color.
+    fn prepare(&mut self, viewer: &HavokPosition) {
+        let viewer = glam::Vec3::from(*viewer);
+        let distance = |shape: &DebugShape| shape.anchor().distance_squared(viewer);
+
+        if let Some(max_distance) = self.max_distance {
+            let max_distance_sq = max_distance * max_distance;
+            self.shapes
+                .retain(|(_, shape)| distance(shape) <= max_distance_sq);
+        }
+
+        let len = self.shapes.len();
+        if let Some(max_shapes) = self.max_shapes.filter(|max_shapes| len > *max_shapes) {
+            self.shapes
+                .select_nth_unstable_by(max_shapes, |(_, a), (_, b)| {
+                    distance(a).total_cmp(&distance(b))
+                });
+            self.shapes.truncate(max_shapes);
+        }
+
+        self.shapes
+            .sort_by_key(|(color, _)| [color.0, color.1, color.2, color.3].map(f32::to_bits));
+    }
+
+    /// Draws every shape as seen from `viewer`, usually the camera or the
+    /// player, and empties the batch for the next frame. Returns how many
+    /// shapes were drawn.
+    pub fn submit(&mut self, viewer: &HavokPosition) -> Result<usize, InstanceError> {
+        let rend_man = unsafe { RendMan::instance() }?;
+        let ez_draw = rend_man.debug_ez_draw.as_mut();
+
+        self.prepare(viewer);
+        let mut current_color = None;
+        for (color, shape) in self.shapes.iter() {
+            if current_color != Some(color) {
+                ez_draw.set_color(color);
+                current_color = Some(color);
+            }
+
+            match shape {
+                DebugShape::Line { from, to } => ez_draw.draw_line(from, to),
+                DebugShape::Sphere { center, radius } => ez_draw.draw_sphere(center, *radius),
+                DebugShape::Capsule {
+                    top,
+                    bottom,
+                    radius,
+                } => ez_draw.draw_capsule(top, bottom, *radius),
+                DebugShape::Triangle(triangle) => ez_draw.draw_triangle(triangle),
+            }
+        }
+
+        let drawn = self.shapes.len();
+        self.shapes.clear();
+        Ok(drawn)
+    }
+}
+
+/// Queues text to be drawn at a position in the world by an overlay.
+pub fn draw_text_3d(position: &HavokPosition, text: impl Into<String>, color: &F32Vector4) {
+    let mut world_text = WORLD_TEXT.lock().unwrap();
+    if world_text.len() >= MAX_WORLD_TEXT {
+        world_text.pop_front();
+    }
+    world_text.push_back(WorldText {
+        position: *position,
+        text: text.into(),
+        color: *color,
+    });
+}
+
+/// Takes all text queued with [draw_text_3d] since the last call.
+pub fn take_world_text() -> Vec<WorldText> {
+    std::mem::take(&mut *WORLD_TEXT.lock().unwrap()).into()
+}
+
+#[cfg(test)]
+mod test {
+    use shared::F32Vector4;
+
+    use super::{DebugDrawBatch, MAX_WORLD_TEXT, draw_text_3d, take_world_text};
+    use crate::position::HavokPosition;
+
+    #[test]
+    fn keeps_latest_world_text() {
+        let white = F32Vector4(1.0, 1.0, 1.0, 1.0);
+        let position = HavokPosition::from_xyz(0.0, 0.0, 0.0);
+        for index in 0..MAX_WORLD_TEXT + 2 {
+            draw_text_3d(&position, index.to_string(), &white);
+        }
+
+        let text = take_world_text();
+        assert_eq!(text.len(), MAX_WORLD_TEXT);
+        assert_eq!(text[0].text, "2");
+        assert!(take_world_text().is_empty());
+    }
+
+    #[test]
+    fn culls_and_groups_by_color() {
+        let red = F32Vector4(1.0, 0.0, 0.0, 1.0);
+        let blue = F32Vector4(0.0, 0.0, 1.0, 1.0);
+        let mut batch = DebugDrawBatch::new()
+            .with_max_distance(50.0)
+            .with_max_shapes(3);
+
+        for (x, color) in [
+            (1.0, &red),
+            (2.0, &blue),
+            (3.0, &red),
+            (4.0, &blue),
+            (60.0, &red),
+        ] {
+            batch.sphere(&HavokPosition::from_xyz(x, 0.0, 0.0), 1.0, color);
+        }
+
+        batch.prepare(&HavokPosition::from_xyz(0.0, 0.0, 0.0));
+        let colors: Vec<_> = batch.shapes.iter().map(|(color, _)| *color).collect();
+        assert_eq!(colors, [blue, red, red]);
+    }
+}
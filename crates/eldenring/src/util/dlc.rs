@@ -0,0 +1,67 @@
+//! Queries for which DLC and bonus content the current user is entitled to.
+//!
+//! The game resolves entitlements through Steam and caches the result, so
+//! these go to the same Steam interface instead of guessing from which
+//! archives happen to be loaded. Steam is initialized by the game itself
+//! before any mod code runs, so no client needs to be created here.
+use steamworks_sys as sys;
+use thiserror::Error;
+
+/// Steam app ID of the base game.
+pub const ELDEN_RING_APP_ID: u32 = 1245620;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dlc {
+    ShadowOfTheErdtree,
+    /// Any other DLC or bonus content by its Steam app ID.
+    Other(u32),
+}
+
+impl Dlc {
+    pub fn app_id(&self) -> u32 {
+        match self {
+            Dlc::ShadowOfTheErdtree => 2778580,
+            Dlc::Other(app_id) => *app_id,
+        }
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum EntitlementError {
+    #[error("Steam has not been initialized yet")]
+    SteamUnavailable,
+}
+
+fn steam_apps() -> Result<*mut sys::ISteamApps, EntitlementError> {
+    let apps = unsafe { sys::SteamAPI_SteamApps_v008() };
+    if apps.is_null() {
+        return Err(EntitlementError::SteamUnavailable);
+    }
+    Ok(apps)
+}
+
+/// Whether the user owns the content. Owning it doesn't mean it's been
+/// downloaded yet, see [is_dlc_installed].
+pub fn is_dlc_owned(dlc: Dlc) -> Result<bool, EntitlementError> {
+    let apps = steam_apps()?;
+    Ok(unsafe { sys::SteamAPI_ISteamApps_BIsSubscribedApp(apps, dlc.app_id()) })
+}
+
+/// Whether the content is owned and installed, which is what the game checks
+/// before it mounts the DLC's archives.
+pub fn is_dlc_installed(dlc: Dlc) -> Result<bool, EntitlementError> {
+    let apps = steam_apps()?;
+    Ok(unsafe { sys::SteamAPI_ISteamApps_BIsDlcInstalled(apps, dlc.app_id()) })
+}
+
+/// Filters `required` down to the content that isn't installed, so a mod
+/// depending on DLC content can disable itself or warn the user.
+pub fn missing_dlc(required: &[Dlc]) -> Result<Vec<Dlc>, EntitlementError> {
+    let mut missing = Vec::new();
+    for dlc in required {
+        if !is_dlc_installed(*dlc)? {
+            missing.push(*dlc);
+        }
+    }
+    Ok(missing)
+}
@@ -0,0 +1,129 @@
+//! Trading visual fidelity for performance at runtime.
+//!
+//! This scales the distances in the params that drive the game's LODs. Map
+//! parts read their [PARTS_DRAW_PARAM_ST]
+//! row when they're loaded, so changes show up as parts stream in or after
+//! reloading the area. Scales are always applied to the values the game
+//! loaded from regulation and never compound.
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use shared::{FromStatic, InstanceError};
+
+use crate::fd4::FD4ParamRepository;
+use crate::param::{GRASS_LOD_RANGE_PARAM_ST, PARTS_DRAW_PARAM_ST, ParamDef};
+
+/// A group of distances that can be scaled together.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DrawDistanceCategory {
+    /// Distances at which map parts switch between their LOD levels. Lower
+    /// values switch to less detailed models sooner.
+    LodBias,
+    /// Distances at which map parts stop being drawn.
+    Parts,
+    /// Distances at which map parts stop casting shadows.
+    Shadows,
+    /// Distances at which grass switches LOD levels and disappears.
+    Grass,
+}
+
+#[derive(Default)]
+struct DrawDistanceState {
+    scales: HashMap<DrawDistanceCategory, f32>,
+    parts: HashMap<u32, PARTS_DRAW_PARAM_ST>,
+    grass: HashMap<u32, GRASS_LOD_RANGE_PARAM_ST>,
+}
+
+static STATE: LazyLock<Mutex<DrawDistanceState>> = LazyLock::new(Default::default);
+
+/// The scale currently applied to a category, 1.0 if it hasn't been changed.
+pub fn draw_distance_scale(category: DrawDistanceCategory) -> f32 {
+    STATE
+        .lock()
+        .unwrap()
+        .scales
+        .get(&category)
+        .copied()
+        .unwrap_or(1.0)
+}
+
+/// Scales every distance in a category. A scale of 1.0 restores the game's
+/// own distances.
+pub fn set_draw_distance_scale(
+    category: DrawDistanceCategory,
+    scale: f32,
+) -> Result<(), InstanceError> {
+    let param_repository = unsafe { FD4ParamRepository::instance() }?;
+    let mut state = STATE.lock().unwrap();
+    state.scales.insert(category, scale);
+
+    let DrawDistanceState {
+        scales,
+        parts,
+        grass,
+    } = &mut *state;
+    let scale = |category| scales.get(&category).copied().unwrap_or(1.0);
+
+    match category {
+        DrawDistanceCategory::Grass => {
+            for (id, row) in original_rows(param_repository, grass) {
+                let Some(current) = param_repository.get_mut::<GRASS_LOD_RANGE_PARAM_ST>(id) else {
+                    continue;
+                };
+
+                let grass_scale = scale(DrawDistanceCategory::Grass);
+                current.set_lod0_range(row.lod0_range() * grass_scale);
+                current.set_lod0_play(row.lod0_play() * grass_scale);
+                current.set_lod1_range(row.lod1_range() * grass_scale);
+                current.set_lod1_play(row.lod1_play() * grass_scale);
+                current.set_lod2_range(row.lod2_range() * grass_scale);
+                current.set_lod2_play(row.lod2_play() * grass_scale);
+            }
+        }
+        _ => {
+            for (id, row) in original_rows(param_repository, parts) {
+                let Some(current) = param_repository.get_mut::<PARTS_DRAW_PARAM_ST>(id) else {
+                    continue;
+                };
+
+                let lod_scale = scale(DrawDistanceCategory::LodBias);
+                current.set_lv01_border_dist(row.lv01_border_dist() * lod_scale);
+                current.set_lv01_play_dist(row.lv01_play_dist() * lod_scale);
+                current.set_lv12_border_dist(row.lv12_border_dist() * lod_scale);
+                current.set_lv12_play_dist(row.lv12_play_dist() * lod_scale);
+                current.set_lv23_border_dist(row.lv23_border_dist() * lod_scale);
+                current.set_lv23_play_dist(row.lv23_play_dist() * lod_scale);
+                current.set_lv34_border_dist(row.lv34_border_dist() * lod_scale);
+                current.set_lv34_play_dist(row.lv34_play_dist() * lod_scale);
+                current.set_lv45_border_dist(row.lv45_border_dist() * lod_scale);
+                current.set_lv45_play_dist(row.lv45_play_dist() * lod_scale);
+
+                current.set_draw_dist(row.draw_dist() * scale(DrawDistanceCategory::Parts));
+                current.set_shadow_draw_dist(
+                    row.shadow_draw_dist() * scale(DrawDistanceCategory::Shadows),
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Copies of the unmodified rows, taken the first time a param is changed.
+fn original_rows<T: ParamDef + Clone>(
+    param_repository: &FD4ParamRepository,
+    originals: &mut HashMap<u32, T>,
+) -> Vec<(u32, T)> {
+    if originals.is_empty() {
+        for id in param_repository.row_ids::<T>() {
+            if let Some(row) = param_repository.get::<T>(id) {
+                originals.insert(id, row.clone());
+            }
+        }
+    }
+
+    originals
+        .iter()
+        .map(|(id, row)| (*id, row.clone()))
+        .collect()
+}
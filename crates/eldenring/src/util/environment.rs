@@ -0,0 +1,86 @@
+//! The surfaces characters are in contact with and the effects they apply.
+//!
+//! Environmental hazards like scarlet rot pools or lava are surfaces whose
+//! HitMtrlParam row applies a SpEffect on contact, with separate SpEffects
+//! for when the surface is wet. Reading the surface a character stands on and
+//! editing what the row applies covers most environment interactions.
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::ChrIns;
+use crate::fd4::FD4ParamRepository;
+use crate::param::HIT_MTRL_PARAM_ST;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+/// The surface a character is standing on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SurfaceContact {
+    /// Material ID of the surface as reported by the character's physics.
+    pub hit_material: i32,
+    /// Material param ID tracked by the character's material module.
+    pub material_param_id: i16,
+    /// Whether the surface makes the character always slide.
+    pub is_slippery: bool,
+    /// Whether the surface disables fall damage.
+    pub disables_fall_damage: bool,
+}
+
+pub fn surface_contact(chr_ins: &ChrIns) -> SurfaceContact {
+    let modules = &chr_ins.module_container;
+    let material_info = &modules.physics.material_info;
+    SurfaceContact {
+        hit_material: material_info.hit_material,
+        material_param_id: modules.material.material_param_id,
+        is_slippery: material_info.is_slippery_surface,
+        disables_fall_damage: modules.material.disable_fall_damage,
+    }
+}
+
+/// The SpEffects a HitMtrlParam row applies to characters touching it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HitMaterialEffects {
+    pub on_hit: [i32; 2],
+    /// SpEffects used while the surface is wet.
+    pub wet: [i32; 5],
+}
+
+/// Reads the SpEffects applied by a HitMtrlParam row.
+pub fn hit_material_effects(row_id: u32) -> Result<Option<HitMaterialEffects>, InstanceError> {
+    let param_repository = unsafe { FD4ParamRepository::instance() }?;
+    Ok(param_repository
+        .get::<HIT_MTRL_PARAM_ST>(row_id)
+        .map(|row| HitMaterialEffects {
+            on_hit: [row.sp_effect_id_on_hit0(), row.sp_effect_id_on_hit1()],
+            wet: [
+                row.sp_effect_id_for_wet00(),
+                row.sp_effect_id_for_wet01(),
+                row.sp_effect_id_for_wet02(),
+                row.sp_effect_id_for_wet03(),
+                row.sp_effect_id_for_wet04(),
+            ],
+        }))
+}
+
+/// Changes the SpEffects applied by a HitMtrlParam row. Returns false if the
+/// row doesn't exist.
+pub fn set_hit_material_effects(
+    row_id: u32,
+    effects: HitMaterialEffects,
+) -> Result<bool, OnlineGuardError> {
+    check_online_guard()?;
+    let param_repository = unsafe { FD4ParamRepository::instance() }?;
+    let Some(row) = param_repository.get_mut::<HIT_MTRL_PARAM_ST>(row_id) else {
+        return Ok(false);
+    };
+
+    let [hit0, hit1] = effects.on_hit;
+    row.set_sp_effect_id_on_hit0(hit0);
+    row.set_sp_effect_id_on_hit1(hit1);
+
+    let [wet0, wet1, wet2, wet3, wet4] = effects.wet;
+    row.set_sp_effect_id_for_wet00(wet0);
+    row.set_sp_effect_id_for_wet01(wet1);
+    row.set_sp_effect_id_for_wet02(wet2);
+    row.set_sp_effect_id_for_wet03(wet3);
+    row.set_sp_effect_id_for_wet04(wet4);
+    Ok(true)
+}
@@ -0,0 +1,255 @@
+//! Equip load and poise calculations for build planners.
+//!
+//! Weights and armor poise are summed from the equipped items' rows in the
+//! live params, so they follow any param changes made by other mods. The roll
+//! tier thresholds aren't part of any param, the game compares the equip load
+//! ratio against fixed values.
+//!
+//! Poise from talismans and other SpEffects isn't included in [armor_poise].
+//! [verify_player] compares the calculation against what the game is actually
+//! using for the local player, a mismatch means something other than the
+//! armor is contributing.
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{ChrAsm, ChrAsmSlot, WorldChrMan};
+use crate::fd4::FD4ParamRepository;
+use crate::param::{
+    ATK_PARAM_ST, EQUIP_PARAM_ACCESSORY_ST, EQUIP_PARAM_PROTECTOR_ST, EQUIP_PARAM_WEAPON_ST,
+};
+
+/// Equip load ratio from which the player rolls at medium speed.
+pub const MEDIUM_LOAD_RATIO: f32 = 0.3;
+/// Equip load ratio from which the player rolls at heavy speed.
+pub const HEAVY_LOAD_RATIO: f32 = 0.7;
+/// Equip load ratio above which the player can't roll anymore.
+pub const OVERLOADED_RATIO: f32 = 1.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum EquipLoadTier {
+    Light,
+    Medium,
+    Heavy,
+    Overloaded,
+}
+
+impl EquipLoadTier {
+    pub fn from_ratio(ratio: f32) -> Self {
+        if ratio < MEDIUM_LOAD_RATIO {
+            EquipLoadTier::Light
+        } else if ratio < HEAVY_LOAD_RATIO {
+            EquipLoadTier::Medium
+        } else if ratio <= OVERLOADED_RATIO {
+            EquipLoadTier::Heavy
+        } else {
+            EquipLoadTier::Overloaded
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EquipLoad {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl EquipLoad {
+    pub fn ratio(&self) -> f32 {
+        if self.max > 0.0 {
+            self.current / self.max
+        } else {
+            f32::INFINITY
+        }
+    }
+
+    pub fn tier(&self) -> EquipLoadTier {
+        EquipLoadTier::from_ratio(self.ratio())
+    }
+
+    /// Distance to the upper threshold of `tier`, None if the load already is
+    /// past it. The light and medium thresholds belong to the next tier, so
+    /// only less than that much can be added while staying in them.
+    pub fn headroom(&self, tier: EquipLoadTier) -> Option<f32> {
+        let (limit, inclusive) = match tier {
+            EquipLoadTier::Light => (self.max * MEDIUM_LOAD_RATIO, false),
+            EquipLoadTier::Medium => (self.max * HEAVY_LOAD_RATIO, false),
+            EquipLoadTier::Heavy => (self.max * OVERLOADED_RATIO, true),
+            EquipLoadTier::Overloaded => return Some(f32::INFINITY),
+        };
+
+        let within = if inclusive {
+            self.current <= limit
+        } else {
+            self.current < limit
+        };
+        within.then(|| limit - self.current)
+    }
+}
+
+/// Param ID of the weapon's EquipParamWeapon row, without the upgrade level.
+fn weapon_row_id(param_id: i32) -> Option<u32> {
+    u32::try_from(param_id).ok().map(|id| id - id % 100)
+}
+
+fn slot_param_id(chr_asm: &ChrAsm, slot: ChrAsmSlot) -> Option<u32> {
+    u32::try_from(chr_asm.equipment_param_ids[slot as usize]).ok()
+}
+
+/// Total weight of everything equipped, slots whose row doesn't exist count
+/// as weightless.
+pub fn equipped_weight(chr_asm: &ChrAsm) -> Result<f32, InstanceError> {
+    use ChrAsmSlot::*;
+
+    let params = unsafe { FD4ParamRepository::instance() }?;
+
+    let weapons = [
+        WeaponLeft1,
+        WeaponRight1,
+        WeaponLeft2,
+        WeaponRight2,
+        WeaponLeft3,
+        WeaponRight3,
+        Arrow1,
+        Bolt1,
+        Arrow2,
+        Bolt2,
+        Arrow3,
+        Bolt3,
+    ]
+    .into_iter()
+    .filter_map(|slot| weapon_row_id(chr_asm.equipment_param_ids[slot as usize]))
+    .filter_map(|id| params.get::<EQUIP_PARAM_WEAPON_ST>(id))
+    .map(|row| row.weight());
+
+    let protectors = [ProtectorHead, ProtectorChest, ProtectorHands, ProtectorLegs]
+        .into_iter()
+        .filter_map(|slot| slot_param_id(chr_asm, slot))
+        .filter_map(|id| params.get::<EQUIP_PARAM_PROTECTOR_ST>(id))
+        .map(|row| row.weight());
+
+    let accessories = [Accessory1, Accessory2, Accessory3, Accessory4]
+        .into_iter()
+        .filter_map(|slot| slot_param_id(chr_asm, slot))
+        .filter_map(|id| params.get::<EQUIP_PARAM_ACCESSORY_ST>(id))
+        .map(|row| row.weight());
+
+    Ok(weapons.chain(protectors).chain(accessories).sum())
+}
+
+/// Poise of the equipped armor pieces.
+pub fn armor_poise(chr_asm: &ChrAsm) -> Result<f32, InstanceError> {
+    use ChrAsmSlot::*;
+
+    let params = unsafe { FD4ParamRepository::instance() }?;
+    Ok(
+        [ProtectorHead, ProtectorChest, ProtectorHands, ProtectorLegs]
+            .into_iter()
+            .filter_map(|slot| slot_param_id(chr_asm, slot))
+            .filter_map(|id| params.get::<EQUIP_PARAM_PROTECTOR_ST>(id))
+            .map(|row| row.sa_durability())
+            .sum(),
+    )
+}
+
+/// Poise damage of an attack, before the target's own damage rates. Weapon
+/// attacks scale the weapon's poise damage by the attack's correction, taken
+/// as a percentage, on top of the attack's flat poise damage. This is the
+/// formula as documented by the community, the game's own poise damage
+/// calculation isn't mapped to check it against.
+pub fn poise_damage(atk: &ATK_PARAM_ST, weapon: Option<&EQUIP_PARAM_WEAPON_ST>) -> f32 {
+    let weapon_damage = weapon.map_or(0.0, |weapon| {
+        weapon.sa_weapon_damage() * atk.atk_super_armor_correction() / 100.0
+    });
+    atk.atk_super_armor() + weapon_damage
+}
+
+/// Hits of `damage` it takes to break `poise`, None if they never do.
+pub fn hits_to_break(poise: f32, damage: f32) -> Option<u32> {
+    if damage <= 0.0 {
+        return None;
+    }
+    Some((poise / damage).ceil().max(1.0) as u32)
+}
+
+/// Lowest poise that doesn't break within `hits` hits of `damage`.
+pub fn poise_breakpoint(hits: u32, damage: f32) -> f32 {
+    (hits as f32 * damage).next_up()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PoiseVerification {
+    /// [armor_poise] of the player's current equipment.
+    pub calculated: f32,
+    /// Maximum poise the game uses for the player.
+    pub engine: f32,
+}
+
+impl PoiseVerification {
+    pub fn matches(&self, tolerance: f32) -> bool {
+        (self.calculated - self.engine).abs() <= tolerance
+    }
+}
+
+/// Current equip load of the local player, None if no player is loaded.
+pub fn player_equip_load() -> Result<Option<EquipLoad>, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let Some(player) = world_chr_man.main_player.as_ref() else {
+        return Ok(None);
+    };
+
+    let game_data = &player.player_game_data;
+    Ok(Some(EquipLoad {
+        current: equipped_weight(&game_data.chr_asm)?,
+        max: game_data.max_equip_load,
+    }))
+}
+
+/// Cross-checks [armor_poise] against the local player's super armor module,
+/// None if no player is loaded.
+pub fn verify_player() -> Result<Option<PoiseVerification>, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let Some(player) = world_chr_man.main_player.as_ref() else {
+        return Ok(None);
+    };
+
+    Ok(Some(PoiseVerification {
+        calculated: armor_poise(&player.player_game_data.chr_asm)?,
+        engine: player
+            .chr_ins
+            .module_container
+            .super_armor
+            .sa_durability_max,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{EquipLoad, EquipLoadTier, hits_to_break, poise_breakpoint};
+
+    #[test]
+    fn tiers_and_headroom() {
+        let load = EquipLoad {
+            current: 20.0,
+            max: 100.0,
+        };
+        assert_eq!(load.tier(), EquipLoadTier::Light);
+        assert!((load.headroom(EquipLoadTier::Light).unwrap() - 10.0).abs() < 0.001);
+        assert_eq!(load.headroom(EquipLoadTier::Heavy), Some(80.0));
+
+        let load = EquipLoad {
+            current: 70.0,
+            max: 100.0,
+        };
+        assert_eq!(load.tier(), EquipLoadTier::Heavy);
+        assert_eq!(load.headroom(EquipLoadTier::Medium), None);
+        assert_eq!(EquipLoadTier::from_ratio(1.01), EquipLoadTier::Overloaded);
+    }
+
+    #[test]
+    fn breakpoints() {
+        assert_eq!(hits_to_break(51.0, 25.0), Some(3));
+        assert_eq!(hits_to_break(50.0, 25.0), Some(2));
+        assert_eq!(hits_to_break(10.0, 0.0), None);
+        assert!(poise_breakpoint(2, 25.0) > 50.0);
+        assert_eq!(hits_to_break(poise_breakpoint(2, 25.0), 25.0), Some(3));
+    }
+}
@@ -0,0 +1,64 @@
+//! Human-readable names for known event flags, meant for debugging overlays
+//! and logging. Only available with the `event-flag-names` feature.
+//!
+//! The database is `data/event_flags.tsv`, one `id<TAB>category<TAB>name`
+//! entry per line, embedded as is and parsed the first time a flag is
+//! described.
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::LazyLock;
+
+static DATABASE_TEXT: &str = include_str!("../../data/event_flags.tsv");
+
+static DATABASE: LazyLock<HashMap<u32, FlagDescription>> = LazyLock::new(|| {
+    DATABASE_TEXT
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let id = parts.next()?.parse().ok()?;
+            let category = parts.next()?;
+            let name = parts.next()?;
+            Some((id, FlagDescription { category, name }))
+        })
+        .collect()
+});
+
+/// A known event flag along with what it's used for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FlagDescription {
+    /// Broad grouping of the flag, e.g. "Boss" or "Grace".
+    pub category: &'static str,
+    pub name: &'static str,
+}
+
+impl Display for FlagDescription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.category, self.name)
+    }
+}
+
+/// Looks up the description of an event flag, if it's a known one.
+pub fn describe_flag(id: u32) -> Option<FlagDescription> {
+    DATABASE.get(&id).copied()
+}
+
+/// Yields every known flag in a category, in no particular order.
+pub fn flags_in_category(category: &str) -> impl Iterator<Item = (u32, FlagDescription)> + '_ {
+    DATABASE
+        .iter()
+        .filter(move |(_, d)| d.category == category)
+        .map(|(id, d)| (*id, *d))
+}
+
+/// Yields every known flag, in no particular order.
+pub fn known_flags() -> impl Iterator<Item = (u32, FlagDescription)> {
+    DATABASE.iter().map(|(id, d)| (*id, *d))
+}
+
+/// Every category used by the known flags, sorted.
+pub fn categories() -> Vec<&'static str> {
+    let mut categories = DATABASE.values().map(|d| d.category).collect::<Vec<_>>();
+    categories.sort_unstable();
+    categories.dedup();
+    categories
+}
@@ -0,0 +1,113 @@
+//! Fall distance tracking, gravity overrides and fall damage free zones for
+//! the local player, done from the physics module.
+//!
+//! This doesn't go through the character's fall module, which this crate
+//! doesn't bind, so there's no access to the game's own fall distance or
+//! fall death thresholds. [update_fall] remembers the height a fall started
+//! at, and applies the overrides by writing to the same physics and material
+//! fields the game updates every frame. For that reason it needs to be called
+//! every frame, after the game's own physics update.
+use std::sync::Mutex;
+
+use glam::Vec3;
+use shared::FromStatic;
+
+use crate::cs::WorldChrMan;
+use crate::position::HavokPosition;
+use crate::util::audited::set;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+/// A sphere in which the player doesn't take fall damage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FallDamageZone {
+    pub center: HavokPosition,
+    pub radius: f32,
+}
+
+impl FallDamageZone {
+    pub fn contains(&self, position: HavokPosition) -> bool {
+        Vec3::from(self.center).distance_squared(position.into()) <= self.radius * self.radius
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FallState {
+    pub is_falling: bool,
+    pub is_touching_ground: bool,
+    pub gravity_multiplier: f32,
+    /// How far the player has fallen since the current fall started, zero
+    /// when not falling.
+    pub fall_distance: f32,
+}
+
+struct FallTracking {
+    fall_start_height: Option<f32>,
+    gravity_override: Option<f32>,
+    zones: Vec<FallDamageZone>,
+}
+
+static TRACKING: Mutex<FallTracking> = Mutex::new(FallTracking {
+    fall_start_height: None,
+    gravity_override: None,
+    zones: Vec::new(),
+});
+
+/// Forces the player's gravity multiplier, or hands it back to the game when
+/// `multiplier` is None.
+pub fn set_gravity_override(multiplier: Option<f32>) {
+    TRACKING.lock().unwrap().gravity_override = multiplier;
+}
+
+/// Replaces the zones in which the player doesn't take fall damage.
+pub fn set_fall_damage_zones(zones: Vec<FallDamageZone>) {
+    TRACKING.lock().unwrap().zones = zones;
+}
+
+/// Tracks the player's fall and applies the overrides. Returns the player's
+/// current fall state, or None if no player is loaded. Fails while the
+/// [online guard](crate::util::online_guard) refuses, as long as there are
+/// overrides to apply.
+pub fn update_fall() -> Result<Option<FallState>, OnlineGuardError> {
+    let mut tracking = TRACKING.lock().unwrap();
+    if tracking.gravity_override.is_some() || !tracking.zones.is_empty() {
+        check_online_guard()?;
+    }
+
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let Some(player) = world_chr_man.main_player.as_mut() else {
+        tracking.fall_start_height = None;
+        return Ok(None);
+    };
+
+    let modules = &mut *player.chr_ins.module_container;
+    let physics = &mut *modules.physics;
+    let height = physics.position.1;
+
+    let fall_distance = match (physics.is_falling, tracking.fall_start_height) {
+        (true, Some(start)) => (start - height).max(0.0),
+        (true, None) => {
+            tracking.fall_start_height = Some(height);
+            0.0
+        }
+        (false, _) => {
+            tracking.fall_start_height = None;
+            0.0
+        }
+    };
+
+    if let Some(multiplier) = tracking.gravity_override {
+        set(&mut physics.gravity_multiplier, multiplier);
+    }
+
+    let position = physics.position;
+    if tracking.zones.iter().any(|zone| zone.contains(position)) {
+        set(&mut modules.material.disable_fall_damage, true);
+    }
+
+    Ok(Some(FallState {
+        is_falling: physics.is_falling,
+        is_touching_ground: physics.is_touching_ground,
+        gravity_multiplier: physics.gravity_multiplier,
+        fall_distance,
+    }))
+}
@@ -0,0 +1,81 @@
+//! Boss arena fog walls.
+//!
+//! Fog walls are map assets placed in the MSB like any other gimmick, and the
+//! arena's event script decides whether they can be walked through and when
+//! they disappear, going by the boss's defeated flag. Neither that script's
+//! state nor a way to destroy geometry instances is mapped, so fog walls can
+//! only be added with [spawn_fog_wall] and stay until their block unloads. An
+//! arena's entry is toggled through [BossArena::set_cleared], the same way
+//! [MapGimmick](super::gimmick::MapGimmick) drives other gimmicks.
+use std::ptr::NonNull;
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{BlockId, CSEventFlagMan, CSWorldGeomIns, CSWorldGeomMan, GeometrySpawnParameters};
+
+use super::audited::set_flag;
+use super::gimmick::find_geometry;
+use super::online_guard::{OnlineGuardError, check_online_guard};
+
+/// A boss arena identified by the MSB parts of its fog walls, ex.
+/// "AEG099_001_9000", and the flag set once its boss is defeated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BossArena {
+    pub fog_wall_parts: &'static [&'static str],
+    pub defeated_flag: u32,
+}
+
+impl BossArena {
+    pub const fn new(fog_wall_parts: &'static [&'static str], defeated_flag: u32) -> Self {
+        Self {
+            fog_wall_parts,
+            defeated_flag,
+        }
+    }
+
+    /// The arena's fog walls that are currently loaded.
+    pub fn fog_walls(&self) -> Result<Vec<&'static mut CSWorldGeomIns>, InstanceError> {
+        let mut fog_walls = Vec::new();
+        for part_name in self.fog_wall_parts {
+            fog_walls.extend(find_geometry(part_name)?);
+        }
+        Ok(fog_walls)
+    }
+
+    pub fn is_cleared(&self) -> Result<bool, InstanceError> {
+        let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+        Ok(event_flag_man
+            .virtual_memory_flag
+            .get_flag(self.defeated_flag))
+    }
+
+    /// Sets the boss's defeated flag, which makes the arena's event script
+    /// drop the fog walls, or clears it to put them back for a rematch. Like
+    /// any defeated flag, it also counts for everything else checking it, ex.
+    /// the boss respawning. The flag is only set locally.
+    pub fn set_cleared(&self, cleared: bool) -> Result<(), OnlineGuardError> {
+        check_online_guard()?;
+        let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+        set_flag(
+            &mut event_flag_man.virtual_memory_flag,
+            self.defeated_flag,
+            cleared,
+        );
+        Ok(())
+    }
+}
+
+/// Places a fog wall asset, ex. "AEG099_001", in a loaded block. Spawned fog
+/// walls aren't driven by any event script and stay as they are until the
+/// block is unloaded. Returns None if the block isn't loaded or the game
+/// refused to spawn the asset.
+pub fn spawn_fog_wall(
+    block_id: &BlockId,
+    asset: &str,
+    parameters: &GeometrySpawnParameters,
+) -> Result<Option<NonNull<CSWorldGeomIns>>, InstanceError> {
+    let world_geom_man = unsafe { CSWorldGeomMan::instance() }?;
+    Ok(world_geom_man
+        .geom_block_data_by_id_mut(block_id)
+        .and_then(|block_data| block_data.spawn_geometry(asset, parameters)))
+}
@@ -0,0 +1,69 @@
+//! Changing the game's frame rate cap.
+//!
+//! The cap is enforced by [CSFlipper], which paces frames towards its fixed
+//! FPS and derives the task delta time from it. This only changes the
+//! flipper's pacing and doesn't patch any game code, so caps above 60 only
+//! go as far as the flipper's pacing gets.
+//!
+//! ## Physics
+//!
+//! Parts of the game assume it runs at 60 FPS or lower. Above that, ladder
+//! climbing, jump height, some animations and Havok based interactions are
+//! known to misbehave, so a warning is logged when a higher cap is set.
+use std::sync::Mutex;
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::CSFlipper;
+
+/// Highest frame rate the game's simulation is known to behave at.
+pub const PHYSICS_SAFE_FRAME_RATE: u32 = 60;
+
+#[derive(Clone, Copy)]
+struct FlipperPacing {
+    fixed_fps: f32,
+    dynamic_fps_lock: f32,
+    use_dynamic_fps_lock: bool,
+}
+
+/// The game's own pacing from before the first override, used to restore it.
+static ORIGINAL_PACING: Mutex<Option<FlipperPacing>> = Mutex::new(None);
+
+/// Caps the frame rate at `limit` FPS, or restores the game's own cap when
+/// passed None.
+pub fn set_frame_rate_limit(limit: Option<u32>) -> Result<(), InstanceError> {
+    let flipper = unsafe { CSFlipper::instance() }?;
+    let mut original = ORIGINAL_PACING.lock().unwrap();
+
+    let Some(limit) = limit else {
+        if let Some(pacing) = original.take() {
+            flipper.fixed_fps = pacing.fixed_fps;
+            flipper.dynamic_fps_lock = pacing.dynamic_fps_lock;
+            flipper.use_dynamic_fps_lock = pacing.use_dynamic_fps_lock;
+        }
+        return Ok(());
+    };
+
+    if limit > PHYSICS_SAFE_FRAME_RATE {
+        tracing::warn!(
+            "Frame rate limit {limit} is above {PHYSICS_SAFE_FRAME_RATE}, physics and animations may misbehave"
+        );
+    }
+
+    original.get_or_insert(FlipperPacing {
+        fixed_fps: flipper.fixed_fps,
+        dynamic_fps_lock: flipper.dynamic_fps_lock,
+        use_dynamic_fps_lock: flipper.use_dynamic_fps_lock,
+    });
+
+    flipper.fixed_fps = limit as f32;
+    flipper.dynamic_fps_lock = limit as f32;
+    flipper.use_dynamic_fps_lock = false;
+    flipper.reset_frame_history_count = true;
+    Ok(())
+}
+
+/// The frame rate the game is currently pacing towards.
+pub fn frame_rate_limit() -> Result<f32, InstanceError> {
+    Ok(unsafe { CSFlipper::instance() }?.fixed_fps)
+}
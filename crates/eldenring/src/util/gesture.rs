@@ -0,0 +1,52 @@
+//! Plays gestures on the local player.
+//!
+//! Gestures are requested the same way the gesture menu does, through the
+//! player's action request module. The game then plays them like any other
+//! gesture, including the restrictions on when one can be performed.
+use shared::{FromStatic, InstanceError};
+use thiserror::Error;
+
+use crate::cs::WorldChrMan;
+use crate::fd4::FD4ParamRepository;
+use crate::param::GESTURE_PARAM_ST;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+#[derive(Error, Debug)]
+pub enum GestureError {
+    #[error("Could not get instance: {0}")]
+    Instance(#[from] InstanceError),
+    #[error("No player character is loaded")]
+    NoPlayer,
+    #[error("Gesture row {0} does not exist")]
+    UnknownGesture(i32),
+    #[error(transparent)]
+    Online(#[from] OnlineGuardError),
+}
+
+/// Requests the player to perform the gesture described by a GestureParam
+/// row.
+///
+/// Action requests are refreshed from the player's input every frame, so
+/// this may have to be called again until the gesture starts playing.
+pub fn play_gesture(gesture_param_id: i32) -> Result<(), GestureError> {
+    check_online_guard()?;
+    let param_repository = unsafe { FD4ParamRepository::instance() }?;
+    if param_repository
+        .get::<GESTURE_PARAM_ST>(gesture_param_id as u32)
+        .is_none()
+    {
+        return Err(GestureError::UnknownGesture(gesture_param_id));
+    }
+
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let player = &mut world_chr_man
+        .main_player
+        .as_mut()
+        .ok_or(GestureError::NoPlayer)?
+        .chr_ins;
+
+    let action_request = &mut player.module_container.action_request;
+    action_request.requested_gesture = gesture_param_id;
+    action_request.action_requests.set_gesture(true);
+    Ok(())
+}
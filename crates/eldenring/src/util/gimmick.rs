@@ -0,0 +1,88 @@
+//! Looks up map gimmicks like doors, levers and elevators and drives their
+//! state.
+//!
+//! A gimmick is looked up through the geometry instance it's built from, and
+//! driven through the event flag the map's event scripts use to remember
+//! whether it has been activated. Flipping that flag is how scripted content already opens doors
+//! and moves elevators, leaving the animation itself to the event script
+//! watching the flag. Because of that, a flag change only shows up once the
+//! block's event script picks it up, and only for gimmicks whose scripts react
+//! to the flag at runtime rather than only on load.
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{CSEventFlagMan, CSWorldGeomIns, CSWorldGeomMan};
+use crate::util::audited::set_flag;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+/// A map gimmick identified by the MSB part of its geometry, ex.
+/// "AEG099_001_9000", and the event flag its state is stored in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MapGimmick {
+    pub part_name: &'static str,
+    pub state_flag: u32,
+}
+
+impl MapGimmick {
+    pub const fn new(part_name: &'static str, state_flag: u32) -> Self {
+        Self {
+            part_name,
+            state_flag,
+        }
+    }
+
+    /// The gimmick's geometry instance, None if its block isn't loaded.
+    pub fn geometry(&self) -> Result<Option<&'static mut CSWorldGeomIns>, InstanceError> {
+        find_geometry(self.part_name)
+    }
+
+    /// Whether the gimmick has been activated, i.e. the door opened or the
+    /// lever pulled.
+    pub fn is_activated(&self) -> Result<bool, InstanceError> {
+        let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+        Ok(event_flag_man.virtual_memory_flag.get_flag(self.state_flag))
+    }
+
+    /// Activates or resets the gimmick by setting its state flag. The flag
+    /// is only set locally and isn't sent to other players.
+    pub fn set_activated(&self, activated: bool) -> Result<(), OnlineGuardError> {
+        check_online_guard()?;
+        let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+        set_flag(
+            &mut event_flag_man.virtual_memory_flag,
+            self.state_flag,
+            activated,
+        );
+        Ok(())
+    }
+}
+
+/// Returns the name of the MSB part a geometry instance was placed from.
+pub fn part_name(geometry: &CSWorldGeomIns) -> Option<String> {
+    let name = geometry.info.msb_parts_geom.msb_parts.msb_part.name;
+    if name.is_null() {
+        return None;
+    }
+
+    unsafe { name.to_string() }.ok()
+}
+
+/// Finds a loaded geometry instance by the name of its MSB part. The name is
+/// compared case insensitively.
+pub fn find_geometry(
+    part_name: &str,
+) -> Result<Option<&'static mut CSWorldGeomIns>, InstanceError> {
+    let world_geom_man = unsafe { CSWorldGeomMan::instance() }?;
+    Ok(world_geom_man.blocks.iter().find_map(|block| {
+        block
+            .data
+            .geom_ins_vector
+            .items_mut()
+            .iter_mut()
+            .find(|geometry| part_name_matches(geometry, part_name))
+            .map(|geometry| &mut **geometry)
+    }))
+}
+
+fn part_name_matches(geometry: &CSWorldGeomIns, expected: &str) -> bool {
+    part_name(geometry).is_some_and(|name| name.eq_ignore_ascii_case(expected))
+}
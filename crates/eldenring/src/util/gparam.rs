@@ -0,0 +1,137 @@
+//! Inspecting and overriding which gparams (lighting, fog, depth of field
+//! and so on) are applied to the world, by gparam ID.
+//!
+//! Every map area has a scene draw param block that can transition to an
+//! override gparam, which is the same mechanism the game uses for weather
+//! and scripted lighting changes.
+//!
+//! Lerper slots can also be pinned to a fixed transition so the lighting
+//! stops following the time of day.
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{BlockId, CSGparamIdLerper, CSWorldSceneDrawParamManager};
+
+/// A transition between two gparams that's in progress or has finished.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GparamTransition {
+    pub begin_id: i32,
+    pub destination_id: i32,
+    pub timer: f32,
+}
+
+impl From<&CSGparamIdLerper> for GparamTransition {
+    fn from(lerper: &CSGparamIdLerper) -> Self {
+        Self {
+            begin_id: lerper.begin_id,
+            destination_id: lerper.destination_id,
+            timer: lerper.timer,
+        }
+    }
+}
+
+/// Index of the lerper that blends the other slots together, as used by
+/// [gparam_transitions] and [pin_gparam_transition].
+pub const BLEND_LERPER_SLOT: usize = 15;
+
+static PINNED: LazyLock<Mutex<HashMap<usize, GparamTransition>>> = LazyLock::new(Default::default);
+
+/// The gparam transitions of every lerper slot of the world's scene draw
+/// param, followed by the one that blends them together.
+pub fn gparam_transitions() -> Result<Vec<GparamTransition>, InstanceError> {
+    let manager = unsafe { CSWorldSceneDrawParamManager::instance() }?;
+    let scene_draw_param = &manager.scene_draw_param;
+
+    Ok(scene_draw_param
+        .lerpers
+        .iter()
+        .chain([&scene_draw_param.lerper])
+        .map(GparamTransition::from)
+        .collect())
+}
+
+/// Holds a lerper slot at `transition` until it's unpinned. Returns false if
+/// there's no such slot. The pin is written by [apply_gparam_pins].
+pub fn pin_gparam_transition(slot: usize, transition: GparamTransition) -> bool {
+    if slot > BLEND_LERPER_SLOT {
+        return false;
+    }
+
+    PINNED.lock().unwrap().insert(slot, transition);
+    true
+}
+
+/// Lets the game drive a lerper slot again.
+pub fn unpin_gparam_transition(slot: usize) {
+    PINNED.lock().unwrap().remove(&slot);
+}
+
+/// Writes the pinned transitions over the game's own. This needs to be
+/// called every frame since the game updates the lerpers every frame too.
+pub fn apply_gparam_pins() -> Result<(), InstanceError> {
+    let pinned = PINNED.lock().unwrap();
+    if pinned.is_empty() {
+        return Ok(());
+    }
+
+    let manager = unsafe { CSWorldSceneDrawParamManager::instance() }?;
+    let scene_draw_param = &mut manager.scene_draw_param;
+    for (slot, lerper) in scene_draw_param
+        .lerpers
+        .iter_mut()
+        .chain([&mut scene_draw_param.lerper])
+        .enumerate()
+    {
+        if let Some(transition) = pinned.get(&slot) {
+            lerper.begin_id = transition.begin_id;
+            lerper.destination_id = transition.destination_id;
+            lerper.timer = transition.timer;
+        }
+    }
+
+    Ok(())
+}
+
+/// Transitions an area to the given gparam over `transition_duration`
+/// seconds. Returns false if the area doesn't have a scene draw param block,
+/// which happens when it isn't loaded.
+pub fn set_area_gparam_override(
+    area: BlockId,
+    gparam_id: u32,
+    transition_duration: f32,
+) -> Result<bool, InstanceError> {
+    let manager = unsafe { CSWorldSceneDrawParamManager::instance() }?;
+    let Some(block) = manager
+        .world_area_blocks
+        .iter_mut()
+        .find(|block| block.area == area)
+    else {
+        return Ok(false);
+    };
+
+    block.override_gparam = gparam_id;
+    block.override_transition_duration = transition_duration;
+    block.transition_to_override = true;
+    Ok(true)
+}
+
+/// Transitions an area back to its own gparam.
+pub fn clear_area_gparam_override(
+    area: BlockId,
+    transition_duration: f32,
+) -> Result<bool, InstanceError> {
+    let manager = unsafe { CSWorldSceneDrawParamManager::instance() }?;
+    let Some(block) = manager
+        .world_area_blocks
+        .iter_mut()
+        .find(|block| block.area == area)
+    else {
+        return Ok(false);
+    };
+
+    block.override_transition_duration = transition_duration;
+    block.transition_to_override = false;
+    Ok(true)
+}
@@ -0,0 +1,75 @@
+//! Reads and toggles the local player's great rune and Rune Arc state.
+//!
+//! [set_rune_arc_active] only flips the flags the game keeps in the player's
+//! game data, the same ones the HUD and the save file read from. Applying the
+//! great rune's effect is left to the game logic that reacts to those flags.
+use shared::{FromStatic, InstanceError};
+use thiserror::Error;
+
+use crate::cs::{ItemId, PlayerGameData, WorldChrMan};
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+#[derive(Error, Debug)]
+pub enum GreatRuneError {
+    #[error("Could not get instance: {0}")]
+    Instance(#[from] InstanceError),
+    #[error("No player character is loaded")]
+    NoPlayer,
+    #[error("No great rune is equipped")]
+    NoGreatRune,
+    #[error(transparent)]
+    Online(#[from] OnlineGuardError),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GreatRuneState {
+    /// The item ID of the equipped great rune, if any.
+    pub equipped: Option<ItemId>,
+    /// Whether a Rune Arc has been used to activate the great rune.
+    pub rune_arc_active: bool,
+}
+
+fn player_game_data() -> Result<&'static mut PlayerGameData, GreatRuneError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    Ok(&mut *world_chr_man
+        .main_player
+        .as_mut()
+        .ok_or(GreatRuneError::NoPlayer)?
+        .player_game_data)
+}
+
+fn equipped_great_rune(game_data: &PlayerGameData) -> Option<ItemId> {
+    let handle = game_data.equipment.equip_item_data.great_rune.gaitem_handle;
+
+    let items = &game_data.equipment.equip_inventory_data.items_data;
+    items
+        .key_items()
+        .iter()
+        .chain(items.normal_items())
+        .find(|item| item.gaitem_handle == handle)
+        .map(|item| item.item_id)
+}
+
+/// Reads the player's equipped great rune and whether it's active.
+pub fn great_rune_state() -> Result<GreatRuneState, GreatRuneError> {
+    let game_data = player_game_data()?;
+    Ok(GreatRuneState {
+        equipped: equipped_great_rune(game_data),
+        rune_arc_active: game_data.rune_arc_active,
+    })
+}
+
+/// Activates or deactivates the player's great rune as if a Rune Arc had been
+/// used, or the effect had worn off on death. Activating fails when no great
+/// rune is equipped, since the game never gets into that state by itself.
+pub fn set_rune_arc_active(active: bool) -> Result<(), GreatRuneError> {
+    check_online_guard()?;
+    let game_data = player_game_data()?;
+    if active && equipped_great_rune(game_data).is_none() {
+        return Err(GreatRuneError::NoGreatRune);
+    }
+
+    game_data.rune_arc_active = active;
+    game_data.frontend_flags.set_rune_arc_active(active);
+    Ok(())
+}
@@ -0,0 +1,63 @@
+//! Resolves menu icon IDs to the atlas page and region they're drawn from.
+//!
+//! The game doesn't keep a queryable index of its atlases around, so the
+//! layouts have to be registered up front, usually by reading the `.layout`
+//! files from the sblytbnd archives. Mods adding their own icons can register
+//! them here too so that overlays mirroring the game's icons pick them up.
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+use crate::formats::{SubTexture, TextureAtlas};
+
+/// Where an icon lives inside of the menu atlases.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IconLocation {
+    /// Texture name of the atlas page, e.g. `SB_Icon_03`.
+    pub page: String,
+    pub sub_texture: SubTexture,
+}
+
+static ICONS: LazyLock<RwLock<HashMap<u32, IconLocation>>> = LazyLock::new(Default::default);
+
+/// Registers every icon in an atlas layout, replacing earlier registrations
+/// of the same IDs.
+pub fn register_atlas(atlas: &TextureAtlas) {
+    let page = atlas.page_name();
+    let mut icons = ICONS.write().unwrap();
+    for sub_texture in &atlas.sub_textures {
+        if let Some(id) = sub_texture.icon_id() {
+            icons.insert(
+                id,
+                IconLocation {
+                    page: page.to_string(),
+                    sub_texture: sub_texture.clone(),
+                },
+            );
+        }
+    }
+}
+
+/// Registers a single icon, e.g. one added to the menu textures by a mod.
+pub fn register_icon(id: u32, page: impl Into<String>, sub_texture: SubTexture) {
+    ICONS.write().unwrap().insert(
+        id,
+        IconLocation {
+            page: page.into(),
+            sub_texture,
+        },
+    );
+}
+
+/// Looks up which atlas page and region icon `id` maps to.
+pub fn resolve_icon(id: u32) -> Option<IconLocation> {
+    ICONS.read().unwrap().get(&id).cloned()
+}
+
+/// Picks the lowest icon ID at or above `start` that isn't taken yet, for
+/// mods that need to allocate IDs for their own icons.
+pub fn next_free_icon_id(start: u32) -> u32 {
+    let icons = ICONS.read().unwrap();
+    (start..)
+        .find(|id| !icons.contains_key(id))
+        .unwrap_or(start)
+}
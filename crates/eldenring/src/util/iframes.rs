@@ -0,0 +1,217 @@
+//! Invincibility frame (iframe) flags of the local player, with events when
+//! a window starts and ends. There's no count of remaining frames.
+//!
+//! Iframes are granted by ChrActionFlag events in the animation's TAE, which
+//! set the matching flags in the character's
+//! [action flag module](crate::cs::CSChrActionFlagModule) for as long as the
+//! event lasts, so how long a window will last is only known once it's over.
+//! [update_iframes] watches the flags
+//! every frame and reports each window as it starts and ends, along with how
+//! long it lasted and the animation it came from.
+use std::sync::Mutex;
+
+use shared::{FromStatic, InstanceError, Listeners};
+
+use crate::cs::{ChrIns, WorldChrMan};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IframeFlags {
+    /// Immune to everything (action 94 PERFECT_INVINCIBILITY).
+    pub perfect: bool,
+    /// Only the lower body is immune, used while jumping (action 132).
+    pub lower_body: bool,
+    /// Immune to enemies but not to other players (action 143).
+    pub pve_only: bool,
+}
+
+impl IframeFlags {
+    pub fn from_chr_ins(chr_ins: &ChrIns) -> Self {
+        let flags = &chr_ins.module_container.action_flag.action_modifiers_flags;
+        Self {
+            perfect: flags.perfect_invincibility(),
+            lower_body: flags.jump_frames_lower_body_iframes(),
+            pve_only: flags.pve_only_iframes(),
+        }
+    }
+
+    pub fn any(&self) -> bool {
+        self.perfect || self.lower_body || self.pve_only
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IframeSample {
+    pub flags: IframeFlags,
+    /// The animation the character is playing.
+    pub anim_id: i32,
+    /// How far into the animation it is, in seconds.
+    pub anim_time: f32,
+}
+
+impl IframeSample {
+    pub fn from_chr_ins(chr_ins: &ChrIns) -> Self {
+        let time_act = &chr_ins.module_container.time_act;
+        let anim = &time_act.anim_queue[time_act.read_idx as usize % time_act.anim_queue.len()];
+        Self {
+            flags: IframeFlags::from_chr_ins(chr_ins),
+            anim_id: anim.anim_id,
+            anim_time: anim.play_time,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IframeEvent {
+    Started {
+        flags: IframeFlags,
+        anim_id: i32,
+        anim_time: f32,
+    },
+    Ended {
+        anim_id: i32,
+        /// Animation time the window started and ended at.
+        start_time: f32,
+        end_time: f32,
+        /// Updates the window was active for, i.e. frames if updated every
+        /// frame.
+        frames: u32,
+    },
+}
+
+#[derive(Default)]
+struct IframeTracker {
+    /// The window in progress: animation, start time and frame count.
+    active: Option<(i32, f32, u32)>,
+    last_time: f32,
+}
+
+impl IframeTracker {
+    fn observe(&mut self, sample: IframeSample) -> Vec<IframeEvent> {
+        let mut events = Vec::new();
+
+        // A window ends with its flags, or once a new animation took over.
+        if let Some((anim_id, start_time, frames)) = self.active {
+            if !sample.flags.any() || sample.anim_id != anim_id {
+                events.push(IframeEvent::Ended {
+                    anim_id,
+                    start_time,
+                    end_time: self.last_time,
+                    frames,
+                });
+                self.active = None;
+            } else {
+                self.active = Some((anim_id, start_time, frames + 1));
+            }
+        }
+
+        if self.active.is_none() && sample.flags.any() {
+            self.active = Some((sample.anim_id, sample.anim_time, 1));
+            events.push(IframeEvent::Started {
+                flags: sample.flags,
+                anim_id: sample.anim_id,
+                anim_time: sample.anim_time,
+            });
+        }
+
+        self.last_time = sample.anim_time;
+        events
+    }
+}
+
+struct IframeState {
+    tracker: IframeTracker,
+    sample: Option<IframeSample>,
+}
+
+static STATE: Mutex<IframeState> = Mutex::new(IframeState {
+    tracker: IframeTracker {
+        active: None,
+        last_time: 0.0,
+    },
+    sample: None,
+});
+
+static LISTENERS: Listeners<IframeEvent> = Listeners::new();
+
+/// Registers a listener that's called for every [IframeEvent].
+pub fn on_iframe_event(listener: impl FnMut(IframeEvent) + Send + 'static) {
+    LISTENERS.add(listener);
+}
+
+/// The player's iframe state as of the last [update_iframes].
+pub fn player_iframes() -> Option<IframeSample> {
+    STATE.lock().unwrap().sample
+}
+
+/// Samples the local player's iframes and notifies the listeners. This needs
+/// to be called every frame, e.g. from a recurring task in the
+/// `ChrIns_PostPhysics` group.
+pub fn update_iframes() -> Result<(), InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let sample = world_chr_man
+        .main_player
+        .as_ref()
+        .map(|player| IframeSample::from_chr_ins(&player.chr_ins));
+
+    let events = {
+        let mut state = STATE.lock().unwrap();
+        state.sample = sample;
+        let Some(sample) = sample else {
+            return Ok(());
+        };
+        state.tracker.observe(sample)
+    };
+
+    LISTENERS.notify(events);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{IframeEvent, IframeFlags, IframeSample, IframeTracker};
+
+    fn sample(perfect: bool, anim_id: i32, anim_time: f32) -> IframeSample {
+        IframeSample {
+            flags: IframeFlags {
+                perfect,
+                ..Default::default()
+            },
+            anim_id,
+            anim_time,
+        }
+    }
+
+    #[test]
+    fn reports_window_length() {
+        let mut tracker = IframeTracker::default();
+        assert!(tracker.observe(sample(false, 27000, 0.0)).is_empty());
+        assert!(matches!(
+            tracker.observe(sample(true, 27000, 0.1))[..],
+            [IframeEvent::Started { anim_id: 27000, .. }]
+        ));
+        assert!(tracker.observe(sample(true, 27000, 0.2)).is_empty());
+        assert_eq!(
+            tracker.observe(sample(false, 27000, 0.3)),
+            [IframeEvent::Ended {
+                anim_id: 27000,
+                start_time: 0.1,
+                end_time: 0.2,
+                frames: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn new_animation_starts_new_window() {
+        let mut tracker = IframeTracker::default();
+        tracker.observe(sample(true, 27000, 0.1));
+        let events = tracker.observe(sample(true, 27010, 0.0));
+        assert!(matches!(
+            events[..],
+            [
+                IframeEvent::Ended { anim_id: 27000, .. },
+                IframeEvent::Started { anim_id: 27010, .. }
+            ]
+        ));
+    }
+}
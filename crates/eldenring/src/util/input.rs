@@ -1,14 +1,21 @@
-use std::collections::{HashMap, hash_map::Entry};
-use std::sync::{LazyLock, Mutex};
-use std::time::{Duration, Instant};
-
+#[cfg(windows)]
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
+};
+#[cfg(windows)]
 use windows::Win32::UI::Input::KeyboardAndMouse;
 
+#[cfg(windows)]
 const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(250);
 
+#[cfg(windows)]
 type DebounceMap = HashMap<i32, Instant>;
+#[cfg(windows)]
 static DEBOUNCE_MAP: LazyLock<Mutex<DebounceMap>> = LazyLock::new(Default::default);
 
+#[cfg(windows)]
 pub fn is_key_pressed(key: i32) -> bool {
     if unsafe { KeyboardAndMouse::GetKeyState(key) } < 0 {
         let now = Instant::now();
@@ -0,0 +1,123 @@
+//! The input device the player used last, from polling the keyboard, the
+//! mouse and the first XInput controller, to show matching button glyphs.
+//!
+//! [update_input_device] switches to whichever device was used last.
+//! Controllers that don't go through XInput, like a DualSense without Steam
+//! Input, aren't seen, and XInput doesn't say which kind of controller is
+//! connected, so the glyph family for gamepads has to come from the mod's
+//! own settings.
+use std::sync::Mutex;
+
+use shared::Listeners;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputDevice {
+    KeyboardMouse,
+    Gamepad,
+}
+
+/// Stick deflection, out of 32767, below which a stick counts as untouched.
+pub const STICK_DEADZONE: i16 = 8000;
+/// Trigger pressure, out of 255, below which a trigger counts as untouched.
+pub const TRIGGER_THRESHOLD: u8 = 30;
+
+#[derive(Default)]
+struct DeviceTracker {
+    device: Option<InputDevice>,
+}
+
+impl DeviceTracker {
+    /// Returns the new device if it changed.
+    fn observe(&mut self, keyboard_mouse: bool, gamepad: bool) -> Option<InputDevice> {
+        // Stay with the current device while both are in use.
+        let used = match (keyboard_mouse, gamepad) {
+            (true, true) if self.device.is_some() => return None,
+            (true, _) => InputDevice::KeyboardMouse,
+            (false, true) => InputDevice::Gamepad,
+            (false, false) => return None,
+        };
+
+        (self.device.replace(used) != Some(used)).then_some(used)
+    }
+}
+
+struct DeviceState {
+    tracker: DeviceTracker,
+    cursor: Option<(i32, i32)>,
+}
+
+static STATE: Mutex<DeviceState> = Mutex::new(DeviceState {
+    tracker: DeviceTracker { device: None },
+    cursor: None,
+});
+
+static LISTENERS: Listeners<InputDevice> = Listeners::new();
+
+/// The device used last, None until the player touched anything.
+pub fn input_device() -> Option<InputDevice> {
+    STATE.lock().unwrap().tracker.device
+}
+
+/// Registers a listener that's called whenever the player switches devices.
+pub fn on_input_device_change(listener: impl FnMut(InputDevice) + Send + 'static) {
+    LISTENERS.add(listener);
+}
+
+/// Checks which devices are in use and notifies the listeners of a switch.
+/// This needs to be called every frame.
+#[cfg(windows)]
+pub fn update_input_device() {
+    use windows::Win32::Foundation::POINT;
+    use windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
+    use windows::Win32::UI::Input::XboxController::{XINPUT_STATE, XInputGetState};
+    use windows::Win32::UI::WindowsAndMessaging::GetCursorPos;
+
+    // Every virtual key code, mouse buttons included.
+    let key_down = (0x01..0xFF).any(|key| unsafe { GetAsyncKeyState(key) < 0 });
+
+    let mut point = POINT::default();
+    let cursor = unsafe { GetCursorPos(&mut point) }
+        .is_ok()
+        .then_some((point.x, point.y));
+
+    let mut state = XINPUT_STATE::default();
+    let gamepad = unsafe { XInputGetState(0, &mut state) } == 0 && {
+        let pad = &state.Gamepad;
+        pad.wButtons.0 != 0
+            || [pad.sThumbLX, pad.sThumbLY, pad.sThumbRX, pad.sThumbRY]
+                .iter()
+                .any(|axis| axis.unsigned_abs() > STICK_DEADZONE as u16)
+            || pad.bLeftTrigger > TRIGGER_THRESHOLD
+            || pad.bRightTrigger > TRIGGER_THRESHOLD
+    };
+
+    let switched = {
+        let mut device_state = STATE.lock().unwrap();
+        let previous_cursor = std::mem::replace(&mut device_state.cursor, cursor);
+        let mouse_moved = previous_cursor.is_some() && cursor != previous_cursor;
+
+        device_state
+            .tracker
+            .observe(key_down || mouse_moved, gamepad)
+    };
+
+    LISTENERS.notify(switched);
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DeviceTracker, InputDevice};
+
+    #[test]
+    fn switches_to_last_used_device() {
+        let mut tracker = DeviceTracker::default();
+        assert_eq!(tracker.observe(false, false), None);
+        assert_eq!(tracker.observe(false, true), Some(InputDevice::Gamepad));
+        assert_eq!(tracker.observe(false, true), None);
+        assert_eq!(tracker.observe(true, true), None);
+        assert_eq!(
+            tracker.observe(true, false),
+            Some(InputDevice::KeyboardMouse)
+        );
+    }
+}
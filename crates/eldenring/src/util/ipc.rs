@@ -0,0 +1,244 @@
+//! A local JSON-RPC 2.0 server for external tools like stream overlays and
+//! LiveSplit components. Only available with the `ipc` feature.
+//!
+//! [start_ipc_server] listens on a loopback TCP port and reads one request
+//! per line from every connection. The game's state can only be read safely
+//! from its main thread, so requests are queued and answered by
+//! [update_ipc_server], which needs to be called every frame. Named pipes
+//! aren't supported.
+//!
+//! Everything served is read-only:
+//!
+//! - `player_position`: `[x, y, z]` in physics coordinates.
+//! - `player_stats`: `{ "hp", "max_hp", "fp", "max_fp", "stamina", "max_stamina" }`.
+//! - `igt`: the play time in milliseconds.
+//! - `get_flag`: the state of the event flag `{ "id": 123 }` or `[123]`.
+//!
+//! Results are null while the player isn't loaded.
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+use serde_json::{Value, json};
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{CSEventFlagMan, GameDataMan, WorldChrMan};
+use crate::position::HavokPosition;
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+/// Returned when a singleton the request needs isn't available.
+const UNAVAILABLE: i64 = -32000;
+
+#[derive(Debug, PartialEq)]
+struct RpcRequest {
+    id: Value,
+    method: String,
+    params: Value,
+}
+
+struct PendingRequest {
+    request: RpcRequest,
+    reply: Sender<String>,
+}
+
+static QUEUE: Mutex<Option<Receiver<PendingRequest>>> = Mutex::new(None);
+
+/// Starts serving requests on `addr`, which has to be a loopback address
+/// like `127.0.0.1:0`. Returns the address that was bound.
+pub fn start_ipc_server(addr: impl ToSocketAddrs) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    if !local_addr.ip().is_loopback() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the IPC server only listens on loopback addresses",
+        ));
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    *QUEUE.lock().unwrap() = Some(receiver);
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    let sender = sender.clone();
+                    std::thread::spawn(move || {
+                        if let Err(e) = serve_connection(stream, sender) {
+                            tracing::debug!("IPC connection closed: {e}");
+                        }
+                    });
+                }
+                Err(e) => tracing::warn!("Could not accept IPC connection: {e}"),
+            }
+        }
+    });
+
+    Ok(local_addr)
+}
+
+fn serve_connection(stream: TcpStream, queue: Sender<PendingRequest>) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match parse_request(&line) {
+            Ok(request) => {
+                let (reply, response) = mpsc::channel();
+                if queue.send(PendingRequest { request, reply }).is_err() {
+                    return Ok(());
+                }
+                // Waits for the next update on the main thread.
+                match response.recv() {
+                    Ok(response) => response,
+                    Err(_) => return Ok(()),
+                }
+            }
+            Err(error) => error,
+        };
+
+        writer.write_all(response.as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+/// Parses a single request, or returns the error response for it.
+fn parse_request(line: &str) -> Result<RpcRequest, String> {
+    let Ok(value) = serde_json::from_str::<Value>(line) else {
+        return Err(error_response(&Value::Null, PARSE_ERROR, "Parse error"));
+    };
+
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let method = value.get("method").and_then(Value::as_str);
+    match (value.get("jsonrpc").and_then(Value::as_str), method) {
+        (Some("2.0"), Some(method)) => Ok(RpcRequest {
+            id,
+            method: method.to_string(),
+            params: value.get("params").cloned().unwrap_or(Value::Null),
+        }),
+        _ => Err(error_response(&id, INVALID_REQUEST, "Invalid request")),
+    }
+}
+
+fn error_response(id: &Value, code: i64, message: &str) -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+    .to_string()
+}
+
+fn result_response(id: &Value, result: Value) -> String {
+    json!({ "jsonrpc": "2.0", "id": id, "result": result }).to_string()
+}
+
+/// Answers the requests received since the last call. This needs to be
+/// called every frame. There's no static lookup for [GameDataMan], so it has
+/// to be passed in for `igt` to be answered.
+pub fn update_ipc_server(game_data_man: Option<&GameDataMan>) {
+    let queue = QUEUE.lock().unwrap();
+    let Some(queue) = queue.as_ref() else {
+        return;
+    };
+
+    for pending in queue.try_iter() {
+        let RpcRequest { id, method, params } = &pending.request;
+        let response = match dispatch(method, params, game_data_man) {
+            Ok(Ok(result)) => result_response(id, result),
+            Ok(Err(e)) => error_response(id, UNAVAILABLE, &e.to_string()),
+            Err((code, message)) => error_response(id, code, message),
+        };
+
+        // The connection may have closed in the meantime.
+        let _ = pending.reply.send(response);
+    }
+}
+
+type DispatchResult = Result<Result<Value, InstanceError>, (i64, &'static str)>;
+
+fn dispatch(method: &str, params: &Value, game_data_man: Option<&GameDataMan>) -> DispatchResult {
+    Ok(match method {
+        "player_position" => player_position(),
+        "player_stats" => player_stats(),
+        "igt" => Ok(game_data_man.map_or(Value::Null, |g| json!(g.play_time))),
+        "get_flag" => {
+            let id = params
+                .get("id")
+                .or_else(|| params.get(0))
+                .and_then(Value::as_u64)
+                .and_then(|id| u32::try_from(id).ok())
+                .ok_or((INVALID_PARAMS, "Expected an event flag ID"))?;
+            get_flag(id)
+        }
+        _ => return Err((METHOD_NOT_FOUND, "Method not found")),
+    })
+}
+
+fn player_position() -> Result<Value, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    Ok(world_chr_man
+        .main_player
+        .as_ref()
+        .map_or(Value::Null, |player| {
+            let HavokPosition(x, y, z, _) = player.chr_ins.module_container.physics.position;
+            json!([x, y, z])
+        }))
+}
+
+fn player_stats() -> Result<Value, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    Ok(world_chr_man
+        .main_player
+        .as_ref()
+        .map_or(Value::Null, |player| {
+            let data = &player.chr_ins.module_container.data;
+            json!({
+                "hp": data.hp,
+                "max_hp": data.max_hp,
+                "fp": data.fp,
+                "max_fp": data.max_fp,
+                "stamina": data.stamina,
+                "max_stamina": data.max_stamina,
+            })
+        }))
+}
+
+fn get_flag(id: u32) -> Result<Value, InstanceError> {
+    let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+    Ok(json!(event_flag_man.virtual_memory_flag.get_flag(id)))
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::{Value, json};
+
+    use super::{RpcRequest, parse_request};
+
+    #[test]
+    fn parses_requests() {
+        assert_eq!(
+            parse_request(r#"{"jsonrpc":"2.0","id":1,"method":"get_flag","params":[100]}"#),
+            Ok(RpcRequest {
+                id: json!(1),
+                method: "get_flag".to_string(),
+                params: json!([100]),
+            })
+        );
+
+        let error = |line| {
+            let response: Value = serde_json::from_str(&parse_request(line).unwrap_err()).unwrap();
+            response["error"]["code"].as_i64().unwrap()
+        };
+        assert_eq!(error("{"), -32700);
+        assert_eq!(error(r#"{"id":1,"method":"igt"}"#), -32600);
+    }
+}
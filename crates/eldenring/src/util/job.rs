@@ -0,0 +1,192 @@
+//! Runs one-off jobs off the game's frame.
+//!
+//! [submit_job] runs jobs on [WORKER_THREADS] background threads owned by
+//! this module, so they never hold up a frame. The game's allocators can be used from them, but
+//! game structures generally can't.
+//!
+//! Work that has to happen at a specific stage of the frame, ex. touching
+//! structures that are only updated from one task group, can be queued with
+//! [run_on_task_group] instead. A recurring task registered once per task
+//! group drains its jobs every time the game runs that group.
+use std::collections::{HashMap, VecDeque, hash_map::Entry};
+use std::panic::AssertUnwindSafe;
+use std::sync::{
+    Arc, LazyLock, Mutex,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    mpsc,
+};
+
+use shared::{FromStatic, InstanceError, RecurringTaskHandle, SharedTaskImpExt};
+
+use crate::cs::{CSTaskGroupIndex, CSTaskImp};
+use crate::fd4::FD4TaskData;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Number of background threads [submit_job] runs jobs on.
+pub const WORKER_THREADS: usize = 2;
+
+/// Jobs submitted to the workers that haven't started yet.
+static BACKLOG: AtomicUsize = AtomicUsize::new(0);
+
+static WORKERS: LazyLock<mpsc::Sender<Job>> = LazyLock::new(|| {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+
+    for index in 0..WORKER_THREADS {
+        let receiver = receiver.clone();
+        std::thread::Builder::new()
+            .name(format!("fromsoftware-rs job worker {index}"))
+            .spawn(move || {
+                loop {
+                    // The lock is only held while waiting for a job, not while
+                    // running it.
+                    let Ok(job) = receiver.lock().unwrap().recv() else {
+                        return;
+                    };
+                    BACKLOG.fetch_sub(1, Ordering::Relaxed);
+                    job();
+                }
+            })
+            .expect("Could not spawn a job worker thread");
+    }
+
+    sender
+});
+
+struct Dispatcher {
+    jobs: Arc<Mutex<VecDeque<Job>>>,
+    _handle: RecurringTaskHandle<FD4TaskData>,
+}
+
+// SAFETY: the handle is never touched after it was registered, it only exists
+// to keep the dispatcher alive.
+unsafe impl Send for Dispatcher {}
+
+static DISPATCHERS: LazyLock<Mutex<HashMap<u32, Dispatcher>>> = LazyLock::new(Default::default);
+
+/// Tracks the completion of a job submitted through [submit_job] or
+/// [run_on_task_group].
+#[derive(Clone)]
+pub struct JobHandle {
+    finished: Arc<AtomicBool>,
+}
+
+impl JobHandle {
+    /// Whether the job ran, including if it panicked.
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Acquire)
+    }
+}
+
+fn tracked(job: impl FnOnce() + Send + 'static) -> (Job, JobHandle) {
+    let finished = Arc::new(AtomicBool::new(false));
+    let handle = JobHandle {
+        finished: finished.clone(),
+    };
+
+    let job: Job = Box::new(move || {
+        // Panics would otherwise take down the worker thread, or abort the
+        // game when unwinding out of a task.
+        if std::panic::catch_unwind(AssertUnwindSafe(job)).is_err() {
+            tracing::error!("Job panicked");
+        }
+        finished.store(true, Ordering::Release);
+    });
+    (job, handle)
+}
+
+/// Queues `job` to run on one of the background worker threads.
+pub fn submit_job(job: impl FnOnce() + Send + 'static) -> JobHandle {
+    let (job, handle) = tracked(job);
+    BACKLOG.fetch_add(1, Ordering::Relaxed);
+    WORKERS
+        .send(job)
+        .expect("Job worker threads don't exit while jobs can be sent");
+    handle
+}
+
+/// Amount of jobs waiting for a background worker thread.
+pub fn pending_background_jobs() -> usize {
+    BACKLOG.load(Ordering::Relaxed)
+}
+
+/// Queues `job` to run the next time the game executes `group`, on whichever
+/// task runner thread the game picked for it. The frame waits for the group
+/// to finish, so heavy work belongs in [submit_job] instead.
+pub fn run_on_task_group(
+    group: CSTaskGroupIndex,
+    job: impl FnOnce() + Send + 'static,
+) -> Result<JobHandle, InstanceError> {
+    let (job, handle) = tracked(job);
+
+    let mut dispatchers = DISPATCHERS.lock().unwrap();
+    let dispatcher = match dispatchers.entry(group as u32) {
+        Entry::Occupied(o) => o.into_mut(),
+        Entry::Vacant(v) => {
+            let cs_task = unsafe { CSTaskImp::instance() }?;
+            let jobs = Arc::new(Mutex::new(VecDeque::<Job>::new()));
+            let handle = cs_task.run_recurring(
+                {
+                    let jobs = jobs.clone();
+                    move |_: &FD4TaskData| {
+                        // Take the jobs out first so that jobs can queue up
+                        // new ones without deadlocking.
+                        let pending = std::mem::take(&mut *jobs.lock().unwrap());
+                        for job in pending {
+                            job();
+                        }
+                    }
+                },
+                group,
+            );
+
+            v.insert(Dispatcher {
+                jobs,
+                _handle: handle,
+            })
+        }
+    };
+
+    dispatcher.jobs.lock().unwrap().push_back(job);
+    Ok(handle)
+}
+
+/// Amount of jobs waiting for `group` to run.
+pub fn pending_jobs(group: CSTaskGroupIndex) -> usize {
+    DISPATCHERS
+        .lock()
+        .unwrap()
+        .get(&(group as u32))
+        .map(|d| d.jobs.lock().unwrap().len())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::mpsc;
+    use std::time::{Duration, Instant};
+
+    use super::{JobHandle, submit_job};
+
+    fn wait_for(handle: &JobHandle) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !handle.is_finished() {
+            assert!(Instant::now() < deadline, "job didn't finish");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn runs_jobs_on_worker_threads() {
+        let (sender, receiver) = mpsc::channel();
+        let handle = submit_job(move || sender.send(std::thread::current().id()).unwrap());
+
+        wait_for(&handle);
+        assert_ne!(receiver.recv().unwrap(), std::thread::current().id());
+
+        // A panicking job still finishes and leaves the workers running.
+        wait_for(&submit_job(|| panic!("job panicked")));
+        wait_for(&submit_job(|| {}));
+    }
+}
@@ -0,0 +1,115 @@
+//! Events at journey (NG+ cycle) boundaries, observed by polling.
+//!
+//! This doesn't hook the function advancing the journey, so listeners run
+//! after the fact rather than before and after it. Transitions are seen
+//! through two fields instead: [GameMan::new_game_plus_requested] is set when
+//! the player agrees to start the next journey, and [GameDataMan::ng_lvl]
+//! changes once the new journey has been set up. [update_journey] has to be
+//! called every frame, ex. from a recurring task in the `FrameBegin` group,
+//! for listeners to be notified.
+//!
+//! There's no static lookup for [GameDataMan] in this crate yet, so it has to
+//! be passed in by the caller.
+use std::sync::Mutex;
+
+use shared::{FromStatic, InstanceError, Listeners};
+
+use crate::cs::{GameDataMan, GameMan};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JourneyEvent {
+    /// The player started the transition to the next journey, nothing has
+    /// been reset yet.
+    TransitionRequested { ng_lvl: u32 },
+    /// The next journey was set up.
+    TransitionCompleted { previous_ng_lvl: u32, ng_lvl: u32 },
+}
+
+#[derive(Default)]
+struct JourneyTracker {
+    ng_lvl: Option<u32>,
+    was_requested: bool,
+    /// The level the pending transition started from.
+    transition_from: Option<u32>,
+}
+
+impl JourneyTracker {
+    fn observe(&mut self, requested: bool, ng_lvl: u32) -> Option<JourneyEvent> {
+        let previous_ng_lvl = self.ng_lvl.replace(ng_lvl);
+        let newly_requested = requested && !self.was_requested;
+        self.was_requested = requested;
+
+        if previous_ng_lvl.is_some_and(|previous| previous != ng_lvl) {
+            // A change without a request is a different character being
+            // loaded rather than a transition.
+            return self.transition_from.take().map(|previous_ng_lvl| {
+                JourneyEvent::TransitionCompleted {
+                    previous_ng_lvl,
+                    ng_lvl,
+                }
+            });
+        }
+
+        if newly_requested && self.transition_from.is_none() {
+            self.transition_from = Some(ng_lvl);
+            return Some(JourneyEvent::TransitionRequested { ng_lvl });
+        }
+
+        None
+    }
+}
+
+static TRACKER: Mutex<JourneyTracker> = Mutex::new(JourneyTracker {
+    ng_lvl: None,
+    was_requested: false,
+    transition_from: None,
+});
+
+static LISTENERS: Listeners<JourneyEvent> = Listeners::new();
+
+/// Registers a listener that's called for every [JourneyEvent].
+pub fn on_journey_event(listener: impl FnMut(JourneyEvent) + Send + 'static) {
+    LISTENERS.add(listener);
+}
+
+/// Checks for journey transitions and notifies the listeners.
+pub fn update_journey(game_data_man: &GameDataMan) -> Result<(), InstanceError> {
+    let requested = unsafe { GameMan::instance() }?.new_game_plus_requested;
+    let ng_lvl = game_data_man.ng_lvl;
+
+    let event = TRACKER.lock().unwrap().observe(requested, ng_lvl);
+    LISTENERS.notify(event);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{JourneyEvent, JourneyTracker};
+
+    #[test]
+    fn reports_transition_once() {
+        let mut tracker = JourneyTracker::default();
+        assert_eq!(tracker.observe(false, 0), None);
+        assert_eq!(
+            tracker.observe(true, 0),
+            Some(JourneyEvent::TransitionRequested { ng_lvl: 0 })
+        );
+        assert_eq!(tracker.observe(true, 0), None);
+        assert_eq!(
+            tracker.observe(false, 1),
+            Some(JourneyEvent::TransitionCompleted {
+                previous_ng_lvl: 0,
+                ng_lvl: 1
+            })
+        );
+        assert_eq!(tracker.observe(false, 1), None);
+    }
+
+    #[test]
+    fn ignores_loading_another_character() {
+        let mut tracker = JourneyTracker::default();
+        tracker.observe(false, 0);
+        assert_eq!(tracker.observe(false, 3), None);
+    }
+}
@@ -0,0 +1,273 @@
+//! Declarative hotkeys for mod actions.
+//!
+//! Actions are registered once with the keys that trigger them, either a
+//! single key or a chord of keyboard keys and controller buttons that have to
+//! be held together. [Keybinds::poll] then has to be called every frame, after
+//! which [Keybinds::triggered] tells whether an action fired on that frame.
+//!
+//! Conflicts are detected between the binds registered here, so pick keys and
+//! chords the game's own controls don't use.
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use thiserror::Error;
+
+/// Controller buttons, with the masks they have in XInput's button state.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum GamepadButton {
+    DpadUp = 0x0001,
+    DpadDown = 0x0002,
+    DpadLeft = 0x0004,
+    DpadRight = 0x0008,
+    Start = 0x0010,
+    Back = 0x0020,
+    LeftThumb = 0x0040,
+    RightThumb = 0x0080,
+    LeftShoulder = 0x0100,
+    RightShoulder = 0x0200,
+    A = 0x1000,
+    B = 0x2000,
+    X = 0x4000,
+    Y = 0x8000,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// Keyboard key or mouse button by its virtual key code, ex. `VK_F1`.
+    Keyboard(u16),
+    /// Button on the first connected controller.
+    Gamepad(GamepadButton),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trigger {
+    /// Fires on the frame the keys are pressed.
+    Tap,
+    /// Fires once the keys have been held for the duration.
+    Hold(Duration),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Keybind {
+    /// Keys that all have to be held, in any order.
+    pub keys: Vec<Key>,
+    pub trigger: Trigger,
+}
+
+impl Keybind {
+    pub fn tap(keys: impl Into<Vec<Key>>) -> Self {
+        Self {
+            keys: keys.into(),
+            trigger: Trigger::Tap,
+        }
+    }
+
+    pub fn hold(keys: impl Into<Vec<Key>>, duration: Duration) -> Self {
+        Self {
+            keys: keys.into(),
+            trigger: Trigger::Hold(duration),
+        }
+    }
+
+    fn is_superset_of(&self, other: &Keybind) -> bool {
+        self.keys.len() > other.keys.len() && other.keys.iter().all(|key| self.keys.contains(key))
+    }
+
+    fn has_same_keys(&self, other: &Keybind) -> bool {
+        self.keys.len() == other.keys.len() && other.keys.iter().all(|key| self.keys.contains(key))
+    }
+}
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum KeybindError {
+    #[error("A keybind needs at least one key")]
+    NoKeys,
+    #[error("The keys are already bound to {0}")]
+    Conflict(String),
+}
+
+struct BindState {
+    bind: Keybind,
+    /// When the keys were first seen held, None while they aren't.
+    held_since: Option<Instant>,
+    /// Whether the bind already fired for the current press.
+    fired: bool,
+    triggered: bool,
+}
+
+#[derive(Default)]
+pub struct Keybinds {
+    binds: HashMap<String, BindState>,
+}
+
+impl Keybinds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Binds `action` to `bind`, replacing the previous bind of the action.
+    /// Fails if another action is already bound to the same keys with the
+    /// same trigger.
+    pub fn register(&mut self, action: &str, bind: Keybind) -> Result<(), KeybindError> {
+        if bind.keys.is_empty() {
+            return Err(KeybindError::NoKeys);
+        }
+
+        if let Some((other, _)) = self.binds.iter().find(|(other, state)| {
+            other.as_str() != action
+                && state.bind.trigger == bind.trigger
+                && state.bind.has_same_keys(&bind)
+        }) {
+            return Err(KeybindError::Conflict(other.clone()));
+        }
+
+        self.binds.insert(
+            action.to_string(),
+            BindState {
+                bind,
+                held_since: None,
+                fired: false,
+                triggered: false,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn unregister(&mut self, action: &str) {
+        self.binds.remove(action);
+    }
+
+    pub fn get(&self, action: &str) -> Option<&Keybind> {
+        self.binds.get(action).map(|state| &state.bind)
+    }
+
+    /// Whether `action` fired on the last update.
+    pub fn triggered(&self, action: &str) -> bool {
+        self.binds.get(action).is_some_and(|state| state.triggered)
+    }
+
+    /// Whether the keys of `action` are currently held.
+    pub fn held(&self, action: &str) -> bool {
+        self.binds
+            .get(action)
+            .is_some_and(|state| state.held_since.is_some())
+    }
+
+    /// Reads the keyboard and the first controller and updates every bind.
+    #[cfg(windows)]
+    pub fn poll(&mut self) {
+        use windows::Win32::UI::Input::KeyboardAndMouse::GetAsyncKeyState;
+        use windows::Win32::UI::Input::XboxController::{XINPUT_STATE, XInputGetState};
+
+        let mut state = XINPUT_STATE::default();
+        let buttons = match unsafe { XInputGetState(0, &mut state) } {
+            0 => state.Gamepad.wButtons.0,
+            _ => 0,
+        };
+
+        self.update(Instant::now(), |key| match key {
+            Key::Keyboard(code) => unsafe { GetAsyncKeyState(code as i32) < 0 },
+            Key::Gamepad(button) => (buttons & button as u16) != 0,
+        });
+    }
+
+    /// Updates every bind from `is_down`. [Keybinds::poll] does this with the
+    /// real keyboard and controller state.
+    ///
+    /// A bind doesn't fire while a bind with more keys that includes all of
+    /// its keys is held, so e.g. binding both `F` and `Ctrl+F` works.
+    pub fn update(&mut self, now: Instant, is_down: impl Fn(Key) -> bool) {
+        let held = self
+            .binds
+            .iter()
+            .filter(|(_, state)| state.bind.keys.iter().all(|key| is_down(*key)))
+            .map(|(action, state)| (action.clone(), state.bind.clone()))
+            .collect::<Vec<_>>();
+
+        for (action, state) in self.binds.iter_mut() {
+            state.triggered = false;
+
+            if !held.iter().any(|(held, _)| held == action) {
+                state.held_since = None;
+                state.fired = false;
+                continue;
+            }
+
+            let held_since = *state.held_since.get_or_insert(now);
+            let shadowed = held
+                .iter()
+                .any(|(_, other)| other.is_superset_of(&state.bind));
+            if state.fired || shadowed {
+                continue;
+            }
+
+            let ready = match state.bind.trigger {
+                Trigger::Tap => true,
+                Trigger::Hold(duration) => now.duration_since(held_since) >= duration,
+            };
+            if ready {
+                state.triggered = true;
+                state.fired = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::{Key, Keybind, KeybindError, Keybinds};
+
+    const CTRL: Key = Key::Keyboard(0x11);
+    const F: Key = Key::Keyboard(0x46);
+
+    #[test]
+    fn chord_shadows_its_subset() {
+        let mut keybinds = Keybinds::new();
+        keybinds.register("single", Keybind::tap([F])).unwrap();
+        keybinds.register("chord", Keybind::tap([CTRL, F])).unwrap();
+
+        let now = Instant::now();
+        keybinds.update(now, |_| true);
+        assert!(keybinds.triggered("chord"));
+        assert!(!keybinds.triggered("single"));
+
+        // Holding the keys doesn't fire the tap again.
+        keybinds.update(now, |_| true);
+        assert!(!keybinds.triggered("chord"));
+    }
+
+    #[test]
+    fn hold_fires_once_after_duration() {
+        let mut keybinds = Keybinds::new();
+        keybinds
+            .register("hold", Keybind::hold([F], Duration::from_secs(1)))
+            .unwrap();
+
+        let start = Instant::now();
+        keybinds.update(start, |_| true);
+        assert!(!keybinds.triggered("hold"));
+        keybinds.update(start + Duration::from_secs(1), |_| true);
+        assert!(keybinds.triggered("hold"));
+        keybinds.update(start + Duration::from_secs(2), |_| true);
+        assert!(!keybinds.triggered("hold"));
+    }
+
+    #[test]
+    fn rejects_duplicate_keys() {
+        let mut keybinds = Keybinds::new();
+        keybinds.register("first", Keybind::tap([CTRL, F])).unwrap();
+
+        assert_eq!(
+            keybinds.register("second", Keybind::tap([F, CTRL])),
+            Err(KeybindError::Conflict("first".to_string()))
+        );
+        assert!(
+            keybinds
+                .register("second", Keybind::hold([F, CTRL], Duration::from_secs(1)))
+                .is_ok()
+        );
+    }
+}
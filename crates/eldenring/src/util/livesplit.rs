@@ -0,0 +1,251 @@
+//! A bridge to the LiveSplit Server component for auto-splitting. Only
+//! available with the `livesplit` feature.
+//!
+//! [LiveSplitBridge] connects to the port the server component listens on
+//! and drives the timer with its text commands. The timer starts once the
+//! play time of a new character starts counting, the game time is kept in
+//! sync with the game's own play time, and a split happens every time the
+//! next event flag in the list of splits gets set, e.g. a boss's defeated
+//! flag. With the `event-flag-names` feature, `flags_in_category("Boss")`
+//! can be used to look them up.
+//!
+//! The player not being loaded is treated as loading. The play time already
+//! stops while loading, pausing the game time only keeps LiveSplit from
+//! running ahead between updates. [GameDataMan] is passed in by the caller.
+use std::fmt;
+use std::io::{self, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+
+use shared::{FromStatic, InstanceError};
+use thiserror::Error;
+
+use crate::cs::{CSEventFlagMan, GameDataMan, WorldChrMan};
+
+/// The address the LiveSplit Server component listens on by default.
+pub const DEFAULT_LIVESPLIT_ADDR: &str = "127.0.0.1:16834";
+
+#[derive(Error, Debug)]
+pub enum LiveSplitError {
+    #[error("Could not get instance: {0}")]
+    Instance(#[from] InstanceError),
+    #[error("Could not talk to LiveSplit: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// The variables auto-splitting is based on, read once per update.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AutoSplitterVars {
+    /// The play time in milliseconds, None if it couldn't be read.
+    pub igt: Option<u32>,
+    pub loading: bool,
+    /// Whether the flag of the next split is set.
+    pub next_split_flag: bool,
+}
+
+impl AutoSplitterVars {
+    /// Reads the variables from the game. `split_flag` is the flag of the
+    /// next split, if there is one.
+    pub fn read(
+        game_data_man: Option<&GameDataMan>,
+        split_flag: Option<u32>,
+    ) -> Result<Self, InstanceError> {
+        let world_chr_man = unsafe { WorldChrMan::instance() }?;
+        let next_split_flag = match split_flag {
+            Some(flag) => {
+                let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+                event_flag_man.virtual_memory_flag.get_flag(flag)
+            }
+            None => false,
+        };
+
+        Ok(Self {
+            igt: game_data_man.map(|g| g.play_time),
+            loading: world_chr_man.main_player.is_none(),
+            next_split_flag,
+        })
+    }
+}
+
+/// A command understood by the LiveSplit Server component.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiveSplitCommand {
+    StartTimer,
+    Split,
+    Reset,
+    InitGameTime,
+    PauseGameTime,
+    UnpauseGameTime,
+    /// Sets the game time in milliseconds.
+    SetGameTime(u32),
+}
+
+impl fmt::Display for LiveSplitCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StartTimer => write!(f, "starttimer"),
+            Self::Split => write!(f, "split"),
+            Self::Reset => write!(f, "reset"),
+            Self::InitGameTime => write!(f, "initgametime"),
+            Self::PauseGameTime => write!(f, "pausegametime"),
+            Self::UnpauseGameTime => write!(f, "unpausegametime"),
+            Self::SetGameTime(ms) => {
+                let (hours, minutes) = (ms / 3_600_000, ms / 60_000 % 60);
+                let (seconds, millis) = (ms / 1000 % 60, ms % 1000);
+                write!(
+                    f,
+                    "setgametime {hours}:{minutes:02}:{seconds:02}.{millis:03}"
+                )
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct SplitTracker {
+    started: bool,
+    loading: bool,
+    last_igt: Option<u32>,
+    /// Index of the next split.
+    next_split: usize,
+}
+
+impl SplitTracker {
+    fn observe(&mut self, vars: AutoSplitterVars, split_count: usize) -> Vec<LiveSplitCommand> {
+        let mut commands = Vec::new();
+        let last_igt = self.last_igt;
+        if vars.igt.is_some() {
+            self.last_igt = vars.igt;
+        }
+
+        if !self.started {
+            // A new character's play time starts counting from zero.
+            if last_igt == Some(0) && vars.igt.is_some_and(|igt| igt > 0) {
+                self.started = true;
+                self.loading = false;
+                commands.push(LiveSplitCommand::StartTimer);
+            } else {
+                return commands;
+            }
+        }
+
+        if vars.loading != self.loading {
+            self.loading = vars.loading;
+            commands.push(if vars.loading {
+                LiveSplitCommand::PauseGameTime
+            } else {
+                LiveSplitCommand::UnpauseGameTime
+            });
+        }
+
+        if let Some(igt) = vars.igt.filter(|igt| last_igt != Some(*igt)) {
+            commands.push(LiveSplitCommand::SetGameTime(igt));
+        }
+
+        if self.next_split < split_count && vars.next_split_flag {
+            self.next_split += 1;
+            commands.push(LiveSplitCommand::Split);
+        }
+
+        commands
+    }
+}
+
+pub struct LiveSplitBridge {
+    stream: TcpStream,
+    /// Event flags to split on, in order.
+    splits: Vec<u32>,
+    tracker: SplitTracker,
+}
+
+impl LiveSplitBridge {
+    /// Connects to the LiveSplit Server component, usually at
+    /// [DEFAULT_LIVESPLIT_ADDR].
+    pub fn connect(addr: impl ToSocketAddrs, splits: Vec<u32>) -> Result<Self, LiveSplitError> {
+        let stream = TcpStream::connect(addr)?;
+        stream.set_nodelay(true)?;
+
+        let mut bridge = Self {
+            stream,
+            splits,
+            tracker: SplitTracker::default(),
+        };
+        bridge.send(LiveSplitCommand::InitGameTime)?;
+        Ok(bridge)
+    }
+
+    /// Index of the next split, equal to the number of splits once the run is
+    /// done.
+    pub fn next_split(&self) -> usize {
+        self.tracker.next_split
+    }
+
+    /// Reads the game's state and sends LiveSplit what changed since the last
+    /// update. This needs to be called every frame.
+    pub fn update(&mut self, game_data_man: Option<&GameDataMan>) -> Result<(), LiveSplitError> {
+        let split_flag = self.splits.get(self.tracker.next_split).copied();
+        let vars = AutoSplitterVars::read(game_data_man, split_flag)?;
+
+        for command in self.tracker.observe(vars, self.splits.len()) {
+            self.send(command)?;
+        }
+        Ok(())
+    }
+
+    /// Resets the timer, the next run starts with a new character.
+    pub fn reset(&mut self) -> Result<(), LiveSplitError> {
+        self.tracker = SplitTracker::default();
+        self.send(LiveSplitCommand::Reset)
+    }
+
+    fn send(&mut self, command: LiveSplitCommand) -> Result<(), LiveSplitError> {
+        Ok(write!(self.stream, "{command}\r\n")?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{AutoSplitterVars, LiveSplitCommand, SplitTracker};
+
+    fn vars(igt: u32, loading: bool, next_split_flag: bool) -> AutoSplitterVars {
+        AutoSplitterVars {
+            igt: Some(igt),
+            loading,
+            next_split_flag,
+        }
+    }
+
+    #[test]
+    fn formats_game_time() {
+        assert_eq!(
+            LiveSplitCommand::SetGameTime(3_723_045).to_string(),
+            "setgametime 1:02:03.045"
+        );
+    }
+
+    #[test]
+    fn starts_loads_and_splits() {
+        use LiveSplitCommand::*;
+
+        let mut tracker = SplitTracker::default();
+        // Loading an existing character doesn't start the timer.
+        assert_eq!(tracker.observe(vars(5000, false, false), 1), []);
+
+        let mut tracker = SplitTracker::default();
+        assert_eq!(tracker.observe(vars(0, true, false), 1), []);
+        assert_eq!(
+            tracker.observe(vars(16, false, false), 1),
+            [StartTimer, SetGameTime(16)]
+        );
+        assert_eq!(
+            tracker.observe(vars(32, true, false), 1),
+            [PauseGameTime, SetGameTime(32)]
+        );
+        assert_eq!(tracker.observe(vars(32, true, false), 1), []);
+        assert_eq!(
+            tracker.observe(vars(48, false, true), 1),
+            [UnpauseGameTime, SetGameTime(48), Split]
+        );
+        // Past the last split.
+        assert_eq!(tracker.observe(vars(48, false, true), 1), []);
+    }
+}
@@ -0,0 +1,85 @@
+//! Looted state of map treasure, chests and corpses.
+//!
+//! The game remembers what has been picked up through the event flag in each
+//! item lot's row, and only spawns the treasure of lots whose flag
+//! is still unset once the map loads. Clearing a flag restocks the lot the
+//! next time its map is loaded, setting it removes the lot the same way.
+//!
+//! An opened chest is a separate map gimmick driven by its own event script
+//! and flag, see [MapGimmick](crate::util::gimmick::MapGimmick), so resetting
+//! a chest's lot doesn't close it again.
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::CSEventFlagMan;
+use crate::fd4::FD4ParamRepository;
+use crate::param::ITEMLOT_PARAM_ST;
+use crate::util::audited::set_flag;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ItemLotSource {
+    /// Treasure placed on the map, including chests and corpses.
+    Map,
+    /// Enemy drops.
+    Enemy,
+}
+
+impl ItemLotSource {
+    pub fn param_name(&self) -> &'static str {
+        match self {
+            ItemLotSource::Map => "ItemLotParam_map",
+            ItemLotSource::Enemy => "ItemLotParam_enemy",
+        }
+    }
+}
+
+/// The event flag recording whether the lot was picked up, None if the lot
+/// doesn't exist or isn't tracked.
+pub fn item_lot_flag(source: ItemLotSource, lot_id: u32) -> Result<Option<u32>, InstanceError> {
+    let params = unsafe { FD4ParamRepository::instance() }?;
+    Ok(params
+        .get_in::<ITEMLOT_PARAM_ST>(source.param_name(), lot_id)
+        .map(|row| row.get_item_flag_id())
+        .filter(|flag| *flag != 0))
+}
+
+/// Whether the lot has been picked up, None if it isn't tracked.
+pub fn is_looted(source: ItemLotSource, lot_id: u32) -> Result<Option<bool>, InstanceError> {
+    let Some(flag) = item_lot_flag(source, lot_id)? else {
+        return Ok(None);
+    };
+
+    let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+    Ok(Some(event_flag_man.virtual_memory_flag.get_flag(flag)))
+}
+
+/// Marks the lot as picked up or restocks it. Returns false if the lot isn't
+/// tracked. Takes effect the next time the lot's map is loaded.
+pub fn set_looted(
+    source: ItemLotSource,
+    lot_id: u32,
+    looted: bool,
+) -> Result<bool, OnlineGuardError> {
+    let Some(flag) = item_lot_flag(source, lot_id)? else {
+        return Ok(false);
+    };
+
+    check_online_guard()?;
+    let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+    set_flag(&mut event_flag_man.virtual_memory_flag, flag, looted);
+    Ok(true)
+}
+
+/// Restocks every given lot, returning how many of them are tracked.
+pub fn restock(
+    source: ItemLotSource,
+    lot_ids: impl IntoIterator<Item = u32>,
+) -> Result<usize, OnlineGuardError> {
+    let mut restocked = 0;
+    for lot_id in lot_ids {
+        if set_looted(source, lot_id, false)? {
+            restocked += 1;
+        }
+    }
+    Ok(restocked)
+}
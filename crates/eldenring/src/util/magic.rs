@@ -0,0 +1,88 @@
+//! Reads and changes the local player's memorized spells.
+//!
+//! Spells are written to the player's [EquipMagicData], which is what the
+//! spell HUD and the memorize menu display, so changes show up immediately.
+//! The character's CSChrMagicModule, which tracks an ongoing cast, isn't
+//! mapped yet, so the state of a spell being charged can't be read from here.
+use shared::{FromStatic, InstanceError};
+use thiserror::Error;
+
+use crate::cs::{EquipMagicData, WorldChrMan};
+use crate::fd4::FD4ParamRepository;
+use crate::param::MAGIC_PARAM_ST;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+#[derive(Error, Debug)]
+pub enum MagicError {
+    #[error("Could not get instance: {0}")]
+    Instance(#[from] InstanceError),
+    #[error("No player character is loaded")]
+    NoPlayer,
+    #[error("There is no spell slot {0}")]
+    InvalidSlot(usize),
+    #[error("Magic row {0} does not exist")]
+    UnknownSpell(i32),
+    #[error(transparent)]
+    Online(#[from] OnlineGuardError),
+}
+
+/// Param ID the game uses for empty spell slots.
+const EMPTY_SLOT: i32 = -1;
+
+fn equip_magic_data() -> Result<&'static mut EquipMagicData, MagicError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    Ok(&mut *world_chr_man
+        .main_player
+        .as_mut()
+        .ok_or(MagicError::NoPlayer)?
+        .player_game_data
+        .equipment
+        .equip_magic_data)
+}
+
+/// Lists the MagicParam row of every spell slot, None for empty slots.
+pub fn memorized_spells() -> Result<Vec<Option<i32>>, MagicError> {
+    Ok(equip_magic_data()?
+        .entries
+        .iter()
+        .map(|entry| (entry.param_id != EMPTY_SLOT).then_some(entry.param_id))
+        .collect())
+}
+
+/// Memorizes a spell in `slot`, or clears the slot if `spell` is None.
+pub fn set_memorized_spell(slot: usize, spell: Option<i32>) -> Result<(), MagicError> {
+    check_online_guard()?;
+    if let Some(spell) = spell {
+        let param_repository = unsafe { FD4ParamRepository::instance() }?;
+        if param_repository
+            .get::<MAGIC_PARAM_ST>(spell as u32)
+            .is_none()
+        {
+            return Err(MagicError::UnknownSpell(spell));
+        }
+    }
+
+    let entry = equip_magic_data()?
+        .entries
+        .get_mut(slot)
+        .ok_or(MagicError::InvalidSlot(slot))?;
+    entry.param_id = spell.unwrap_or(EMPTY_SLOT);
+    Ok(())
+}
+
+/// The slot of the spell that's cast when the player uses their catalyst,
+/// None if no slot is selected.
+pub fn selected_spell_slot() -> Result<Option<usize>, MagicError> {
+    Ok(usize::try_from(equip_magic_data()?.selected_slot).ok())
+}
+
+/// Switches to another spell slot, like cycling through spells does.
+pub fn select_spell_slot(slot: usize) -> Result<(), MagicError> {
+    let magic_data = equip_magic_data()?;
+    if slot >= magic_data.entries.len() {
+        return Err(MagicError::InvalidSlot(slot));
+    }
+
+    magic_data.selected_slot = slot as i32;
+    Ok(())
+}
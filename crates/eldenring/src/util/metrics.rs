@@ -0,0 +1,258 @@
+//! Performance and gameplay counters in the Prometheus text format, served
+//! over a local HTTP endpoint. Only available with the `metrics` feature.
+//!
+//! [update_metrics] has to be called every frame to sample the game's state,
+//! and [start_metrics_server] serves the latest sample to any request on a
+//! loopback port, so it can be scraped like any other exporter. Every request
+//! is served on its own thread and has to arrive within [REQUEST_TIMEOUT].
+//! Sampled are:
+//!
+//! - Frame timings from [frame_timing] and, if [profile_task_groups] was
+//!   called, the task group timings.
+//! - Character counts from [streaming_stats].
+//! - The damage the player took and the number of deaths, from HP changes.
+//! - With the `task-profiling` feature, the callback timings of
+//!   `shared::task_profiler`: every recurring task, and every hook whose
+//!   detour is wrapped in its `time_callback`.
+//!
+//! [profile_task_groups]: crate::util::profiling::profile_task_groups
+use std::fmt::Write as _;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::WorldChrMan;
+use crate::util::profiling::{frame_timing, task_group_timings};
+use crate::util::streaming::streaming_stats;
+
+/// How long a client has to send its request, and to receive the response.
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// Requests are read up to this many bytes. Anything after is ignored.
+const MAX_REQUEST_LEN: u64 = 8 * 1024;
+
+#[derive(Default)]
+struct Counters {
+    last_hp: Option<i32>,
+    damage_taken: u64,
+    deaths: u64,
+}
+
+impl Counters {
+    fn observe_hp(&mut self, hp: Option<i32>) {
+        if let (Some(last_hp), Some(hp)) = (self.last_hp, hp) {
+            if hp < last_hp {
+                self.damage_taken += (last_hp - hp) as u64;
+            }
+            if last_hp > 0 && hp <= 0 {
+                self.deaths += 1;
+            }
+        }
+        self.last_hp = hp;
+    }
+}
+
+static COUNTERS: Mutex<Counters> = Mutex::new(Counters {
+    last_hp: None,
+    damage_taken: 0,
+    deaths: 0,
+});
+/// The latest sample, in the text format.
+static SAMPLE: Mutex<String> = Mutex::new(String::new());
+
+/// Starts serving the metrics on `addr`, which has to be a loopback address
+/// like `127.0.0.1:9100`. Returns the address that was bound.
+pub fn start_metrics_server(addr: impl ToSocketAddrs) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    if !local_addr.ip().is_loopback() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the metrics server only listens on loopback addresses",
+        ));
+    }
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    std::thread::spawn(move || {
+                        if let Err(e) = serve_request(stream) {
+                            tracing::debug!("Could not serve metrics: {e}");
+                        }
+                    });
+                }
+                Err(e) => tracing::debug!("Could not accept metrics request: {e}"),
+            }
+        }
+    });
+
+    Ok(local_addr)
+}
+
+/// Answers a single request with the latest sample, whatever was requested.
+fn serve_request(mut stream: TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+
+    // Skips the request line and headers.
+    let mut reader = BufReader::new(stream.try_clone()?.take(MAX_REQUEST_LEN));
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 2 {
+        line.clear();
+    }
+
+    let body = SAMPLE.lock().unwrap().clone();
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+struct Sample(String);
+
+impl Sample {
+    fn metric(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.0, "# HELP eldenring_{name} {help}");
+        let _ = writeln!(self.0, "# TYPE eldenring_{name} {kind}");
+    }
+
+    fn value(&mut self, name: &str, labels: &str, value: impl std::fmt::Display) {
+        let _ = if labels.is_empty() {
+            writeln!(self.0, "eldenring_{name} {value}")
+        } else {
+            writeln!(self.0, "eldenring_{name}{{{labels}}} {value}")
+        };
+    }
+}
+
+/// Samples the game's state for the next scrape. This needs to be called
+/// every frame from the game's main thread.
+pub fn update_metrics() -> Result<(), InstanceError> {
+    let mut sample = Sample(String::new());
+
+    let timing = frame_timing()?;
+    sample.metric("frame_time_seconds", "gauge", "Duration of the last frame.");
+    sample.value("frame_time_seconds", "", timing.frame_time);
+    sample.metric("fps", "gauge", "Frame rate as calculated by the game.");
+    sample.value("fps", "", timing.fps);
+    sample.metric("target_fps", "gauge", "Frame rate the game paces towards.");
+    sample.value("target_fps", "", timing.target_fps);
+
+    let timings = task_group_timings();
+    if !timings.is_empty() {
+        sample.metric(
+            "task_group_seconds",
+            "gauge",
+            "Time spent up to and including a task group last frame.",
+        );
+        for timing in timings {
+            let labels = format!("group=\"{:?}\"", timing.group);
+            sample.value("task_group_seconds", &labels, timing.elapsed.as_secs_f64());
+        }
+    }
+
+    let stats = streaming_stats()?;
+    sample.metric("characters", "gauge", "Character slots by load state.");
+    sample.value("characters", "state=\"active\"", stats.active);
+    sample.value("characters", "state=\"pending\"", stats.pending);
+    sample.value("characters", "state=\"unloading\"", stats.unloading);
+    sample.metric("character_capacity", "gauge", "Character slots in total.");
+    sample.value("character_capacity", "", stats.capacity);
+
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let hp = world_chr_man
+        .main_player
+        .as_ref()
+        .map(|player| player.chr_ins.module_container.data.hp);
+    let mut counters = COUNTERS.lock().unwrap();
+    counters.observe_hp(hp);
+    sample.metric("damage_taken_total", "counter", "HP the player lost.");
+    sample.value("damage_taken_total", "", counters.damage_taken);
+    sample.metric(
+        "deaths_total",
+        "counter",
+        "Times the player's HP reached zero.",
+    );
+    sample.value("deaths_total", "", counters.deaths);
+
+    #[cfg(feature = "task-profiling")]
+    sample_callback_timings(&mut sample);
+
+    *SAMPLE.lock().unwrap() = sample.0;
+    Ok(())
+}
+
+#[cfg(feature = "task-profiling")]
+fn sample_callback_timings(sample: &mut Sample) {
+    let timings = shared::task_profiler::callback_timings();
+    if timings.is_empty() {
+        return;
+    }
+
+    sample.metric(
+        "callback_calls_total",
+        "counter",
+        "Calls of profiled tasks and hooks.",
+    );
+    for timing in &timings {
+        let labels = format!("callback=\"{}\"", escape_label(timing.name));
+        sample.value("callback_calls_total", &labels, timing.calls);
+    }
+
+    sample.metric(
+        "callback_seconds",
+        "gauge",
+        "Duration of recent calls of profiled tasks and hooks.",
+    );
+    for timing in &timings {
+        let name = escape_label(timing.name);
+        for (quantile, duration) in [("0.5", timing.p50), ("0.99", timing.p99), ("1", timing.max)] {
+            let labels = format!("callback=\"{name}\",quantile=\"{quantile}\"");
+            sample.value("callback_seconds", &labels, duration.as_secs_f64());
+        }
+    }
+}
+
+/// Escapes a label value for the text format.
+#[cfg(any(test, feature = "task-profiling"))]
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Counters, Sample, escape_label};
+
+    #[test]
+    fn counts_damage_and_deaths() {
+        let mut counters = Counters::default();
+        for hp in [Some(100), Some(60), Some(80), None, Some(80), Some(0)] {
+            counters.observe_hp(hp);
+        }
+        assert_eq!(counters.damage_taken, 120);
+        assert_eq!(counters.deaths, 1);
+    }
+
+    #[test]
+    fn formats_samples() {
+        let mut sample = Sample(String::new());
+        sample.metric("fps", "gauge", "Frame rate.");
+        sample.value("fps", "", 60.0);
+        sample.value("characters", "state=\"active\"", 3);
+        assert_eq!(
+            sample.0,
+            "# HELP eldenring_fps Frame rate.\n\
+             # TYPE eldenring_fps gauge\n\
+             eldenring_fps 60\n\
+             eldenring_characters{state=\"active\"} 3\n"
+        );
+        assert_eq!(escape_label("a\"b\\c\n"), "a\\\"b\\\\c\\n");
+    }
+}
@@ -0,0 +1,117 @@
+//! A guard that makes the crate's mutating helpers refuse to run while the
+//! game is online, so tools built on them don't touch anything the server
+//! or other players could see.
+//!
+//! The guard is off until [set_online_guard] is called, typically once when
+//! the mod is loaded. While it's on, [check_online_guard] fails and so do the
+//! helpers calling it:
+//!
+//! - [ParamPatcher](crate::util::param_patch::ParamPatcher) edits and undos,
+//!   and the HitMtrlParam edits in [environment](crate::util::environment).
+//! - Setting event flags through [loot](crate::util::loot),
+//!   [BossArena](crate::util::fog_wall::BossArena),
+//!   [MapGimmick](crate::util::gimmick::MapGimmick),
+//!   [boss_rush](crate::util::boss_rush),
+//!   [Savestate::restore](crate::util::savestate::Savestate::restore) and
+//!   scripts.
+//! - Moving the player through
+//!   [RespawnPoint::teleport](crate::util::respawn::RespawnPoint::teleport),
+//!   which respawn overrides, boss rush and scripts use.
+//! - Gravity and fall damage overrides in [fall](crate::util::fall).
+//! - Setting the player's HP, FP and stamina from scripts.
+//! - Changing characters' teams and visibility in [chr](crate::util::chr),
+//!   and moving party members for friendly fire in
+//!   [party](crate::util::party).
+//! - The player's great rune and memorized spells in
+//!   [great_rune](crate::util::great_rune) and [magic](crate::util::magic).
+//! - Scaling and streaming distance overrides in
+//!   [scaling](crate::util::scaling) and [streaming](crate::util::streaming).
+//! - Letting a throw's target escape through
+//!   [request_throw_escape](crate::util::throw::request_throw_escape).
+//! - Playing gestures through [gesture](crate::util::gesture).
+//!
+//! Helpers that only change what the local player sees or how the game saves
+//! aren't guarded: [draw_distance](crate::util::draw_distance),
+//! [gparam](crate::util::gparam), [frame_rate](crate::util::frame_rate),
+//! [debug_draw](crate::util::debug_draw) and [save](crate::util::save).
+//! Writing to the bindings directly isn't covered either, and neither are
+//! item grants and code patches, which this crate has no helpers for. Mods
+//! doing those should call [check_online_guard] themselves first.
+//!
+//! [CSNetMan] doesn't have a mapped flag for being in a session, so sessions
+//! are read from the lobby state [CSSessionManager] keeps.
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use shared::{FromStatic, InstanceError};
+use thiserror::Error;
+
+use crate::cs::{CSNetMan, CSSessionManager, LobbyState};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum OnlineGuardMode {
+    /// Nothing is refused.
+    #[default]
+    Off = 0,
+    /// Refuses while hosting, joining or in another player's world.
+    Sessions = 1,
+    /// Like [OnlineGuardMode::Sessions], and also refuses while
+    /// [CSNetMan] hasn't lost its connection to the game's servers. Whether
+    /// that flag is also set when the game is started in offline mode hasn't
+    /// been checked, so this may refuse offline too.
+    Connected = 2,
+}
+
+#[derive(Error, Debug)]
+pub enum OnlineGuardError {
+    #[error("Could not get instance: {0}")]
+    Instance(#[from] InstanceError),
+    #[error("Refusing to modify the game in an online session ({0:?})")]
+    Session(LobbyState),
+    #[error("Refusing to modify the game while connected to the server")]
+    Connected,
+}
+
+static MODE: AtomicU8 = AtomicU8::new(OnlineGuardMode::Off as u8);
+
+pub fn set_online_guard(mode: OnlineGuardMode) {
+    MODE.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn online_guard() -> OnlineGuardMode {
+    match MODE.load(Ordering::Relaxed) {
+        1 => OnlineGuardMode::Sessions,
+        2 => OnlineGuardMode::Connected,
+        _ => OnlineGuardMode::Off,
+    }
+}
+
+/// The state of the current session, None if there is none. Failed and
+/// leaving sessions count as sessions until the game is back to none.
+pub fn online_session() -> Result<Option<LobbyState>, InstanceError> {
+    let session_manager = unsafe { CSSessionManager::instance() }?;
+    Ok(Some(session_manager.lobby_state).filter(|state| *state != LobbyState::None))
+}
+
+/// Fails if the game is online as far as the current [OnlineGuardMode] is
+/// concerned. Also fails if the state can't be read, since the game might be
+/// online then.
+pub fn check_online_guard() -> Result<(), OnlineGuardError> {
+    let mode = online_guard();
+    if mode == OnlineGuardMode::Off {
+        return Ok(());
+    }
+
+    if let Some(state) = online_session()? {
+        return Err(OnlineGuardError::Session(state));
+    }
+
+    if mode == OnlineGuardMode::Connected {
+        let net_man = unsafe { CSNetMan::instance() }?;
+        if !net_man.server_connection_lost {
+            return Err(OnlineGuardError::Connected);
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+//! Live edits to loaded params that can be undone.
+//!
+//! [ParamPatcher] writes fields through a
+//! [ParamDefinition](crate::formats::ParamDefinition) loaded at
+//! runtime, so any param can be edited without a generated struct for it,
+//! and keeps the bytes each edit replaced. Edits are undone in reverse
+//! order with [ParamPatcher::undo], or all at once with
+//! [ParamPatcher::revert_all].
+use shared::{FromStatic, InstanceError};
+use thiserror::Error;
+
+use crate::fd4::FD4ParamRepository;
+use crate::formats::{ParamField, ParamTableError, ParamValue};
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+#[derive(Error, Debug)]
+pub enum ParamPatchError {
+    #[error("Could not get instance: {0}")]
+    Instance(#[from] InstanceError),
+    #[error("Param {0} is not loaded")]
+    MissingParam(String),
+    #[error("Param {0} has no row {1}")]
+    MissingRow(String, u32),
+    #[error("Could not read param: {0}")]
+    Table(#[from] ParamTableError),
+    #[error("Value {1:?} does not fit field {0}")]
+    InvalidValue(String, ParamValue),
+    #[error(transparent)]
+    Online(#[from] OnlineGuardError),
+}
+
+/// A single edit, with the bytes it replaced.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamPatch {
+    /// Name of the param, ex. "EquipParamWeapon".
+    pub param_name: String,
+    pub row_id: u32,
+    pub offset: usize,
+    pub original: Vec<u8>,
+    pub patched: Vec<u8>,
+}
+
+#[derive(Debug, Default)]
+pub struct ParamPatcher {
+    history: Vec<ParamPatch>,
+}
+
+impl ParamPatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every edit still in effect, oldest first.
+    pub fn history(&self) -> &[ParamPatch] {
+        &self.history
+    }
+
+    /// Writes a field of a loaded row.
+    #[track_caller]
+    pub fn write_field(
+        &mut self,
+        param_name: &str,
+        row_id: u32,
+        field: &ParamField,
+        value: &ParamValue,
+    ) -> Result<(), ParamPatchError> {
+        check_online_guard()?;
+        let params = unsafe { FD4ParamRepository::instance() }?;
+        let row = loaded_row(params, param_name, row_id)?;
+
+        // Bitfields only touch part of their storage unit, but the whole unit
+        // is kept.
+        let size = field.field_type.size() * field.count;
+        let Some(original) = row.get(field.offset..field.offset + size) else {
+            return Err(ParamPatchError::InvalidValue(
+                field.name.clone(),
+                value.clone(),
+            ));
+        };
+        let original = original.to_vec();
+
+        if !field.write(row, value) {
+            return Err(ParamPatchError::InvalidValue(
+                field.name.clone(),
+                value.clone(),
+            ));
+        }
+
+        let patched = row[field.offset..field.offset + size].to_vec();
+        #[cfg(feature = "audit")]
+        crate::util::audit::record_write(
+            row[field.offset..].as_ptr() as usize,
+            &original,
+            &patched,
+        );
+
+        self.history.push(ParamPatch {
+            param_name: param_name.to_string(),
+            row_id,
+            offset: field.offset,
+            original,
+            patched,
+        });
+        Ok(())
+    }
+
+    /// Undoes the most recent edit and returns it, None if there's nothing
+    /// left to undo.
+    #[track_caller]
+    pub fn undo(&mut self) -> Result<Option<ParamPatch>, ParamPatchError> {
+        let Some(patch) = self.history.last() else {
+            return Ok(None);
+        };
+
+        check_online_guard()?;
+        let params = unsafe { FD4ParamRepository::instance() }?;
+        let row = loaded_row(params, &patch.param_name, patch.row_id)?;
+        let bytes = &mut row[patch.offset..patch.offset + patch.original.len()];
+        #[cfg(feature = "audit")]
+        crate::util::audit::record_write(bytes.as_ptr() as usize, bytes, &patch.original);
+        bytes.copy_from_slice(&patch.original);
+        Ok(self.history.pop())
+    }
+
+    /// Undoes every edit.
+    #[track_caller]
+    pub fn revert_all(&mut self) -> Result<(), ParamPatchError> {
+        while self.undo()?.is_some() {}
+        Ok(())
+    }
+}
+
+fn loaded_row<'a>(
+    params: &'a mut FD4ParamRepository,
+    param_name: &str,
+    row_id: u32,
+) -> Result<&'a mut [u8], ParamPatchError> {
+    let res_cap = params
+        .res_rep
+        .res_cap_holder
+        .entries_mut()
+        .find(|e| e.inner.name.to_string() == param_name)
+        .ok_or_else(|| ParamPatchError::MissingParam(param_name.to_string()))?;
+
+    res_cap
+        .row_mut(row_id)?
+        .ok_or_else(|| ParamPatchError::MissingRow(param_name.to_string(), row_id))
+}
@@ -0,0 +1,112 @@
+//! Lists the members of the local player's party and turns on friendly fire
+//! between them.
+//!
+//! Friendly fire works by moving the other party members to a team the host's
+//! team can hit instead, and moving them back once it's turned off. Only the
+//! local copies of the characters are changed, other players in the session
+//! keep seeing the original teams.
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{
+    FieldInsHandle, GameMan, MemberType, PartyMemberEntryState, TeamType, WorldChrMan,
+};
+use crate::util::audited::set;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartyMember {
+    pub handle: FieldInsHandle,
+    pub member_type: MemberType,
+    pub state: PartyMemberEntryState,
+}
+
+/// Lists the party members whose characters are currently loaded, including
+/// the host.
+pub fn party_members() -> Result<Vec<PartyMember>, InstanceError> {
+    let game_man = unsafe { GameMan::instance() }?;
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+
+    Ok(game_man
+        .party_member_info
+        .party_members
+        .iter()
+        .filter(|entry| {
+            world_chr_man
+                .chr_ins_by_handle(&entry.field_ins_handle)
+                .is_some()
+        })
+        .map(|entry| PartyMember {
+            handle: entry.field_ins_handle,
+            member_type: entry.member_type,
+            state: entry.state,
+        })
+        .collect())
+}
+
+#[derive(Default)]
+struct FriendlyFireState {
+    team: Option<TeamType>,
+    /// Team every moved character had before friendly fire was turned on.
+    original_teams: HashMap<FieldInsHandle, u8>,
+}
+
+static STATE: LazyLock<Mutex<FriendlyFireState>> = LazyLock::new(Default::default);
+
+/// Turns on friendly fire by moving every party member except the host to
+/// `team`, e.g. [TeamType::HostileAlly].
+pub fn enable_friendly_fire(team: TeamType) {
+    STATE.lock().unwrap().team = Some(team);
+}
+
+/// Turns friendly fire off again. The party members are moved back to their
+/// original teams by the next [update_friendly_fire].
+pub fn disable_friendly_fire() {
+    STATE.lock().unwrap().team = None;
+}
+
+/// Moves party members between teams as needed. This needs to be called every
+/// frame so members that join or load in later are moved as well. Members are
+/// still moved back while the online guard refuses.
+pub fn update_friendly_fire() -> Result<(), OnlineGuardError> {
+    let mut state = STATE.lock().unwrap();
+    if state.team.is_none() && state.original_teams.is_empty() {
+        return Ok(());
+    }
+
+    let game_man = unsafe { GameMan::instance() }?;
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let FriendlyFireState {
+        team,
+        original_teams,
+    } = &mut *state;
+
+    let Some(team) = *team else {
+        for (handle, original_team) in original_teams.drain() {
+            if let Some(chr_ins) = world_chr_man.chr_ins_by_handle(&handle) {
+                set(&mut chr_ins.team_type, original_team);
+            }
+        }
+        return Ok(());
+    };
+
+    check_online_guard()?;
+    for entry in game_man.party_member_info.party_members.iter() {
+        if entry.member_type == MemberType::Host {
+            continue;
+        }
+
+        let Some(chr_ins) = world_chr_man.chr_ins_by_handle(&entry.field_ins_handle) else {
+            continue;
+        };
+
+        original_teams
+            .entry(entry.field_ins_handle)
+            .or_insert(chr_ins.team_type);
+        set(&mut chr_ins.team_type, team as u8);
+    }
+
+    Ok(())
+}
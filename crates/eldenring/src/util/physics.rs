@@ -0,0 +1,34 @@
+//! Read-only ragdoll state of characters, as kept by
+//! [ChrCtrl](crate::cs::ChrCtrl).
+//!
+//! There are no toggles here. Forcing a ragdoll or disabling cloth would go
+//! through the ragdoll driver and the cloth simulation handles, neither of
+//! which this crate binds.
+use crate::cs::ChrIns;
+
+/// Snapshot of a character's ragdoll.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RagdollState {
+    /// Whether the character has a ragdoll instance at all.
+    pub has_ragdoll: bool,
+    /// The raw ragdoll state as tracked by the game, 0 while the character
+    /// is animated normally.
+    pub state: u8,
+    /// Time until a ragdolled character gets back up.
+    pub revive_time: f32,
+}
+
+impl RagdollState {
+    pub fn is_ragdolling(&self) -> bool {
+        self.has_ragdoll && self.state != 0
+    }
+}
+
+pub fn ragdoll_state(chr_ins: &ChrIns) -> RagdollState {
+    let chr_ctrl = &chr_ins.chr_ctrl;
+    RagdollState {
+        has_ragdoll: chr_ctrl.ragdoll_ins != 0,
+        state: chr_ctrl.chr_ragdoll_state,
+        revive_time: chr_ctrl.ragdoll_revive_time,
+    }
+}
@@ -0,0 +1,164 @@
+//! Finding the character the camera is pointed at, e.g. to select it in an
+//! inspector overlay.
+//!
+//! The ray is tested against the hit capsule of every loaded character, built
+//! from its [physics module](crate::cs::CSChrPhysicsModule)'s hit radius and
+//! height, and the closest one it passes through is picked. Walls and the
+//! ground don't stop the ray, so a character behind a wall can be picked as
+//! well.
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{CSCamera, ChrIns, FieldInsHandle, WorldChrMan};
+use crate::position::HavokPosition;
+
+/// How far from the camera characters are picked by default, in meters.
+pub const DEFAULT_PICK_DISTANCE: f32 = 100.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PickedEntity {
+    pub handle: FieldInsHandle,
+    /// Distance from the camera along the ray to where it passes closest to
+    /// the character.
+    pub distance: f32,
+    pub position: HavokPosition,
+}
+
+/// Picks the character under the crosshair, i.e. in the center of the
+/// screen. The local player is never picked.
+pub fn pick_under_crosshair() -> Result<Option<PickedEntity>, InstanceError> {
+    let camera = unsafe { CSCamera::instance() }?;
+    let camera = &camera.pers_cam_1;
+    pick_along_ray(
+        camera.position().into(),
+        glam::Vec3::from(camera.forward()).normalize_or_zero(),
+        DEFAULT_PICK_DISTANCE,
+    )
+}
+
+/// Picks the closest character whose hit capsule the ray passes through.
+/// The local player is never picked.
+pub fn pick_along_ray(
+    origin: glam::Vec3,
+    direction: glam::Vec3,
+    max_distance: f32,
+) -> Result<Option<PickedEntity>, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let main_player = world_chr_man
+        .main_player
+        .as_ref()
+        .map(|player| player.chr_ins.field_ins_handle);
+
+    Ok(world_chr_man
+        .chr_sets
+        .iter()
+        .flatten()
+        .flat_map(|chr_set| chr_set.characters())
+        .filter(|chr_ins| Some(chr_ins.field_ins_handle) != main_player)
+        .filter_map(|chr_ins| {
+            let (bottom, top, radius) = hit_capsule(chr_ins);
+            let distance =
+                ray_capsule_distance(origin, direction, max_distance, bottom, top, radius)?;
+            Some(PickedEntity {
+                handle: chr_ins.field_ins_handle,
+                distance,
+                position: chr_ins.module_container.physics.position,
+            })
+        })
+        .min_by(|a, b| a.distance.total_cmp(&b.distance)))
+}
+
+/// The end points and radius of a character's hit capsule, standing upright
+/// on its position.
+fn hit_capsule(chr_ins: &ChrIns) -> (glam::Vec3, glam::Vec3, f32) {
+    let physics = &chr_ins.module_container.physics;
+    let position = glam::Vec3::from(physics.position);
+    let radius = physics.hit_radius.max(0.0);
+    let height = physics.hit_height.max(radius * 2.0);
+    (
+        position + glam::Vec3::Y * radius,
+        position + glam::Vec3::Y * (height - radius),
+        radius,
+    )
+}
+
+/// Distance along the ray to where it passes closest to the capsule, None if
+/// it misses the capsule within `max_distance`. `direction` has to be
+/// normalized.
+fn ray_capsule_distance(
+    origin: glam::Vec3,
+    direction: glam::Vec3,
+    max_distance: f32,
+    bottom: glam::Vec3,
+    top: glam::Vec3,
+    radius: f32,
+) -> Option<f32> {
+    // Closest points between the ray, as a segment of max_distance, and the
+    // capsule's axis.
+    let ray = direction * max_distance;
+    let axis = top - bottom;
+    let offset = origin - bottom;
+
+    let ray_len_sq = ray.length_squared();
+    let axis_len_sq = axis.length_squared();
+    if ray_len_sq <= f32::EPSILON {
+        return None;
+    }
+
+    let ray_offset = ray.dot(offset);
+    let (t, s) = if axis_len_sq <= f32::EPSILON {
+        ((-ray_offset / ray_len_sq).clamp(0.0, 1.0), 0.0)
+    } else {
+        let axis_offset = axis.dot(offset);
+        let ray_axis = ray.dot(axis);
+        let denom = ray_len_sq * axis_len_sq - ray_axis * ray_axis;
+
+        let t = if denom > f32::EPSILON {
+            ((ray_axis * axis_offset - ray_offset * axis_len_sq) / denom).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let s = (ray_axis * t + axis_offset) / axis_len_sq;
+
+        if s < 0.0 {
+            ((-ray_offset / ray_len_sq).clamp(0.0, 1.0), 0.0)
+        } else if s > 1.0 {
+            (((ray_axis - ray_offset) / ray_len_sq).clamp(0.0, 1.0), 1.0)
+        } else {
+            (t, s)
+        }
+    };
+
+    let closest_on_ray = origin + ray * t;
+    let closest_on_axis = bottom + axis * s;
+    (closest_on_ray.distance_squared(closest_on_axis) <= radius * radius)
+        .then_some(t * max_distance)
+}
+
+#[cfg(test)]
+mod test {
+    use super::ray_capsule_distance;
+
+    #[test]
+    fn hits_capsule_in_front() {
+        let bottom = glam::vec3(0.0, 0.5, 10.0);
+        let top = glam::vec3(0.0, 1.5, 10.0);
+        let forward = glam::Vec3::Z;
+
+        let hit = ray_capsule_distance(glam::vec3(0.0, 1.0, 0.0), forward, 100.0, bottom, top, 0.5);
+        assert!((hit.unwrap() - 10.0).abs() < 1e-4);
+
+        // Passing over the top, behind the camera and out of range.
+        assert_eq!(
+            ray_capsule_distance(glam::vec3(0.0, 2.5, 0.0), forward, 100.0, bottom, top, 0.5),
+            None
+        );
+        assert_eq!(
+            ray_capsule_distance(glam::vec3(0.0, 1.0, 0.0), -forward, 100.0, bottom, top, 0.5),
+            None
+        );
+        assert_eq!(
+            ray_capsule_distance(glam::vec3(0.0, 1.0, 0.0), forward, 5.0, bottom, top, 0.5),
+            None
+        );
+    }
+}
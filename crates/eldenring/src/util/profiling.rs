@@ -0,0 +1,114 @@
+//! CPU frame and task group timings for performance overlays. There are no
+//! GPU timings and nothing from the engine's own profiler.
+//!
+//! Whole-frame timings come straight from [CSFlipper], which the game uses
+//! for its own frame pacing. Per task group times are measured from the
+//! outside: [profile_task_groups] registers a marker task in every requested
+//! group and the time between two markers running is attributed to the later
+//! group. That time includes every group the game ran in between that isn't
+//! profiled, so only profiling neighboring groups isolates a single one.
+//! Groups that run concurrently on the worker threads (the `SteamThread*`
+//! groups) don't produce meaningful numbers this way.
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use shared::{FromStatic, InstanceError, RecurringTaskHandle, SharedTaskImpExt};
+
+use crate::cs::{CSFlipper, CSTaskGroupIndex, CSTaskImp};
+use crate::fd4::FD4TaskData;
+
+/// Frame timings as tracked by the game's frame pacing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameTiming {
+    /// How long the last frame took in seconds.
+    pub frame_time: f32,
+    /// Average frame time over the game's rolling window in seconds.
+    pub average_frame_time: f32,
+    /// Frame rate as calculated by the game.
+    pub fps: f32,
+    /// Frame rate the game is currently pacing towards.
+    pub target_fps: f32,
+}
+
+/// Reads the current frame timings.
+pub fn frame_timing() -> Result<FrameTiming, InstanceError> {
+    let flipper = unsafe { CSFlipper::instance() }?;
+    let rolling_average = &flipper.frame_time_rolling_average;
+
+    Ok(FrameTiming {
+        frame_time: flipper.last_frame_time,
+        average_frame_time: rolling_average.iter().sum::<f32>() / rolling_average.len() as f32,
+        fps: flipper.calculated_fps,
+        target_fps: flipper.fixed_fps,
+    })
+}
+
+/// Time spent since the previous profiled task group ran, up to and
+/// including `group`.
+#[derive(Clone, Copy, Debug)]
+pub struct TaskGroupTiming {
+    pub group: CSTaskGroupIndex,
+    pub elapsed: Duration,
+}
+
+#[derive(Default)]
+struct ProfilerState {
+    /// Groups that already have a marker task.
+    profiled: Vec<u32>,
+    last_marker: Option<Instant>,
+    current_frame: Vec<TaskGroupTiming>,
+    last_frame: Vec<TaskGroupTiming>,
+    handles: Vec<RecurringTaskHandle<FD4TaskData>>,
+}
+
+// SAFETY: the handles are never touched after they were registered, they
+// only exist to keep the marker tasks alive.
+unsafe impl Send for ProfilerState {}
+
+static STATE: LazyLock<Mutex<ProfilerState>> = LazyLock::new(Default::default);
+
+/// Starts measuring the given task groups. Can be called multiple times,
+/// groups that are already being profiled are skipped.
+pub fn profile_task_groups(groups: &[CSTaskGroupIndex]) -> Result<(), InstanceError> {
+    let cs_task = unsafe { CSTaskImp::instance() }?;
+    let mut state = STATE.lock().unwrap();
+
+    // The FrameBegin marker is what separates the frames from each other.
+    for group in [CSTaskGroupIndex::FrameBegin].iter().chain(groups) {
+        let group = *group;
+        if state.profiled.contains(&(group as u32)) {
+            continue;
+        }
+
+        let handle = cs_task.run_recurring(
+            move |_: &FD4TaskData| record_marker(&mut STATE.lock().unwrap(), group),
+            group,
+        );
+
+        state.profiled.push(group as u32);
+        state.handles.push(handle);
+    }
+
+    Ok(())
+}
+
+fn record_marker(state: &mut ProfilerState, group: CSTaskGroupIndex) {
+    let now = Instant::now();
+
+    if let CSTaskGroupIndex::FrameBegin = group {
+        state.last_frame = std::mem::take(&mut state.current_frame);
+    } else if let Some(last_marker) = state.last_marker {
+        state.current_frame.push(TaskGroupTiming {
+            group,
+            elapsed: now - last_marker,
+        });
+    }
+
+    state.last_marker = Some(now);
+}
+
+/// Timings of the profiled task groups during the last full frame, in the
+/// order they ran in.
+pub fn task_group_timings() -> Vec<TaskGroupTiming> {
+    STATE.lock().unwrap().last_frame.clone()
+}
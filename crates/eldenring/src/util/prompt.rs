@@ -0,0 +1,30 @@
+//! Contextual button prompts drawn by the game's own UI.
+//!
+//! Prompts are action buttons, the "Examine" or "Open" prompts next to
+//! interactable objects. They're described by an ActionButtonParam row, show the glyph for the
+//! player's current input device and report when they're pressed. To show
+//! custom text, point a row's text at a message added to the game's FMGs,
+//! ex. with a text mod written through [Fmg](crate::formats::Fmg).
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::CSActionButtonManImp;
+use crate::fd4::FD4ParamRepository;
+use crate::param::ACTIONBUTTON_PARAM_ST;
+
+/// Shows the prompt described by an ActionButtonParam row for this frame.
+/// Returns true if the player pressed the button.
+///
+/// Prompts have to be shown again every frame for them to stay on screen,
+/// e.g. from a recurring task in the `ChrIns_PostPhysics` group.
+pub fn show_prompt(action_button_param_id: i32) -> Result<bool, InstanceError> {
+    let action_button_man = unsafe { CSActionButtonManImp::instance() }?;
+    Ok(action_button_man.present_action_button(action_button_param_id))
+}
+
+/// The message ID of the text an ActionButtonParam row shows.
+pub fn prompt_text_id(action_button_param_id: i32) -> Result<Option<i32>, InstanceError> {
+    let param_repository = unsafe { FD4ParamRepository::instance() }?;
+    Ok(param_repository
+        .get::<ACTIONBUTTON_PARAM_ST>(action_button_param_id as u32)
+        .map(|row| row.text_id()))
+}
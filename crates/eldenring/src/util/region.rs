@@ -0,0 +1,198 @@
+//! Caller-registered trigger volumes identified by entity ID, with
+//! containment queries for the local player.
+//!
+//! Regions are registered with the same entity ID, block and shape the map
+//! defines, ex. as exported from a map editor, after which
+//! [is_player_in_region] answers the same question as the in/outside area
+//! conditions of EMEVD.
+//!
+//! Only rotation around the vertical axis is supported. Positions are block
+//! positions, the player is only ever in regions of the block they're
+//! currently in.
+use std::collections::HashMap;
+use std::sync::{LazyLock, RwLock};
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{BlockId, WorldChrMan};
+use crate::position::BlockPosition;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RegionShape {
+    Sphere {
+        radius: f32,
+    },
+    /// Upright cylinder, the position is the center of its bottom.
+    Cylinder {
+        radius: f32,
+        height: f32,
+    },
+    /// Box rotated by the position's yaw, the position is the center of its
+    /// bottom.
+    Box {
+        width: f32,
+        depth: f32,
+        height: f32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Region {
+    pub entity_id: u32,
+    pub block_id: BlockId,
+    pub position: BlockPosition,
+    pub shape: RegionShape,
+    /// Inactive regions never contain anything.
+    pub active: bool,
+}
+
+impl Region {
+    pub fn new(
+        entity_id: u32,
+        block_id: BlockId,
+        position: BlockPosition,
+        shape: RegionShape,
+    ) -> Self {
+        Self {
+            entity_id,
+            block_id,
+            position,
+            shape,
+            active: true,
+        }
+    }
+
+    pub fn contains(&self, block_id: BlockId, position: BlockPosition) -> bool {
+        if !self.active || block_id != self.block_id {
+            return false;
+        }
+
+        let dx = position.x - self.position.x;
+        let dy = position.y - self.position.y;
+        let dz = position.z - self.position.z;
+        match self.shape {
+            RegionShape::Sphere { radius } => dx * dx + dy * dy + dz * dz <= radius * radius,
+            RegionShape::Cylinder { radius, height } => {
+                (0.0..=height).contains(&dy) && dx * dx + dz * dz <= radius * radius
+            }
+            RegionShape::Box {
+                width,
+                depth,
+                height,
+            } => {
+                // Undo the box's rotation so its sides line up with the axes.
+                let (sin, cos) = self.position.yaw.sin_cos();
+                let local_x = dx * cos - dz * sin;
+                let local_z = dx * sin + dz * cos;
+                (0.0..=height).contains(&dy)
+                    && local_x.abs() <= width / 2.0
+                    && local_z.abs() <= depth / 2.0
+            }
+        }
+    }
+}
+
+static REGIONS: LazyLock<RwLock<HashMap<u32, Region>>> = LazyLock::new(Default::default);
+
+/// Registers a region, replacing any region with the same entity ID.
+pub fn register_region(region: Region) {
+    REGIONS.write().unwrap().insert(region.entity_id, region);
+}
+
+pub fn unregister_region(entity_id: u32) {
+    REGIONS.write().unwrap().remove(&entity_id);
+}
+
+pub fn region(entity_id: u32) -> Option<Region> {
+    REGIONS.read().unwrap().get(&entity_id).copied()
+}
+
+/// Activates or deactivates a region. Returns false if it isn't registered.
+pub fn set_region_active(entity_id: u32, active: bool) -> bool {
+    match REGIONS.write().unwrap().get_mut(&entity_id) {
+        Some(region) => {
+            region.active = active;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Whether the local player is inside the region, None if the region isn't
+/// registered or no player is loaded.
+pub fn is_player_in_region(entity_id: u32) -> Result<Option<bool>, InstanceError> {
+    let Some(region) = region(entity_id) else {
+        return Ok(None);
+    };
+
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    Ok(world_chr_man
+        .main_player
+        .as_ref()
+        .map(|player| region.contains(player.current_block_id, player.block_position)))
+}
+
+/// Entity IDs of every active region the local player is inside of.
+pub fn player_regions() -> Result<Vec<u32>, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let Some(player) = world_chr_man.main_player.as_ref() else {
+        return Ok(Vec::new());
+    };
+
+    Ok(REGIONS
+        .read()
+        .unwrap()
+        .values()
+        .filter(|region| region.contains(player.current_block_id, player.block_position))
+        .map(|region| region.entity_id)
+        .collect())
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::FRAC_PI_2;
+
+    use super::{Region, RegionShape};
+    use crate::cs::BlockId;
+    use crate::position::BlockPosition;
+
+    #[test]
+    fn rotated_box_contains() {
+        let block = BlockId::from_parts(10, 0, 0, 0);
+        let mut position = BlockPosition::from_xyz(10.0, 0.0, 10.0);
+        position.yaw = FRAC_PI_2;
+        let region = Region::new(
+            1,
+            block,
+            position,
+            RegionShape::Box {
+                width: 10.0,
+                depth: 2.0,
+                height: 5.0,
+            },
+        );
+
+        // Rotated a quarter turn the box runs along the z axis.
+        assert!(region.contains(block, BlockPosition::from_xyz(10.0, 1.0, 14.0)));
+        assert!(!region.contains(block, BlockPosition::from_xyz(14.0, 1.0, 10.0)));
+        assert!(!region.contains(block, BlockPosition::from_xyz(10.0, -1.0, 10.0)));
+        assert!(!region.contains(
+            BlockId::from_parts(11, 0, 0, 0),
+            BlockPosition::from_xyz(10.0, 1.0, 10.0)
+        ));
+    }
+
+    #[test]
+    fn inactive_region_contains_nothing() {
+        let block = BlockId::from_parts(10, 0, 0, 0);
+        let mut region = Region::new(
+            1,
+            block,
+            BlockPosition::from_xyz(0.0, 0.0, 0.0),
+            RegionShape::Sphere { radius: 1.0 },
+        );
+        region.active = false;
+
+        assert!(!region.contains(block, BlockPosition::from_xyz(0.0, 0.0, 0.0)));
+    }
+}
@@ -0,0 +1,114 @@
+//! Records and plays back the local player's action requests frame by frame.
+//!
+//! Only the actions the player requests through their
+//! [CSChrActionRequestModule](crate::cs::CSChrActionRequestModule) are
+//! recorded, which covers attacks, dodges, item use and the like but not
+//! movement or camera input. Playback writes the recorded actions back into
+//! the same module, so it's the game that decides whether they're performed.
+//! The game's own [ReplayRecorder](crate::cs::ReplayRecorder) only tracks the
+//! player's position for ghost replays and isn't involved.
+//!
+//! [update_replay] needs to be called every frame after the game has read the
+//! player's input, e.g. from a recurring task in the `ChrIns_PostPhysics`
+//! group.
+use std::sync::Mutex;
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{ChrActions, WorldChrMan};
+
+/// Action requests captured by a recording, one entry per frame.
+#[derive(Clone, Debug, Default)]
+pub struct InputRecording {
+    pub frames: Vec<ChrActions>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayStatus {
+    Idle,
+    Recording { frame: usize },
+    Playing { frame: usize, frame_count: usize },
+}
+
+enum ReplayState {
+    Idle,
+    Recording(InputRecording),
+    Playing {
+        recording: InputRecording,
+        frame: usize,
+    },
+}
+
+static STATE: Mutex<ReplayState> = Mutex::new(ReplayState::Idle);
+
+/// Starts a new recording, discarding any recording or playback in progress.
+pub fn start_recording() {
+    *STATE.lock().unwrap() = ReplayState::Recording(InputRecording::default());
+}
+
+/// Stops recording and returns what was recorded, None if nothing was being
+/// recorded.
+pub fn stop_recording() -> Option<InputRecording> {
+    let mut state = STATE.lock().unwrap();
+    match std::mem::replace(&mut *state, ReplayState::Idle) {
+        ReplayState::Recording(recording) => Some(recording),
+        other => {
+            *state = other;
+            None
+        }
+    }
+}
+
+/// Plays a recording back from its first frame, stopping any recording in
+/// progress.
+pub fn start_playback(recording: InputRecording) {
+    *STATE.lock().unwrap() = ReplayState::Playing {
+        recording,
+        frame: 0,
+    };
+}
+
+pub fn stop_playback() {
+    let mut state = STATE.lock().unwrap();
+    if matches!(*state, ReplayState::Playing { .. }) {
+        *state = ReplayState::Idle;
+    }
+}
+
+/// Records or plays back a single frame. Playback stops by itself after the
+/// last recorded frame.
+pub fn update_replay() -> Result<ReplayStatus, InstanceError> {
+    let mut state = STATE.lock().unwrap();
+    if matches!(*state, ReplayState::Idle) {
+        return Ok(ReplayStatus::Idle);
+    }
+
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let Some(player) = world_chr_man.main_player.as_mut() else {
+        return Ok(ReplayStatus::Idle);
+    };
+    let action_request = &mut player.chr_ins.module_container.action_request;
+
+    match &mut *state {
+        ReplayState::Idle => Ok(ReplayStatus::Idle),
+        ReplayState::Recording(recording) => {
+            recording.frames.push(action_request.action_requests);
+            Ok(ReplayStatus::Recording {
+                frame: recording.frames.len(),
+            })
+        }
+        ReplayState::Playing { recording, frame } => {
+            let Some(actions) = recording.frames.get(*frame) else {
+                *state = ReplayState::Idle;
+                return Ok(ReplayStatus::Idle);
+            };
+
+            action_request.action_requests = *actions;
+            *frame += 1;
+            Ok(ReplayStatus::Playing {
+                frame: *frame,
+                frame_count: recording.frames.len(),
+            })
+        }
+    }
+}
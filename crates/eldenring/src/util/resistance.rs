@@ -0,0 +1,171 @@
+//! Status effect resistance thresholds for all characters, and buildup for
+//! players.
+//!
+//! Enemies and NPCs only have their thresholds, straight from their NpcParam
+//! row. Their current buildup lives in the resist module, which is still a
+//! bare pointer in [ChrInsModuleContainer](crate::cs::ChrInsModuleContainer),
+//! so it can't be read here.
+//!
+//! Players keep their buildup, thresholds and the timers of active effects in
+//! [PlayerGameData]. Those are arrays with one slot per effect, which slot
+//! belongs to which effect hasn't been confirmed yet, so they're exposed by
+//! slot index. [StatusGaugeWatcher] reports when they change.
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{ChrIns, PlayerGameData};
+use crate::fd4::FD4ParamRepository;
+use crate::param::NPC_PARAM_ST;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StatusEffect {
+    Poison,
+    ScarletRot,
+    Hemorrhage,
+    DeathBlight,
+    Frostbite,
+    Sleep,
+    Madness,
+}
+
+impl StatusEffect {
+    pub const ALL: [StatusEffect; 7] = [
+        StatusEffect::Poison,
+        StatusEffect::ScarletRot,
+        StatusEffect::Hemorrhage,
+        StatusEffect::DeathBlight,
+        StatusEffect::Frostbite,
+        StatusEffect::Sleep,
+        StatusEffect::Madness,
+    ];
+}
+
+/// Buildup needed for each status effect to trigger.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Resistances {
+    pub poison: u16,
+    pub scarlet_rot: u16,
+    pub hemorrhage: u16,
+    pub death_blight: u16,
+    pub frostbite: u16,
+    pub sleep: u16,
+    pub madness: u16,
+}
+
+impl Resistances {
+    pub fn from_npc_param(row: &NPC_PARAM_ST) -> Self {
+        Self {
+            poison: row.resist_poison(),
+            scarlet_rot: row.resist_desease(),
+            hemorrhage: row.resist_blood(),
+            death_blight: row.resist_curse(),
+            frostbite: row.resist_freeze(),
+            sleep: row.resist_sleep(),
+            madness: row.resist_madness(),
+        }
+    }
+
+    pub fn get(&self, effect: StatusEffect) -> u16 {
+        match effect {
+            StatusEffect::Poison => self.poison,
+            StatusEffect::ScarletRot => self.scarlet_rot,
+            StatusEffect::Hemorrhage => self.hemorrhage,
+            StatusEffect::DeathBlight => self.death_blight,
+            StatusEffect::Frostbite => self.frostbite,
+            StatusEffect::Sleep => self.sleep,
+            StatusEffect::Madness => self.madness,
+        }
+    }
+}
+
+/// Resistances of an enemy or NPC, None if its NpcParam row doesn't exist.
+pub fn npc_resistances(chr_ins: &ChrIns) -> Result<Option<Resistances>, InstanceError> {
+    let param_repository = unsafe { FD4ParamRepository::instance() }?;
+    Ok(u32::try_from(chr_ins.npc_param_id)
+        .ok()
+        .and_then(|id| param_repository.get::<NPC_PARAM_ST>(id))
+        .map(Resistances::from_npc_param))
+}
+
+/// One of the player's status effect slots.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatusGauge {
+    pub buildup: u32,
+    /// Buildup at which the effect triggers.
+    pub max: u32,
+    /// Time left on the effect once it triggered.
+    pub timer: f32,
+    pub timer_max: f32,
+}
+
+/// The player's status effect slots, in the order the game stores them.
+pub fn status_gauges(player_game_data: &PlayerGameData) -> [StatusGauge; 7] {
+    std::array::from_fn(|slot| StatusGauge {
+        buildup: player_game_data.resistance_gauges[slot],
+        max: player_game_data.resistance_gauge_max[slot],
+        timer: player_game_data.proc_status_timers[slot],
+        timer_max: player_game_data.proc_status_timer_max[slot],
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StatusGaugeChange {
+    pub slot: usize,
+    pub previous: StatusGauge,
+    pub current: StatusGauge,
+}
+
+/// Compares status gauges between updates, e.g. to redraw an overlay only when
+/// something changed.
+#[derive(Default)]
+pub struct StatusGaugeWatcher {
+    previous: Option<[StatusGauge; 7]>,
+}
+
+impl StatusGaugeWatcher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the slots that changed since the last update. The first update
+    /// reports nothing.
+    pub fn update(&mut self, gauges: [StatusGauge; 7]) -> Vec<StatusGaugeChange> {
+        let Some(previous) = self.previous.replace(gauges) else {
+            return Vec::new();
+        };
+
+        (0..gauges.len())
+            .filter(|slot| previous[*slot] != gauges[*slot])
+            .map(|slot| StatusGaugeChange {
+                slot,
+                previous: previous[slot],
+                current: gauges[slot],
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{StatusGauge, StatusGaugeWatcher};
+
+    #[test]
+    fn watcher_reports_changed_slots() {
+        let empty = StatusGauge {
+            buildup: 0,
+            max: 300,
+            timer: 0.0,
+            timer_max: 0.0,
+        };
+        let mut gauges = [empty; 7];
+
+        let mut watcher = StatusGaugeWatcher::new();
+        assert!(watcher.update(gauges).is_empty());
+
+        gauges[2].buildup = 120;
+        let changes = watcher.update(gauges);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].slot, 2);
+        assert_eq!(changes[0].current.buildup, 120);
+        assert!(watcher.update(gauges).is_empty());
+    }
+}
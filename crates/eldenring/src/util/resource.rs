@@ -0,0 +1,33 @@
+//! Reads files without stalling the game thread.
+//!
+//! This is not the engine's asynchronous resource request path, which isn't
+//! mapped yet: requests made here don't go through the CSFileImp
+//! repositories, so they don't produce a resource capsule and can't read
+//! from the game's archives. Files are read from disk on a background thread
+//! through [submit_job], and the result is handed back through
+//! [run_on_task_group] on the task group the caller asked for, usually the
+//! same stage of the frame that made the request.
+use std::io;
+use std::path::PathBuf;
+
+use crate::cs::CSTaskGroupIndex;
+use crate::util::job::{JobHandle, run_on_task_group, submit_job};
+
+/// Reads `path` in the background and calls `on_loaded` with its contents
+/// the next time the game runs `callback_group`.
+///
+/// The returned handle only tracks the read itself, the callback runs some
+/// time after it finishes.
+pub fn load_file(
+    path: impl Into<PathBuf>,
+    callback_group: CSTaskGroupIndex,
+    on_loaded: impl FnOnce(io::Result<Vec<u8>>) + Send + 'static,
+) -> JobHandle {
+    let path = path.into();
+    submit_job(move || {
+        let result = std::fs::read(&path);
+        if let Err(e) = run_on_task_group(callback_group, move || on_loaded(result)) {
+            tracing::error!("Could not deliver {} after loading it: {e}", path.display());
+        }
+    })
+}
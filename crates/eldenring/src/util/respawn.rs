@@ -0,0 +1,119 @@
+//! Overriding where the player comes back after dying.
+//!
+//! The entity the player is placed at when a map is loaded is exposed through
+//! [GameMan::initial_area_entity_id]. Overrides work by letting the game
+//! respawn the player as usual and moving them to the override point once
+//! they're back.
+//!
+//! Points are stored in the physics world's coordinates, which only stay
+//! meaningful while the respawn loads the player back into the same map.
+use std::sync::Mutex;
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{ChrIns, GameMan, WorldChrMan};
+use crate::position::HavokPosition;
+use crate::rotation::Quaternion;
+use crate::util::audited::set;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RespawnPoint {
+    pub position: HavokPosition,
+    pub orientation: Quaternion,
+}
+
+impl RespawnPoint {
+    pub fn from_chr_ins(chr_ins: &ChrIns) -> Self {
+        let physics = &chr_ins.module_container.physics;
+        Self {
+            position: physics.position,
+            orientation: physics.orientation,
+        }
+    }
+
+    /// Moves the character to this point.
+    pub fn teleport(&self, chr_ins: &mut ChrIns) -> Result<(), OnlineGuardError> {
+        check_online_guard()?;
+        let physics = &mut chr_ins.module_container.physics;
+        set(&mut physics.position, self.position);
+        set(&mut physics.orientation, self.orientation);
+
+        // Moves the Havok character along with the physics module.
+        let proxy_flags = &mut chr_ins.chr_ctrl.chr_proxy_flags;
+        proxy_flags.set_position_sync_requested(true);
+        proxy_flags.set_rotation_sync_requested(true);
+        Ok(())
+    }
+}
+
+struct RespawnState {
+    point: Option<RespawnPoint>,
+    /// Whether the player died and hasn't been moved to the point yet.
+    pending: bool,
+}
+
+static STATE: Mutex<RespawnState> = Mutex::new(RespawnState {
+    point: None,
+    pending: false,
+});
+
+/// Entity ID of the spot the player is placed at when the next map loads,
+/// e.g. the site of grace they warped to.
+pub fn initial_area_entity_id() -> Result<u32, InstanceError> {
+    Ok(unsafe { GameMan::instance() }?.initial_area_entity_id)
+}
+
+/// Moves the player to `point` every time they respawn after dying, until
+/// it's cleared again with None.
+pub fn set_respawn_override(point: Option<RespawnPoint>) {
+    let mut state = STATE.lock().unwrap();
+    state.point = point;
+    state.pending = false;
+}
+
+/// Sets the player's current position as the respawn override. Returns the
+/// point, None if no player is loaded.
+pub fn set_respawn_here() -> Result<Option<RespawnPoint>, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let point = world_chr_man
+        .main_player
+        .as_ref()
+        .map(|player| RespawnPoint::from_chr_ins(&player.chr_ins));
+
+    if point.is_some() {
+        set_respawn_override(point);
+    }
+    Ok(point)
+}
+
+pub fn respawn_override() -> Option<RespawnPoint> {
+    STATE.lock().unwrap().point
+}
+
+/// Watches for the player dying and moves them to the override point once
+/// they're alive again. This needs to be called every frame.
+pub fn update_respawn_override() -> Result<(), OnlineGuardError> {
+    let mut state = STATE.lock().unwrap();
+    let Some(point) = state.point else {
+        return Ok(());
+    };
+
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let Some(player) = world_chr_man.main_player.as_mut() else {
+        return Ok(());
+    };
+
+    let chr_ins = &mut player.chr_ins;
+    if chr_ins.module_container.data.hp <= 0 {
+        state.pending = true;
+        return Ok(());
+    }
+
+    if state.pending {
+        state.pending = false;
+        point.teleport(chr_ins)?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+//! Requesting and holding back the game's saves.
+//!
+//! This goes through the save request flag on [GameMan] that every save,
+//! autosaves included, passes through. Call [apply_save_control] every frame,
+//! e.g. from a recurring task in the `FrameBegin` group, for suppression to
+//! work. The flag only says a save is wanted, not when it's written, so
+//! completed saves aren't reported.
+use std::sync::Mutex;
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::GameMan;
+
+struct SaveControl {
+    suppressed: bool,
+    /// A save that was requested while saves were suppressed.
+    deferred: bool,
+}
+
+static STATE: Mutex<SaveControl> = Mutex::new(SaveControl {
+    suppressed: false,
+    deferred: false,
+});
+
+/// Asks the game to save at the next opportunity. While saves are
+/// suppressed the request is held back until they're allowed again.
+pub fn request_save() -> Result<(), InstanceError> {
+    let mut state = STATE.lock().unwrap();
+    if state.suppressed {
+        state.deferred = true;
+        return Ok(());
+    }
+
+    unsafe { GameMan::instance() }?.save_requested = true;
+    Ok(())
+}
+
+/// Holds back all saves, including the game's own autosaves, until called
+/// again with false. Saves requested in the meantime aren't lost, a single
+/// save is made once suppression ends.
+pub fn suppress_saves(suppress: bool) {
+    STATE.lock().unwrap().suppressed = suppress;
+}
+
+pub fn saves_suppressed() -> bool {
+    STATE.lock().unwrap().suppressed
+}
+
+/// Holds back or releases pending save requests.
+pub fn apply_save_control() -> Result<(), InstanceError> {
+    let game_man = unsafe { GameMan::instance() }?;
+    let mut state = STATE.lock().unwrap();
+
+    if state.suppressed {
+        if game_man.save_requested {
+            game_man.save_requested = false;
+            state.deferred = true;
+        }
+    } else if state.deferred {
+        game_man.save_requested = true;
+        state.deferred = false;
+    }
+
+    Ok(())
+}
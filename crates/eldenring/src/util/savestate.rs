@@ -0,0 +1,188 @@
+//! In-memory snapshots of the player's situation for practice tools.
+//!
+//! A [Savestate] captures a configurable subset of the game state and puts
+//! it back on demand, without touching the save file. It's deliberately not
+//! a full snapshot of the game: anything that isn't listed in the
+//! [SavestateConfig] keeps running as usual, and characters that were
+//! unloaded or killed since the capture aren't brought back.
+//!
+//! Restoring writes to live characters, so it should happen from a task
+//! rather than another thread. Combine it with
+//! [suppress_saves](super::save::suppress_saves) so the game doesn't persist
+//! the intermediate state.
+use thiserror::Error;
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{CSEventFlagMan, ChrIns, FieldInsHandle, WorldChrMan};
+use crate::util::audited::{set, set_flag};
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+use crate::util::respawn::RespawnPoint;
+
+#[derive(Error, Debug)]
+pub enum SavestateError {
+    #[error(transparent)]
+    Instance(#[from] InstanceError),
+    #[error("The main player isn't loaded")]
+    NoPlayer,
+    #[error(transparent)]
+    Online(#[from] OnlineGuardError),
+}
+
+/// What a [Savestate] captures.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SavestateConfig {
+    /// The player's position and orientation.
+    pub player_position: bool,
+    /// The player's HP, FP and stamina.
+    pub player_stats: bool,
+    /// Event flags to capture, e.g. a boss's defeated flag.
+    pub event_flags: Vec<u32>,
+    /// Captures the position and stats of every character within this many
+    /// meters of the player.
+    pub enemy_radius: Option<f32>,
+}
+
+impl Default for SavestateConfig {
+    fn default() -> Self {
+        Self {
+            player_position: true,
+            player_stats: true,
+            event_flags: Vec::new(),
+            enemy_radius: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ChrSnapshot {
+    point: RespawnPoint,
+    hp: i32,
+    fp: i32,
+    stamina: i32,
+}
+
+impl ChrSnapshot {
+    fn capture(chr_ins: &ChrIns) -> Self {
+        let data = &chr_ins.module_container.data;
+        Self {
+            point: RespawnPoint::from_chr_ins(chr_ins),
+            hp: data.hp,
+            fp: data.fp,
+            stamina: data.stamina,
+        }
+    }
+
+    fn restore_stats(&self, chr_ins: &mut ChrIns) {
+        let data = &mut chr_ins.module_container.data;
+        set(&mut data.hp, self.hp.min(data.max_hp));
+        set(&mut data.fp, self.fp.min(data.max_fp));
+        set(&mut data.stamina, self.stamina.min(data.max_stamina));
+    }
+}
+
+/// A snapshot taken with [Savestate::capture].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Savestate {
+    config: SavestateConfig,
+    player: ChrSnapshot,
+    event_flags: Vec<(u32, bool)>,
+    enemies: Vec<(FieldInsHandle, ChrSnapshot)>,
+}
+
+impl Savestate {
+    pub fn capture(config: &SavestateConfig) -> Result<Self, SavestateError> {
+        let world_chr_man = unsafe { WorldChrMan::instance() }?;
+        let player = &world_chr_man
+            .main_player
+            .as_ref()
+            .ok_or(SavestateError::NoPlayer)?
+            .chr_ins;
+        let player_snapshot = ChrSnapshot::capture(player);
+        let player_handle = player.field_ins_handle;
+
+        let event_flags = match config.event_flags.is_empty() {
+            true => Vec::new(),
+            false => {
+                let flags = &unsafe { CSEventFlagMan::instance() }?.virtual_memory_flag;
+                config
+                    .event_flags
+                    .iter()
+                    .map(|flag| (*flag, flags.get_flag(*flag)))
+                    .collect()
+            }
+        };
+
+        let mut enemies = Vec::new();
+        if let Some(radius) = config.enemy_radius {
+            let player_position = glam::Vec3A::from(player_snapshot.point.position);
+            for chr_ins in world_chr_man
+                .chr_sets
+                .iter()
+                .flatten()
+                .flat_map(|chr_set| chr_set.characters())
+            {
+                let position = chr_ins.module_container.physics.position;
+                if chr_ins.field_ins_handle != player_handle
+                    && player_position.distance(position.into()) <= radius
+                {
+                    enemies.push((chr_ins.field_ins_handle, ChrSnapshot::capture(chr_ins)));
+                }
+            }
+        }
+
+        Ok(Self {
+            config: config.clone(),
+            player: player_snapshot,
+            event_flags,
+            enemies,
+        })
+    }
+
+    /// Puts the captured state back. Characters that aren't loaded anymore
+    /// are skipped.
+    pub fn restore(&self) -> Result<(), SavestateError> {
+        check_online_guard()?;
+        let world_chr_man = unsafe { WorldChrMan::instance() }?;
+
+        let player = &mut world_chr_man
+            .main_player
+            .as_mut()
+            .ok_or(SavestateError::NoPlayer)?
+            .chr_ins;
+        if self.config.player_position {
+            self.player.point.teleport(player)?;
+        }
+        if self.config.player_stats {
+            self.player.restore_stats(player);
+        }
+
+        if !self.event_flags.is_empty() {
+            let flags = &mut unsafe { CSEventFlagMan::instance() }?.virtual_memory_flag;
+            for (flag, state) in &self.event_flags {
+                set_flag(flags, *flag, *state);
+            }
+        }
+
+        for (handle, snapshot) in &self.enemies {
+            let Some(chr_ins) = world_chr_man
+                .chr_sets
+                .iter_mut()
+                .flatten()
+                .find_map(|chr_set| chr_set.chr_ins_by_handle(handle))
+            else {
+                continue;
+            };
+
+            snapshot.point.teleport(chr_ins)?;
+            snapshot.restore_stats(chr_ins);
+        }
+
+        Ok(())
+    }
+
+    /// Amount of characters other than the player in this snapshot.
+    pub fn enemy_count(&self) -> usize {
+        self.enemies.len()
+    }
+}
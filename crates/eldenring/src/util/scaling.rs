@@ -0,0 +1,129 @@
+//! Per-encounter control over how enemies are scaled.
+//!
+//! The game scales enemies by applying SpEffects to them. NG+ scaling comes
+//! from the NpcParam's gameClearSpEffectId (and dlcGameClearSpEffectId in
+//! the DLC's areas), which in turn picks up the multipliers for the current
+//! clear count. Area scaling comes from the other SpEffect slots of the row
+//! or from map events.
+//!
+//! Overrides registered here are applied to characters by their event
+//! entity ID, so a mod can scale a single boss without editing the NpcParam
+//! row that it shares with every other instance of that enemy. Call
+//! [apply_scaling_overrides] every frame, e.g. from a recurring task.
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{ChrIns, FieldInsHandle, WorldChrMan};
+use crate::fd4::FD4ParamRepository;
+use crate::param::NPC_PARAM_ST;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+/// The scaling SpEffects the game applies to a character by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NpcScaling {
+    pub game_clear_sp_effect: Option<i32>,
+    pub dlc_game_clear_sp_effect: Option<i32>,
+}
+
+/// How to change the scaling of a single encounter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScalingOverride {
+    /// Removes the NG+ scaling SpEffects the character would normally get.
+    pub remove_game_clear_scaling: bool,
+    /// Applies an additional SpEffect, e.g. a custom scaling row.
+    pub sp_effect: Option<i32>,
+}
+
+fn valid_sp_effect(id: i32) -> Option<i32> {
+    (id > 0).then_some(id)
+}
+
+/// Looks up the default scaling SpEffects of a character from its NpcParam.
+pub fn npc_scaling(chr_ins: &ChrIns) -> Result<Option<NpcScaling>, InstanceError> {
+    let param_repository = unsafe { FD4ParamRepository::instance() }?;
+    Ok(param_repository
+        .get::<NPC_PARAM_ST>(chr_ins.npc_param_id as u32)
+        .map(|npc_param| NpcScaling {
+            game_clear_sp_effect: valid_sp_effect(npc_param.game_clear_sp_effect_id()),
+            dlc_game_clear_sp_effect: valid_sp_effect(npc_param.dlc_game_clear_sp_effect_id()),
+        }))
+}
+
+#[derive(Default)]
+struct OverrideState {
+    overrides: HashMap<u32, ScalingOverride>,
+    /// Loaded characters the overrides have been applied to already.
+    applied: HashSet<FieldInsHandle>,
+}
+
+static STATE: LazyLock<Mutex<OverrideState>> = LazyLock::new(Default::default);
+
+/// Registers an override for every character with the given event entity ID.
+/// Characters that are already loaded pick it up on the next
+/// [apply_scaling_overrides].
+pub fn set_scaling_override(event_entity_id: u32, scaling: ScalingOverride) {
+    let mut state = STATE.lock().unwrap();
+    state.overrides.insert(event_entity_id, scaling);
+    state.applied.clear();
+}
+
+/// Removes an override. Characters it was applied to keep their changed
+/// scaling until they're reloaded.
+pub fn remove_scaling_override(event_entity_id: u32) {
+    STATE.lock().unwrap().overrides.remove(&event_entity_id);
+}
+
+/// Applies registered overrides to characters that haven't received them
+/// yet.
+pub fn apply_scaling_overrides() -> Result<(), OnlineGuardError> {
+    let mut state = STATE.lock().unwrap();
+    if state.overrides.is_empty() {
+        return Ok(());
+    }
+
+    check_online_guard()?;
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let characters = world_chr_man
+        .chr_sets
+        .iter()
+        .flatten()
+        .flat_map(|chr_set| chr_set.characters());
+
+    let OverrideState { overrides, applied } = &mut *state;
+    let mut loaded = HashSet::new();
+    for chr_ins in characters {
+        loaded.insert(chr_ins.field_ins_handle);
+        let Some(scaling) = overrides.get(&chr_ins.event_entity_id) else {
+            continue;
+        };
+
+        if !applied.insert(chr_ins.field_ins_handle) {
+            continue;
+        }
+
+        if scaling.remove_game_clear_scaling
+            && let Some(npc_scaling) = npc_scaling(chr_ins)?
+        {
+            for sp_effect in [
+                npc_scaling.game_clear_sp_effect,
+                npc_scaling.dlc_game_clear_sp_effect,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                chr_ins.remove_speffect(sp_effect);
+            }
+        }
+
+        if let Some(sp_effect) = scaling.sp_effect {
+            chr_ins.apply_speffect(sp_effect, false);
+        }
+    }
+
+    // Forget characters that were unloaded, so they get their override again
+    // once they're loaded back in.
+    applied.retain(|handle| loaded.contains(handle));
+    Ok(())
+}
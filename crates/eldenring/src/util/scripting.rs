@@ -0,0 +1,330 @@
+//! An embedded [Rhai](https://rhai.rs) engine with a small set of the
+//! crate's bindings, for quick scripts that don't need a rebuild. Only
+//! available with the `scripting` feature.
+//!
+//! Scripts get these functions:
+//!
+//! - `get_flag(id)` and `set_flag(id, state)` for event flags.
+//! - `player_hp()`, `player_max_hp()`, `set_player_hp(hp)` and the same for
+//!   `fp` and `stamina`.
+//! - `player_position()`, which returns `[x, y, z]`, and `teleport(x, y, z)`
+//!   in physics coordinates.
+//! - `get_param(param, row, field)` and `set_param(param, row, field, value)`
+//!   for params whose paramdef was added with [ScriptHost::add_paramdef].
+//!   Param edits can be undone with [ScriptHost::revert_params].
+//!
+//! [ScriptHost::engine_mut] can be used to register more functions.
+//!
+//! Scripts touch the game's state directly, so [ScriptHost::run] has to be
+//! called from the game's main thread, e.g. from a recurring task. To keep a
+//! runaway script from hanging the game, runs are cut off at the
+//! [ScriptLimits] set with [ScriptHost::set_limits].
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use rhai::{Dynamic, Engine, EvalAltResult, INT};
+use shared::FromStatic;
+use thiserror::Error;
+
+use crate::cs::{CSEventFlagMan, PlayerIns, WorldChrMan};
+use crate::fd4::FD4ParamRepository;
+use crate::formats::{ParamDefinition, ParamField, ParamValue};
+use crate::position::HavokPosition;
+use crate::util::audited::{set, set_flag};
+use crate::util::online_guard::check_online_guard;
+use crate::util::param_patch::{ParamPatchError, ParamPatcher};
+use crate::util::respawn::RespawnPoint;
+
+#[derive(Error, Debug)]
+pub enum ScriptError {
+    #[error("Script failed: {0}")]
+    Script(#[from] Box<EvalAltResult>),
+    #[error("Could not revert param edits: {0}")]
+    Params(#[from] ParamPatchError),
+}
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Limits a single [ScriptHost::run] is held to. Scripts exceeding them fail
+/// with an error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScriptLimits {
+    /// Operations a run may take, ex. loop iterations and function calls.
+    pub max_operations: u64,
+    /// How deeply functions may call each other.
+    pub max_call_levels: usize,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            max_operations: 1_000_000,
+            max_call_levels: 32,
+        }
+    }
+}
+
+#[derive(Default)]
+struct HostState {
+    /// Paramdefs by param type.
+    defs: HashMap<String, ParamDefinition>,
+    patcher: ParamPatcher,
+}
+
+pub struct ScriptHost {
+    engine: Engine,
+    state: Rc<RefCell<HostState>>,
+}
+
+impl Default for ScriptHost {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScriptHost {
+    pub fn new() -> Self {
+        let mut engine = Engine::new();
+        let state = Rc::new(RefCell::new(HostState::default()));
+
+        register_flags(&mut engine);
+        register_player(&mut engine);
+        register_params(&mut engine, &state);
+
+        let mut host = Self { engine, state };
+        host.set_limits(ScriptLimits::default());
+        host
+    }
+
+    pub fn set_limits(&mut self, limits: ScriptLimits) {
+        self.engine.set_max_operations(limits.max_operations);
+        self.engine.set_max_call_levels(limits.max_call_levels);
+    }
+
+    pub fn limits(&self) -> ScriptLimits {
+        ScriptLimits {
+            max_operations: self.engine.max_operations(),
+            max_call_levels: self.engine.max_call_levels(),
+        }
+    }
+
+    /// Makes a param type available to `get_param` and `set_param`.
+    pub fn add_paramdef(&mut self, def: ParamDefinition) {
+        self.state
+            .borrow_mut()
+            .defs
+            .insert(def.param_type.clone(), def);
+    }
+
+    /// The engine scripts run in, to register more functions.
+    pub fn engine_mut(&mut self) -> &mut Engine {
+        &mut self.engine
+    }
+
+    pub fn run(&self, script: &str) -> Result<(), ScriptError> {
+        Ok(self.engine.run(script)?)
+    }
+
+    /// Undoes every param edit made by scripts.
+    pub fn revert_params(&self) -> Result<(), ScriptError> {
+        Ok(self.state.borrow_mut().patcher.revert_all()?)
+    }
+}
+
+fn script_error(e: impl Display) -> Box<EvalAltResult> {
+    e.to_string().into()
+}
+
+fn flag_id(id: INT) -> ScriptResult<u32> {
+    u32::try_from(id).map_err(|_| script_error(format!("Invalid event flag {id}")))
+}
+
+fn register_flags(engine: &mut Engine) {
+    engine.register_fn("get_flag", |id: INT| -> ScriptResult<bool> {
+        let event_flag_man = unsafe { CSEventFlagMan::instance() }.map_err(script_error)?;
+        Ok(event_flag_man.virtual_memory_flag.get_flag(flag_id(id)?))
+    });
+
+    engine.register_fn("set_flag", |id: INT, state: bool| -> ScriptResult<()> {
+        check_online_guard().map_err(script_error)?;
+        let event_flag_man = unsafe { CSEventFlagMan::instance() }.map_err(script_error)?;
+        set_flag(&mut event_flag_man.virtual_memory_flag, flag_id(id)?, state);
+        Ok(())
+    });
+}
+
+fn player() -> ScriptResult<&'static mut PlayerIns> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }.map_err(script_error)?;
+    world_chr_man
+        .main_player
+        .as_mut()
+        .map(|player| &mut **player)
+        .ok_or_else(|| script_error("No player is loaded"))
+}
+
+fn register_player(engine: &mut Engine) {
+    macro_rules! resource {
+        ($get:literal, $get_max:literal, $set:literal, $field:ident, $max:ident) => {
+            engine.register_fn($get, || -> ScriptResult<INT> {
+                Ok(player()?.chr_ins.module_container.data.$field as INT)
+            });
+            engine.register_fn($get_max, || -> ScriptResult<INT> {
+                Ok(player()?.chr_ins.module_container.data.$max as INT)
+            });
+            engine.register_fn($set, |value: INT| -> ScriptResult<()> {
+                check_online_guard().map_err(script_error)?;
+                let data = &mut player()?.chr_ins.module_container.data;
+                set(&mut data.$field, value.min(data.$max as INT).max(0) as i32);
+                Ok(())
+            });
+        };
+    }
+
+    resource!("player_hp", "player_max_hp", "set_player_hp", hp, max_hp);
+    resource!("player_fp", "player_max_fp", "set_player_fp", fp, max_fp);
+    resource!(
+        "player_stamina",
+        "player_max_stamina",
+        "set_player_stamina",
+        stamina,
+        max_stamina
+    );
+
+    engine.register_fn("player_position", || -> ScriptResult<rhai::Array> {
+        let HavokPosition(x, y, z, _) = player()?.chr_ins.module_container.physics.position;
+        Ok(vec![
+            Dynamic::from_float(x as _),
+            Dynamic::from_float(y as _),
+            Dynamic::from_float(z as _),
+        ])
+    });
+
+    engine.register_fn(
+        "teleport",
+        |x: rhai::FLOAT, y: rhai::FLOAT, z: rhai::FLOAT| -> ScriptResult<()> {
+            let chr_ins = &mut player()?.chr_ins;
+            let mut point = RespawnPoint::from_chr_ins(chr_ins);
+            point.position = HavokPosition::from_xyz(x as f32, y as f32, z as f32);
+            point.teleport(chr_ins).map_err(script_error)
+        },
+    );
+}
+
+fn to_dynamic(value: ParamValue) -> Dynamic {
+    match value {
+        ParamValue::Signed(v) => Dynamic::from_int(v as INT),
+        ParamValue::Unsigned(v) => Dynamic::from_int(v as INT),
+        ParamValue::Float(v) => Dynamic::from_float(v as _),
+        ParamValue::String(v) => v.into(),
+        ParamValue::Array(values) => {
+            Dynamic::from_array(values.into_iter().map(to_dynamic).collect())
+        }
+    }
+}
+
+/// Converts a script value to the same kind of value as the field's current
+/// one.
+fn from_dynamic(current: &ParamValue, value: &Dynamic) -> Option<ParamValue> {
+    let int = || value.as_int().ok();
+    match current {
+        ParamValue::Signed(_) => int()?.try_into().ok().map(ParamValue::Signed),
+        ParamValue::Unsigned(_) => int()?.try_into().ok().map(ParamValue::Unsigned),
+        ParamValue::Float(_) => value
+            .as_float()
+            .ok()
+            .or_else(|| int().map(|v| v as _))
+            .map(|v| ParamValue::Float(v as f32)),
+        _ => None,
+    }
+}
+
+/// Reads a field of a loaded row, along with its definition.
+fn read_param_field(
+    state: &HostState,
+    param_name: &str,
+    row_id: INT,
+    field_name: &str,
+) -> ScriptResult<(ParamField, ParamValue)> {
+    let row_id =
+        u32::try_from(row_id).map_err(|_| script_error(format!("Invalid row {row_id}")))?;
+    let params = unsafe { FD4ParamRepository::instance() }.map_err(script_error)?;
+    let table = params
+        .res_rep
+        .res_cap_holder
+        .entries()
+        .find(|e| e.inner.name.to_string() == param_name)
+        .ok_or_else(|| script_error(format!("Param {param_name} is not loaded")))?
+        .table()
+        .map_err(script_error)?;
+
+    let def = state
+        .defs
+        .get(table.name())
+        .ok_or_else(|| script_error(format!("No paramdef for {}", table.name())))?;
+    let row = table
+        .row(row_id)
+        .map_err(script_error)?
+        .ok_or_else(|| script_error(format!("Param {param_name} has no row {row_id}")))?;
+    let field = def
+        .field(field_name)
+        .ok_or_else(|| script_error(format!("{} has no field {field_name}", table.name())))?;
+    let value = field
+        .read(row)
+        .ok_or_else(|| script_error(format!("{field_name} is past the end of the row")))?;
+
+    Ok((field.clone(), value))
+}
+
+fn register_params(engine: &mut Engine, state: &Rc<RefCell<HostState>>) {
+    let get_state = state.clone();
+    engine.register_fn(
+        "get_param",
+        move |param_name: &str, row_id: INT, field_name: &str| -> ScriptResult<Dynamic> {
+            let state = get_state.borrow();
+            let (_, value) = read_param_field(&state, param_name, row_id, field_name)?;
+            Ok(to_dynamic(value))
+        },
+    );
+
+    let set_state = state.clone();
+    engine.register_fn(
+        "set_param",
+        move |param_name: &str,
+              row_id: INT,
+              field_name: &str,
+              value: Dynamic|
+              -> ScriptResult<()> {
+            let mut state = set_state.borrow_mut();
+            let (field, current) = read_param_field(&state, param_name, row_id, field_name)?;
+            let value = from_dynamic(&current, &value)
+                .ok_or_else(|| script_error(format!("Invalid value for {field_name}")))?;
+
+            state
+                .patcher
+                .write_field(param_name, row_id as u32, &field, &value)
+                .map_err(script_error)
+        },
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ScriptHost, ScriptLimits};
+
+    #[test]
+    fn stops_runaway_scripts() {
+        let mut host = ScriptHost::new();
+        assert_eq!(host.limits(), ScriptLimits::default());
+        assert!(host.run("let x = 0; while x < 10 { x += 1; }").is_ok());
+        assert!(host.run("loop {}").is_err());
+        assert!(host.run("fn f(n) { f(n + 1) } f(0)").is_err());
+
+        host.set_limits(ScriptLimits {
+            max_operations: 10,
+            ..ScriptLimits::default()
+        });
+        assert!(host.run("let x = 0; while x < 10 { x += 1; }").is_err());
+    }
+}
@@ -0,0 +1,236 @@
+//! Validates a battery of bindings against the running game.
+//!
+//! Every check only reads memory and compares it against invariants that hold
+//! for any correct binding, like a character's HP not exceeding its max HP or
+//! a handle resolving back to the character it was read from, so no values
+//! specific to a game version are needed. After a patch, a failing check
+//! points at the binding whose layout most likely shifted.
+//!
+//! [run] should be called once the game has finished loading into the world,
+//! e.g. from a task, so the singletons being checked are initialized.
+use std::fmt;
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{
+    CSEventFlagMan, CSNetMan, CSWorldGeomMan, CSWorldSceneDrawParamManager, ChrIns, GameMan,
+    MsbRepository, WorldAreaTime, WorldChrMan,
+};
+use crate::fd4::FD4ParamRepository;
+use crate::param::EQUIP_PARAM_WEAPON_ST;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckOutcome {
+    Pass,
+    Fail(String),
+    /// The check couldn't run, e.g. because no player is loaded.
+    Skipped(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub outcome: CheckOutcome,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SelfTestReport {
+    pub results: Vec<CheckResult>,
+}
+
+impl SelfTestReport {
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, CheckOutcome::Fail(_)))
+    }
+
+    /// Whether every check that ran passed.
+    pub fn is_ok(&self) -> bool {
+        self.failures().next().is_none()
+    }
+
+    fn record(&mut self, name: &'static str, outcome: CheckOutcome) {
+        self.results.push(CheckResult { name, outcome });
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            match &result.outcome {
+                CheckOutcome::Pass => writeln!(f, "[PASS] {}", result.name)?,
+                CheckOutcome::Fail(reason) => writeln!(f, "[FAIL] {}: {reason}", result.name)?,
+                CheckOutcome::Skipped(reason) => writeln!(f, "[SKIP] {}: {reason}", result.name)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn instance_outcome<T: FromStatic>() -> CheckOutcome {
+    match unsafe { T::instance() } {
+        Ok(_) => CheckOutcome::Pass,
+        Err(e) => CheckOutcome::Fail(e.to_string()),
+    }
+}
+
+/// Runs a check, reporting a missing instance as a failure.
+fn check(f: impl FnOnce() -> Result<CheckOutcome, InstanceError>) -> CheckOutcome {
+    f().unwrap_or_else(|e| CheckOutcome::Fail(e.to_string()))
+}
+
+/// Runs every check and returns their results.
+pub fn run() -> SelfTestReport {
+    let mut report = SelfTestReport::default();
+
+    report.record("CSEventFlagMan", instance_outcome::<CSEventFlagMan>());
+    report.record("CSNetMan", instance_outcome::<CSNetMan>());
+    report.record("CSWorldGeomMan", instance_outcome::<CSWorldGeomMan>());
+    report.record(
+        "CSWorldSceneDrawParamManager",
+        instance_outcome::<CSWorldSceneDrawParamManager>(),
+    );
+    report.record(
+        "FD4ParamRepository",
+        instance_outcome::<FD4ParamRepository>(),
+    );
+    report.record("GameMan", instance_outcome::<GameMan>());
+    report.record("MsbRepository", instance_outcome::<MsbRepository>());
+    report.record("WorldAreaTime", instance_outcome::<WorldAreaTime>());
+    report.record("WorldChrMan", instance_outcome::<WorldChrMan>());
+
+    report.record("Main player stats", check(check_main_player));
+    report.record("Character handles", check(check_character_handles));
+    report.record("Param rows", check(check_param_rows));
+    report.record("Event flag blocks", check(check_event_flag_blocks));
+    report.record("Geometry blocks", check(check_geometry_blocks));
+
+    report
+}
+
+fn check_main_player() -> Result<CheckOutcome, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let Some(player) = world_chr_man.main_player.as_ref() else {
+        return Ok(CheckOutcome::Skipped("no player is loaded".to_string()));
+    };
+
+    let modules = &player.chr_ins.module_container;
+    let data = &modules.data;
+    if !(0..=data.max_hp).contains(&data.hp) {
+        return Ok(CheckOutcome::Fail(format!(
+            "HP {} outside of 0..={}",
+            data.hp, data.max_hp
+        )));
+    }
+    if !(0..=data.max_fp).contains(&data.fp) {
+        return Ok(CheckOutcome::Fail(format!(
+            "FP {} outside of 0..={}",
+            data.fp, data.max_fp
+        )));
+    }
+    if !(0..=data.max_stamina).contains(&data.stamina) {
+        return Ok(CheckOutcome::Fail(format!(
+            "stamina {} outside of 0..={}",
+            data.stamina, data.max_stamina
+        )));
+    }
+
+    let position = modules.physics.position;
+    if ![position.0, position.1, position.2]
+        .iter()
+        .all(|c| c.is_finite())
+    {
+        return Ok(CheckOutcome::Fail(format!(
+            "position {position:?} isn't finite"
+        )));
+    }
+
+    Ok(CheckOutcome::Pass)
+}
+
+fn check_character_handles() -> Result<CheckOutcome, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let characters = world_chr_man
+        .chr_sets
+        .iter()
+        .flatten()
+        .flat_map(|chr_set| chr_set.characters())
+        .map(|chr_ins| (chr_ins.field_ins_handle, chr_ins as *const ChrIns))
+        .collect::<Vec<_>>();
+
+    if characters.is_empty() {
+        return Ok(CheckOutcome::Skipped(
+            "no characters are loaded".to_string(),
+        ));
+    }
+
+    for (handle, expected) in characters {
+        let resolved = world_chr_man
+            .chr_ins_by_handle(&handle)
+            .map(|chr_ins| chr_ins as *const ChrIns);
+        if resolved != Some(expected) {
+            return Ok(CheckOutcome::Fail(format!(
+                "{handle} doesn't resolve to the character it was read from"
+            )));
+        }
+    }
+
+    Ok(CheckOutcome::Pass)
+}
+
+fn check_param_rows() -> Result<CheckOutcome, InstanceError> {
+    let param_repository = unsafe { FD4ParamRepository::instance() }?;
+    let row_ids = param_repository.row_ids::<EQUIP_PARAM_WEAPON_ST>();
+    if row_ids.is_empty() {
+        return Ok(CheckOutcome::Fail(
+            "EquipParamWeapon has no rows".to_string(),
+        ));
+    }
+
+    if let Some(id) = row_ids.iter().find(|id| {
+        param_repository
+            .get::<EQUIP_PARAM_WEAPON_ST>(**id)
+            .is_none()
+    }) {
+        return Ok(CheckOutcome::Fail(format!(
+            "EquipParamWeapon row {id} is listed but can't be read"
+        )));
+    }
+
+    Ok(CheckOutcome::Pass)
+}
+
+fn check_event_flag_blocks() -> Result<CheckOutcome, InstanceError> {
+    let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+    let virtual_memory_flag = &event_flag_man.virtual_memory_flag;
+    if virtual_memory_flag.flag_block_descriptors.is_empty() {
+        return Ok(CheckOutcome::Fail(
+            "no flag blocks are registered".to_string(),
+        ));
+    }
+    if virtual_memory_flag.event_flag_divisor == 0 {
+        return Ok(CheckOutcome::Fail("event flag divisor is zero".to_string()));
+    }
+
+    Ok(CheckOutcome::Pass)
+}
+
+fn check_geometry_blocks() -> Result<CheckOutcome, InstanceError> {
+    let world_geom_man = unsafe { CSWorldGeomMan::instance() }?;
+    if world_geom_man.blocks.is_empty() {
+        return Ok(CheckOutcome::Skipped("no blocks are loaded".to_string()));
+    }
+
+    for block in world_geom_man.blocks.iter() {
+        if block.data.block_id != block.block_id {
+            return Ok(CheckOutcome::Fail(format!(
+                "block {} holds the data for block {}",
+                block.block_id, block.data.block_id
+            )));
+        }
+    }
+
+    Ok(CheckOutcome::Pass)
+}
@@ -0,0 +1,127 @@
+//! Character streaming distance overrides and load statistics.
+//!
+//! Every character has its own deactivation and fade-out distances, and the
+//! game honors per-character overrides for both, which is what the radius
+//! settings here write to. Call [apply_chr_streaming_distances] every frame,
+//! e.g. from a recurring task, so characters that get loaded in later pick
+//! the settings up as well.
+use std::sync::Mutex;
+
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{ChrLoadStatus, WorldChrMan};
+use crate::util::audited::set;
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+/// Distances in meters at which characters stop being simulated and start
+/// fading out respectively.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ChrStreamingDistances {
+    pub deactivation: f32,
+    pub fade_out_start: f32,
+}
+
+/// Snapshot of the load state of every character slot in [WorldChrMan].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StreamingStats {
+    /// Characters that are loaded and being updated.
+    pub active: usize,
+    /// Characters that are still being initialized, either locally or over
+    /// the network.
+    pub pending: usize,
+    /// Characters that are in the process of being unloaded.
+    pub unloading: usize,
+    /// Total amount of character slots across all ChrSets.
+    pub capacity: usize,
+}
+
+struct DistanceState {
+    distances: Option<ChrStreamingDistances>,
+    /// Set when an override was removed and the loaded characters still
+    /// have to be reset.
+    pending_reset: bool,
+}
+
+static STATE: Mutex<DistanceState> = Mutex::new(DistanceState {
+    distances: None,
+    pending_reset: false,
+});
+
+/// Overrides the streaming distances of every character, or restores the
+/// defaults from their params when passed None.
+pub fn set_chr_streaming_distances(distances: Option<ChrStreamingDistances>) {
+    let mut state = STATE.lock().unwrap();
+    state.pending_reset |= state.distances.is_some() && distances.is_none();
+    state.distances = distances;
+}
+
+/// The currently configured streaming distance override, if any.
+pub fn chr_streaming_distances() -> Option<ChrStreamingDistances> {
+    STATE.lock().unwrap().distances
+}
+
+/// Writes the configured streaming distances to every loaded character.
+pub fn apply_chr_streaming_distances() -> Result<(), OnlineGuardError> {
+    let mut state = STATE.lock().unwrap();
+    if state.distances.is_none() && !state.pending_reset {
+        return Ok(());
+    }
+
+    check_online_guard()?;
+    // Negative values disable the overrides.
+    let (deactivation, fade_out_start) = match state.distances {
+        Some(d) => (
+            d.deactivation * d.deactivation,
+            d.fade_out_start * d.fade_out_start,
+        ),
+        None => (-1.0, -1.0),
+    };
+
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    for chr_ins in world_chr_man
+        .chr_sets
+        .iter()
+        .flatten()
+        .flat_map(|chr_set| chr_set.characters())
+    {
+        set(
+            &mut chr_ins.squared_deactivation_distance_override,
+            deactivation,
+        );
+        set(
+            &mut chr_ins.squared_fade_out_start_distance_override,
+            fade_out_start,
+        );
+    }
+
+    state.pending_reset = false;
+    Ok(())
+}
+
+/// Counts the character slots of every ChrSet by their load status.
+pub fn streaming_stats() -> Result<StreamingStats, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+
+    let mut stats = StreamingStats::default();
+    for chr_set in world_chr_man.chr_sets.iter().flatten() {
+        stats.capacity += chr_set.capacity as usize;
+
+        for i in 0..chr_set.capacity as usize {
+            let entry = unsafe { chr_set.entries.add(i).as_ref() };
+            if entry.chr_ins.is_none() {
+                continue;
+            }
+
+            match entry.chr_load_status {
+                ChrLoadStatus::Active => stats.active += 1,
+                ChrLoadStatus::Initializing
+                | ChrLoadStatus::NetworkInitializing
+                | ChrLoadStatus::ReadyForActivation => stats.pending += 1,
+                ChrLoadStatus::Unloading => stats.unloading += 1,
+                ChrLoadStatus::Unloaded => {}
+            }
+        }
+    }
+
+    Ok(stats)
+}
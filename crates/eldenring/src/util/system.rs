@@ -18,6 +18,8 @@ pub enum SystemInitError {
     Timeout,
     #[error("Could not translate RVA to VA")]
     InvalidRva,
+    #[error(transparent)]
+    Version(#[from] crate::Error),
 }
 
 /// Wait for the system to finish initializing by waiting a global hInstance to be populated for CSWindow.
@@ -25,7 +27,7 @@ pub enum SystemInitError {
 pub fn wait_for_system_init(module: &Program, timeout: Duration) -> Result<(), SystemInitError> {
     if std::ptr::eq(GLOBAL_HINSTANCE.load(Ordering::Relaxed), 0x0 as _) {
         let va = module
-            .rva_to_va(rva::get().global_hinstance)
+            .rva_to_va(rva::try_get()?.global_hinstance)
             .map_err(|_| SystemInitError::InvalidRva)?;
 
         GLOBAL_HINSTANCE.store(va as _, Ordering::Relaxed);
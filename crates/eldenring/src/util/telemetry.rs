@@ -0,0 +1,303 @@
+//! Game events pushed to WebSocket clients as JSON, for stream widgets and
+//! chat integrations. Only available with the `telemetry` feature.
+//!
+//! [start_telemetry_server] accepts WebSocket connections on a loopback
+//! port, and every event is sent to all connected clients as a single text
+//! message like `{"event":"level_up","level":12}`. Every client gets its own
+//! thread, so a client that's slow to handshake or to receive only holds up
+//! itself. Browser pages can only connect if they're served from the local
+//! machine or their origin was allowed with [allow_telemetry_origin].
+//!
+//! [update_telemetry] has to be called every frame to pick up:
+//!
+//! - `death`, the first frame the player's HP reaches zero.
+//! - `level_up`, when the player's level goes up.
+//! - `boss_defeated`, when one of the flags passed to [watch_boss_flags] gets
+//!   set. With the `event-flag-names` feature, `flags_in_category("Boss")`
+//!   can be used to look them up.
+use std::io;
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, SyncSender, TrySendError};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use shared::{FromStatic, InstanceError};
+use tungstenite::Message;
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::{StatusCode, header::ORIGIN};
+
+use crate::cs::{CSEventFlagMan, WorldChrMan};
+use crate::position::HavokPosition;
+
+/// How long a client gets to complete the WebSocket handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long sending a single event may take before the client is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Events held back for a client that isn't keeping up. Any more are dropped
+/// for that client.
+const CLIENT_BACKLOG: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TelemetryEvent {
+    Death { position: HavokPosition },
+    LevelUp { level: u32 },
+    BossDefeated { flag: u32 },
+}
+
+impl TelemetryEvent {
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Death { position } => {
+                let HavokPosition(x, y, z, _) = *position;
+                json!({ "event": "death", "position": [x, y, z] })
+            }
+            Self::LevelUp { level } => json!({ "event": "level_up", "level": level }),
+            Self::BossDefeated { flag } => json!({ "event": "boss_defeated", "flag": flag }),
+        }
+    }
+}
+
+/// Event queues of the connected clients.
+static CLIENTS: Mutex<Vec<SyncSender<String>>> = Mutex::new(Vec::new());
+static ALLOWED_ORIGINS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// Lets browser pages from `origin`, ex. `https://example.com`, connect.
+/// Pages served from `localhost` or a loopback address are always allowed,
+/// and so are clients that don't send an origin, like most non-browser
+/// tools. Local files opened in a browser send the origin `null`.
+pub fn allow_telemetry_origin(origin: impl Into<String>) {
+    ALLOWED_ORIGINS.write().unwrap().push(origin.into());
+}
+
+fn is_allowed_origin(origin: &str, allowed: &[String]) -> bool {
+    let authority = origin
+        .split_once("://")
+        .map_or("", |(_, authority)| authority);
+    let host = match authority.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next(),
+        None => authority.split(':').next(),
+    };
+
+    matches!(host, Some("localhost" | "127.0.0.1" | "::1"))
+        || allowed
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(origin))
+}
+
+fn check_origin(request: &Request, response: Response) -> Result<Response, ErrorResponse> {
+    let Some(origin) = request.headers().get(ORIGIN) else {
+        return Ok(response);
+    };
+
+    let origin = origin.to_str().unwrap_or_default();
+    if is_allowed_origin(origin, &ALLOWED_ORIGINS.read().unwrap()) {
+        return Ok(response);
+    }
+
+    let mut error = ErrorResponse::new(Some(format!("Origin {origin} is not allowed")));
+    *error.status_mut() = StatusCode::FORBIDDEN;
+    Err(error)
+}
+
+/// Handshakes with a single client, then sends it events until it
+/// disconnects or sending to it times out.
+fn serve_client(stream: TcpStream) {
+    let timeouts = stream
+        .set_read_timeout(Some(HANDSHAKE_TIMEOUT))
+        .and_then(|_| stream.set_write_timeout(Some(WRITE_TIMEOUT)));
+    if let Err(e) = timeouts {
+        tracing::warn!("Could not set up telemetry client: {e}");
+        return;
+    }
+
+    let mut client = match tungstenite::accept_hdr(stream, check_origin) {
+        Ok(client) => client,
+        Err(e) => {
+            tracing::warn!("Could not accept telemetry client: {e}");
+            return;
+        }
+    };
+
+    let (sender, receiver) = mpsc::sync_channel(CLIENT_BACKLOG);
+    CLIENTS.lock().unwrap().push(sender);
+    for event in receiver {
+        if client.send(Message::text(event)).is_err() {
+            return;
+        }
+    }
+}
+
+/// Starts accepting WebSocket clients on `addr`, which has to be a loopback
+/// address like `127.0.0.1:0`. Returns the address that was bound.
+pub fn start_telemetry_server(addr: impl ToSocketAddrs) -> io::Result<SocketAddr> {
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    if !local_addr.ip().is_loopback() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the telemetry server only listens on loopback addresses",
+        ));
+    }
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            match stream {
+                Ok(stream) => {
+                    std::thread::spawn(move || serve_client(stream));
+                }
+                Err(e) => tracing::warn!("Could not accept telemetry client: {e}"),
+            }
+        }
+    });
+
+    Ok(local_addr)
+}
+
+/// Sends `event` to every connected client. Does nothing if the server
+/// wasn't started.
+pub fn publish(event: TelemetryEvent) {
+    let mut clients = CLIENTS.lock().unwrap();
+    if clients.is_empty() {
+        return;
+    }
+
+    let event = event.to_json().to_string();
+    clients.retain(|client| match client.try_send(event.clone()) {
+        Ok(()) | Err(TrySendError::Full(_)) => true,
+        Err(TrySendError::Disconnected(_)) => false,
+    });
+}
+
+#[derive(Default)]
+struct TelemetryTracker {
+    hp: Option<i32>,
+    level: Option<u32>,
+    /// Watched boss flags and whether they were set last frame.
+    boss_flags: Vec<(u32, Option<bool>)>,
+}
+
+impl TelemetryTracker {
+    fn observe_player(
+        &mut self,
+        hp: i32,
+        level: u32,
+        position: HavokPosition,
+    ) -> Vec<TelemetryEvent> {
+        let mut events = Vec::new();
+        if self.hp.is_some_and(|previous| previous > 0) && hp <= 0 {
+            events.push(TelemetryEvent::Death { position });
+        }
+        if self.level.is_some_and(|previous| level > previous) {
+            events.push(TelemetryEvent::LevelUp { level });
+        }
+
+        self.hp = Some(hp);
+        self.level = Some(level);
+        events
+    }
+
+    fn observe_flags(&mut self, get_flag: impl Fn(u32) -> bool) -> Vec<TelemetryEvent> {
+        let mut events = Vec::new();
+        for (flag, was_set) in &mut self.boss_flags {
+            let set = get_flag(*flag);
+            if *was_set == Some(false) && set {
+                events.push(TelemetryEvent::BossDefeated { flag: *flag });
+            }
+            *was_set = Some(set);
+        }
+        events
+    }
+}
+
+static TRACKER: Mutex<Option<TelemetryTracker>> = Mutex::new(None);
+
+/// Publishes `boss_defeated` whenever one of `flags` gets set, replacing the
+/// previously watched flags. Flags that are already set when they're first
+/// read don't count.
+pub fn watch_boss_flags(flags: impl IntoIterator<Item = u32>) {
+    let mut tracker = TRACKER.lock().unwrap();
+    tracker.get_or_insert_with(Default::default).boss_flags =
+        flags.into_iter().map(|flag| (flag, None)).collect();
+}
+
+/// Publishes the events that happened since the last call. This needs to be
+/// called every frame.
+pub fn update_telemetry() -> Result<(), InstanceError> {
+    let mut tracker = TRACKER.lock().unwrap();
+    let tracker = tracker.get_or_insert_with(Default::default);
+
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    let mut events = match world_chr_man.main_player.as_ref() {
+        Some(player) => {
+            let chr_ins = &player.chr_ins;
+            tracker.observe_player(
+                chr_ins.module_container.data.hp,
+                player.player_game_data.level,
+                chr_ins.module_container.physics.position,
+            )
+        }
+        None => {
+            // Don't report the reloaded player's stats as changes.
+            tracker.hp = None;
+            tracker.level = None;
+            Vec::new()
+        }
+    };
+
+    if !tracker.boss_flags.is_empty() {
+        let event_flag_man = unsafe { CSEventFlagMan::instance() }?;
+        let flags = &event_flag_man.virtual_memory_flag;
+        events.extend(tracker.observe_flags(|flag| flags.get_flag(flag)));
+    }
+
+    for event in events {
+        publish(event);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{TelemetryEvent, TelemetryTracker, is_allowed_origin};
+    use crate::position::HavokPosition;
+
+    #[test]
+    fn observes_deaths_level_ups_and_bosses() {
+        let position = HavokPosition::from_xyz(1.0, 2.0, 3.0);
+        let mut tracker = TelemetryTracker::default();
+        assert_eq!(tracker.observe_player(0, 10, position), []);
+        assert_eq!(tracker.observe_player(100, 10, position), []);
+        assert_eq!(
+            tracker.observe_player(0, 11, position),
+            [
+                TelemetryEvent::Death { position },
+                TelemetryEvent::LevelUp { level: 11 }
+            ]
+        );
+
+        tracker.boss_flags = vec![(1, None), (2, None)];
+        assert_eq!(tracker.observe_flags(|flag| flag == 1), []);
+        assert_eq!(
+            tracker.observe_flags(|_| true),
+            [TelemetryEvent::BossDefeated { flag: 2 }]
+        );
+    }
+
+    #[test]
+    fn allows_local_and_listed_origins() {
+        let allowed = ["https://example.com".to_string()];
+        for origin in [
+            "http://localhost",
+            "http://localhost:8080",
+            "http://127.0.0.1:3000",
+            "http://[::1]:3000",
+            "https://Example.com",
+        ] {
+            assert!(is_allowed_origin(origin, &allowed), "{origin}");
+        }
+        for origin in ["https://localhost.example.com", "https://other.com", "null"] {
+            assert!(!is_allowed_origin(origin, &allowed), "{origin}");
+        }
+    }
+}
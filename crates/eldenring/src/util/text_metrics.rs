@@ -0,0 +1,160 @@
+//! Estimated on-screen widths of text, ex. to size custom menu entries.
+//!
+//! [TextMetrics] estimates widths from how wide each kind of
+//! character usually is: CJK characters, kana and Hangul take up a full em,
+//! Latin, Cyrillic and the like around half of one. Advances measured from
+//! the actual game fonts can be set per character for exact results in a
+//! given locale.
+use std::collections::HashMap;
+use std::fmt::Display;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GlyphClass {
+    /// Combining marks and zero width spaces.
+    ZeroWidth,
+    Narrow,
+    /// Characters drawn a full em wide.
+    Wide,
+}
+
+impl GlyphClass {
+    pub fn of(c: char) -> Self {
+        match c as u32 {
+            0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => GlyphClass::ZeroWidth,
+            0x1100..=0x115F
+            | 0x2E80..=0x303E
+            | 0x3041..=0x33FF
+            | 0x3400..=0x4DBF
+            | 0x4E00..=0x9FFF
+            | 0xA000..=0xA4CF
+            | 0xAC00..=0xD7A3
+            | 0xF900..=0xFAFF
+            | 0xFE30..=0xFE4F
+            | 0xFF00..=0xFF60
+            | 0xFFE0..=0xFFE6
+            | 0x20000..=0x3FFFD => GlyphClass::Wide,
+            _ => GlyphClass::Narrow,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TextSize {
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Widths of characters relative to the font size.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TextMetrics {
+    pub narrow_advance: f32,
+    pub wide_advance: f32,
+    pub line_height: f32,
+    advances: HashMap<char, f32>,
+}
+
+impl Default for TextMetrics {
+    fn default() -> Self {
+        Self {
+            narrow_advance: 0.55,
+            wide_advance: 1.0,
+            line_height: 1.25,
+            advances: HashMap::new(),
+        }
+    }
+}
+
+impl TextMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the advance of a single character, relative to the font size.
+    pub fn with_advance(mut self, c: char, advance: f32) -> Self {
+        self.advances.insert(c, advance);
+        self
+    }
+
+    pub fn advance(&self, c: char) -> f32 {
+        if let Some(advance) = self.advances.get(&c) {
+            return *advance;
+        }
+
+        match GlyphClass::of(c) {
+            GlyphClass::ZeroWidth => 0.0,
+            GlyphClass::Narrow => self.narrow_advance,
+            GlyphClass::Wide => self.wide_advance,
+        }
+    }
+
+    /// Size of `text` drawn at `font_size`, with every line break starting a
+    /// new line.
+    pub fn measure(&self, text: &str, font_size: f32) -> TextSize {
+        let lines = text.split('\n');
+        let (count, widest) = lines.fold((0, 0.0f32), |(count, widest), line| {
+            (count + 1, widest.max(self.line_width(line)))
+        });
+
+        TextSize {
+            width: widest * font_size,
+            height: count as f32 * self.line_height * font_size,
+        }
+    }
+
+    /// Like [Self::measure] for game strings, ex. a [DLString](crate::dltx::DLString).
+    pub fn measure_display(&self, text: &impl Display, font_size: f32) -> TextSize {
+        self.measure(&text.to_string(), font_size)
+    }
+
+    /// Shortens a single line of text to fit into `max_width`, ending it with
+    /// `ellipsis` if anything had to be cut.
+    pub fn truncate(&self, text: &str, font_size: f32, max_width: f32, ellipsis: &str) -> String {
+        if self.line_width(text) * font_size <= max_width {
+            return text.to_string();
+        }
+
+        let budget = max_width / font_size - self.line_width(ellipsis);
+        let mut width = 0.0;
+        let mut truncated = String::new();
+        for c in text.chars() {
+            width += self.advance(c);
+            if width > budget {
+                break;
+            }
+            truncated.push(c);
+        }
+
+        truncated.push_str(ellipsis);
+        truncated
+    }
+
+    fn line_width(&self, line: &str) -> f32 {
+        line.chars().map(|c| self.advance(c)).sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GlyphClass, TextMetrics};
+
+    #[test]
+    fn classifies_scripts() {
+        assert_eq!(GlyphClass::of('A'), GlyphClass::Narrow);
+        assert_eq!(GlyphClass::of('Ж'), GlyphClass::Narrow);
+        assert_eq!(GlyphClass::of('褪'), GlyphClass::Wide);
+        assert_eq!(GlyphClass::of('ア'), GlyphClass::Wide);
+        assert_eq!(GlyphClass::of('한'), GlyphClass::Wide);
+        assert_eq!(GlyphClass::of('\u{0301}'), GlyphClass::ZeroWidth);
+    }
+
+    #[test]
+    fn measures_and_truncates() {
+        let metrics = TextMetrics::new().with_advance('i', 0.25);
+        let size = metrics.measure("褪せ人\nii", 20.0);
+        assert_eq!(size.width, 60.0);
+        assert_eq!(size.height, 50.0);
+
+        assert_eq!(metrics.truncate("褪せ人", 20.0, 60.0, "…"), "褪せ人");
+        assert_eq!(metrics.truncate("褪せ人です", 20.0, 60.0, "…"), "褪せ…");
+    }
+}
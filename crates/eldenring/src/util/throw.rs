@@ -0,0 +1,63 @@
+//! The state of the throws (grabs, critical hits and other paired
+//! animations) characters are part of, as tracked by their
+//! [CSChrThrowModule].
+//!
+//! This covers the state of a throw and the character on the other end of
+//! it.
+use shared::{FromStatic, InstanceError};
+
+use crate::cs::{CSChrThrowModule, ChrIns, FieldInsHandle, ThrowNodeState, WorldChrMan};
+use crate::util::online_guard::{OnlineGuardError, check_online_guard};
+
+/// A character's part in a throw.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ThrowInfo {
+    pub chr: FieldInsHandle,
+    /// The character on the other end of the throw.
+    pub partner: FieldInsHandle,
+    pub state: ThrowNodeState,
+    pub escape_transition: bool,
+    pub death_transition: bool,
+}
+
+impl ThrowInfo {
+    pub fn is_attacker(&self) -> bool {
+        matches!(
+            self.state,
+            ThrowNodeState::InThrowAttacker | ThrowNodeState::DeathAttacker
+        )
+    }
+}
+
+/// Reads the throw a character is part of, if any.
+pub fn throw_info(chr_ins: &ChrIns) -> Option<ThrowInfo> {
+    let throw = &chr_ins.module_container.throw;
+    Some(ThrowInfo {
+        chr: chr_ins.field_ins_handle,
+        partner: throw.throw_node.super_pair_anim_node.forwarding_recipient,
+        state: throw.throw_node.state()?,
+        escape_transition: throw.flags.escape_transition(),
+        death_transition: throw.flags.death_transition(),
+    })
+}
+
+/// Lists every loaded character that's currently part of a throw. Both sides
+/// of a throw are listed separately.
+pub fn active_throws() -> Result<Vec<ThrowInfo>, InstanceError> {
+    let world_chr_man = unsafe { WorldChrMan::instance() }?;
+    Ok(world_chr_man
+        .chr_sets
+        .iter()
+        .flatten()
+        .flat_map(|chr_set| chr_set.characters())
+        .filter_map(|chr_ins| throw_info(chr_ins))
+        .collect())
+}
+
+/// Sets the flag the attacker's animation sets to let the target escape the
+/// throw, as if the animation had reached that point.
+pub fn request_throw_escape(throw: &mut CSChrThrowModule) -> Result<(), OnlineGuardError> {
+    check_online_guard()?;
+    throw.flags.set_escape_transition(true);
+    Ok(())
+}
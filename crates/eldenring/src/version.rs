@@ -0,0 +1,199 @@
+//! The version of the running executable.
+//!
+//! The bindings in this crate have a fixed layout, mapped against the
+//! versions [GameVersion] knows about. On any other version they may read
+//! the wrong fields, so mods should check [GameVersion::is_supported] before
+//! relying on them.
+//!
+//! Regional releases of the same patch have different product versions, ex.
+//! 2.6.1.0 worldwide and 2.6.1.1 in Japan, so releases are compared by their
+//! [PatchVersion] and told apart by their [Region].
+use std::fmt;
+use std::sync::LazyLock;
+
+use pelite::pe64::Pe;
+use shared::Program;
+
+const LANG_ID_EN: u16 = 0x0009;
+const LANG_ID_JP: u16 = 0x0011;
+
+const WW_2_6_1: ProductVersion = ProductVersion::new(2, 6, 1, 0);
+const JP_2_6_1: ProductVersion = ProductVersion::new(2, 6, 1, 1);
+
+/// The product version from an executable's version resource.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProductVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+    pub build: u16,
+}
+
+impl ProductVersion {
+    pub const fn new(major: u16, minor: u16, patch: u16, build: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            build,
+        }
+    }
+}
+
+impl fmt::Display for ProductVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.patch, self.build
+        )
+    }
+}
+
+/// The patch a release belongs to, which is the product version without its
+/// build number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PatchVersion {
+    pub major: u16,
+    pub minor: u16,
+    pub patch: u16,
+}
+
+impl PatchVersion {
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl From<ProductVersion> for PatchVersion {
+    fn from(version: ProductVersion) -> Self {
+        Self::new(version.major, version.minor, version.patch)
+    }
+}
+
+impl fmt::Display for PatchVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Region {
+    Worldwide,
+    Japan,
+}
+
+/// A release of the game. Releases aren't ordered themselves, compare their
+/// [GameVersion::patch_version] instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GameVersion {
+    /// The worldwide release of 2.6.1.
+    Ww261,
+    /// The Japanese release of 2.6.1, versioned 2.6.1.1.
+    Jp2611,
+    /// Any other release, which this crate doesn't support.
+    Unsupported(ProductVersion),
+}
+
+impl GameVersion {
+    fn from_metadata(product: &str, lang_id: u16, version: ProductVersion) -> Self {
+        match (product, lang_id, version) {
+            ("ELDEN RING™", LANG_ID_EN, WW_2_6_1) => Self::Ww261,
+            ("ELDEN RING", LANG_ID_JP, JP_2_6_1) => Self::Jp2611,
+            _ => Self::Unsupported(version),
+        }
+    }
+
+    /// Reads the version resource of `program`. Returns None if it doesn't
+    /// have one.
+    pub fn detect<'a>(program: impl Pe<'a>) -> Option<Self> {
+        let resources = program.resources().ok()?;
+        let info = resources.version_info().ok()?;
+
+        let product_version = info.fixed()?.dwProductVersion;
+        let version = ProductVersion::new(
+            product_version.Major,
+            product_version.Minor,
+            product_version.Patch,
+            product_version.Build,
+        );
+
+        let language = *info.translation().first()?;
+        let mut product_name: Option<String> = None;
+        info.strings(language, |k, v| {
+            if k == "ProductName" {
+                product_name = Some(v.to_string());
+            }
+        });
+
+        let product = product_name.unwrap_or_default();
+        let lang_id_base = language.lang_id & 0x03FF;
+        Some(Self::from_metadata(&product, lang_id_base, version))
+    }
+
+    pub fn product_version(&self) -> ProductVersion {
+        match self {
+            Self::Ww261 => WW_2_6_1,
+            Self::Jp2611 => JP_2_6_1,
+            Self::Unsupported(version) => *version,
+        }
+    }
+
+    pub fn patch_version(&self) -> PatchVersion {
+        self.product_version().into()
+    }
+
+    /// The region of the release, None if it's unsupported.
+    pub fn region(&self) -> Option<Region> {
+        match self {
+            Self::Ww261 => Some(Region::Worldwide),
+            Self::Jp2611 => Some(Region::Japan),
+            Self::Unsupported(_) => None,
+        }
+    }
+
+    pub fn is_supported(&self) -> bool {
+        !matches!(self, Self::Unsupported(_))
+    }
+}
+
+/// The version of the running executable, None if it doesn't have a
+/// version resource.
+pub fn game_version() -> Option<GameVersion> {
+    static VERSION: LazyLock<Option<GameVersion>> =
+        LazyLock::new(|| GameVersion::detect(Program::current()));
+
+    if Program::current_is_fixture() {
+        return GameVersion::detect(Program::current());
+    }
+    *VERSION
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GameVersion, PatchVersion, ProductVersion, Region};
+
+    #[test]
+    fn compares_releases_by_patch() {
+        let v1_10 = GameVersion::Unsupported(ProductVersion::new(1, 10, 0, 0));
+        let v2_6_2 = GameVersion::Unsupported(ProductVersion::new(2, 6, 2, 0));
+        assert!(v1_10.patch_version() < GameVersion::Ww261.patch_version());
+        assert_eq!(
+            GameVersion::Ww261.patch_version(),
+            GameVersion::Jp2611.patch_version()
+        );
+        assert_eq!(
+            GameVersion::Jp2611.patch_version(),
+            PatchVersion::new(2, 6, 1)
+        );
+        assert!(GameVersion::Jp2611.patch_version() < v2_6_2.patch_version());
+
+        assert_eq!(GameVersion::Ww261.region(), Some(Region::Worldwide));
+        assert_eq!(GameVersion::Jp2611.region(), Some(Region::Japan));
+        assert_eq!(v2_6_2.region(), None);
+    }
+}
@@ -7161,32 +7161,32 @@ impl BULLET_PARAM_ST {
 
     #[allow(clippy::identity_op)]
     pub fn emitte_pos_type(&self) -> u8 {
-        (self.bits_9a >> 1) & 0b00000111
+        (self.bits_9a >> 3) & 0b00000111
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_emitte_pos_type(&mut self, value: u8) {
-        self.bits_9a = (self.bits_9a & !(0b00000111 << 1)) | ((value & 0b00000111) << 1);
+        self.bits_9a = (self.bits_9a & !(0b00000111 << 3)) | ((value & 0b00000111) << 3);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_attack_sfx(&self) -> u8 {
-        (self.bits_9a >> 2) & 0b00000001
+        (self.bits_9a >> 6) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_attack_sfx(&mut self, value: u8) {
-        self.bits_9a = (self.bits_9a & !(0b00000001 << 2)) | ((value & 0b00000001) << 2);
+        self.bits_9a = (self.bits_9a & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_endless_hit(&self) -> u8 {
-        (self.bits_9a >> 3) & 0b00000001
+        (self.bits_9a >> 7) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_endless_hit(&mut self, value: u8) {
-        self.bits_9a = (self.bits_9a & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+        self.bits_9a = (self.bits_9a & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
     }
 
     #[allow(clippy::identity_op)]
@@ -7301,42 +7301,42 @@ impl BULLET_PARAM_ST {
 
     #[allow(clippy::identity_op)]
     pub fn is_enable_auto_homing(&self) -> u8 {
-        (self.bits_9c >> 3) & 0b00000001
+        (self.bits_9c >> 4) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_enable_auto_homing(&mut self, value: u8) {
-        self.bits_9c = (self.bits_9c & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+        self.bits_9c = (self.bits_9c & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_sync_bullet_culc_dumypoly_pos(&self) -> u8 {
-        (self.bits_9c >> 4) & 0b00000001
+        (self.bits_9c >> 5) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_sync_bullet_culc_dumypoly_pos(&mut self, value: u8) {
-        self.bits_9c = (self.bits_9c & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+        self.bits_9c = (self.bits_9c & !(0b00000001 << 5)) | ((value & 0b00000001) << 5);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_owner_override_init_angle(&self) -> u8 {
-        (self.bits_9c >> 5) & 0b00000001
+        (self.bits_9c >> 6) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_owner_override_init_angle(&mut self, value: u8) {
-        self.bits_9c = (self.bits_9c & !(0b00000001 << 5)) | ((value & 0b00000001) << 5);
+        self.bits_9c = (self.bits_9c & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_inherit_sfx_to_child(&self) -> u8 {
-        (self.bits_9c >> 6) & 0b00000001
+        (self.bits_9c >> 7) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_inherit_sfx_to_child(&mut self, value: u8) {
-        self.bits_9c = (self.bits_9c & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
+        self.bits_9c = (self.bits_9c & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
     }
 
     pub fn dark_damage_damp(&self) -> i8 {
@@ -11537,9 +11537,7 @@ pub struct DECAL_PARAM_ST {
     near_size: f32,
     far_size: f32,
     mask_speffect_id: i32,
-    bits_24: u8,
-    bits_25: u8,
-    bits_26: u8,
+    bits_24: u32,
     random_size_min: i16,
     random_size_max: i16,
     random_roll_min: f32,
@@ -11589,10 +11587,7 @@ pub struct DECAL_PARAM_ST {
     dist_thin_out_max_num: u8,
     dist_thin_out_check_num: u8,
     delay_appear_frame: i16,
-    bits_b0: u8,
-    bits_b1: u8,
-    bits_b2: u8,
-    bits_b3: u8,
+    bits_b0: u32,
     fade_in_time_sec: f32,
     thin_out_overlap_multi_radius: f32,
     thin_out_neighbor_add_radius: f32,
@@ -11684,93 +11679,102 @@ impl DECAL_PARAM_ST {
     }
 
     #[allow(clippy::identity_op)]
-    pub fn replace_texture_id_by_material(&self) -> u8 {
-        (self.bits_24 >> 1) & 0b00000001
+    pub fn replace_texture_id_by_material(&self) -> u32 {
+        (self.bits_24 >> 4) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_replace_texture_id_by_material(&mut self, value: u8) {
-        self.bits_24 = (self.bits_24 & !(0b00000001 << 1)) | ((value & 0b00000001) << 1);
+    pub fn set_replace_texture_id_by_material(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 4))
+            | ((value & 0b00000000000000000000000000000001) << 4);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn dmypoly_category(&self) -> u8 {
-        (self.bits_24 >> 2) & 0b00000011
+    pub fn dmypoly_category(&self) -> u32 {
+        (self.bits_24 >> 5) & 0b00000000000000000000000000000011
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_dmypoly_category(&mut self, value: u8) {
-        self.bits_24 = (self.bits_24 & !(0b00000011 << 2)) | ((value & 0b00000011) << 2);
+    pub fn set_dmypoly_category(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000011 << 5))
+            | ((value & 0b00000000000000000000000000000011) << 5);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn use_deferred_decal(&self) -> u8 {
-        (self.bits_25 >> 1) & 0b00000001
+    pub fn use_deferred_decal(&self) -> u32 {
+        (self.bits_24 >> 11) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_use_deferred_decal(&mut self, value: u8) {
-        self.bits_25 = (self.bits_25 & !(0b00000001 << 1)) | ((value & 0b00000001) << 1);
+    pub fn set_use_deferred_decal(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 11))
+            | ((value & 0b00000000000000000000000000000001) << 11);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn use_paint_decal(&self) -> u8 {
-        (self.bits_25 >> 2) & 0b00000001
+    pub fn use_paint_decal(&self) -> u32 {
+        (self.bits_24 >> 12) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_use_paint_decal(&mut self, value: u8) {
-        self.bits_25 = (self.bits_25 & !(0b00000001 << 2)) | ((value & 0b00000001) << 2);
+    pub fn set_use_paint_decal(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 12))
+            | ((value & 0b00000000000000000000000000000001) << 12);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn blood_type_enable(&self) -> u8 {
-        (self.bits_25 >> 3) & 0b00000001
+    pub fn blood_type_enable(&self) -> u32 {
+        (self.bits_24 >> 13) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_blood_type_enable(&mut self, value: u8) {
-        self.bits_25 = (self.bits_25 & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+    pub fn set_blood_type_enable(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 13))
+            | ((value & 0b00000000000000000000000000000001) << 13);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn b_use_normal(&self) -> u8 {
-        (self.bits_25 >> 4) & 0b00000001
+    pub fn b_use_normal(&self) -> u32 {
+        (self.bits_24 >> 14) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_b_use_normal(&mut self, value: u8) {
-        self.bits_25 = (self.bits_25 & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+    pub fn set_b_use_normal(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 14))
+            | ((value & 0b00000000000000000000000000000001) << 14);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn use_pom(&self) -> u8 {
-        (self.bits_26 >> 2) & 0b00000001
+    pub fn use_pom(&self) -> u32 {
+        (self.bits_24 >> 17) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_use_pom(&mut self, value: u8) {
-        self.bits_26 = (self.bits_26 & !(0b00000001 << 2)) | ((value & 0b00000001) << 2);
+    pub fn set_use_pom(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 17))
+            | ((value & 0b00000000000000000000000000000001) << 17);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn use_emissive(&self) -> u8 {
-        (self.bits_26 >> 3) & 0b00000001
+    pub fn use_emissive(&self) -> u32 {
+        (self.bits_24 >> 18) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_use_emissive(&mut self, value: u8) {
-        self.bits_26 = (self.bits_26 & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+    pub fn set_use_emissive(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 18))
+            | ((value & 0b00000000000000000000000000000001) << 18);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn put_vertical(&self) -> u8 {
-        (self.bits_26 >> 4) & 0b00000001
+    pub fn put_vertical(&self) -> u32 {
+        (self.bits_24 >> 19) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_put_vertical(&mut self, value: u8) {
-        self.bits_26 = (self.bits_26 & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+    pub fn set_put_vertical(&mut self, value: u32) {
+        self.bits_24 = (self.bits_24 & !(0b00000000000000000000000000000001 << 19))
+            | ((value & 0b00000000000000000000000000000001) << 19);
     }
 
     pub fn random_size_min(&self) -> i16 {
@@ -12150,63 +12154,69 @@ impl DECAL_PARAM_ST {
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_diffuse(&self) -> u8 {
-        (self.bits_b0 >> 0) & 0b00001111
+    pub fn rand_varia_diffuse(&self) -> u32 {
+        (self.bits_b0 >> 0) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_diffuse(&mut self, value: u8) {
-        self.bits_b0 = (self.bits_b0 & !(0b00001111 << 0)) | ((value & 0b00001111) << 0);
+    pub fn set_rand_varia_diffuse(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 0))
+            | ((value & 0b00000000000000000000000000001111) << 0);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_mask(&self) -> u8 {
-        (self.bits_b0 >> 1) & 0b00001111
+    pub fn rand_varia_mask(&self) -> u32 {
+        (self.bits_b0 >> 4) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_mask(&mut self, value: u8) {
-        self.bits_b0 = (self.bits_b0 & !(0b00001111 << 1)) | ((value & 0b00001111) << 1);
+    pub fn set_rand_varia_mask(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 4))
+            | ((value & 0b00000000000000000000000000001111) << 4);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_reflec(&self) -> u8 {
-        (self.bits_b1 >> 0) & 0b00001111
+    pub fn rand_varia_reflec(&self) -> u32 {
+        (self.bits_b0 >> 8) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_reflec(&mut self, value: u8) {
-        self.bits_b1 = (self.bits_b1 & !(0b00001111 << 0)) | ((value & 0b00001111) << 0);
+    pub fn set_rand_varia_reflec(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 8))
+            | ((value & 0b00000000000000000000000000001111) << 8);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_normal(&self) -> u8 {
-        (self.bits_b2 >> 0) & 0b00001111
+    pub fn rand_varia_normal(&self) -> u32 {
+        (self.bits_b0 >> 16) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_normal(&mut self, value: u8) {
-        self.bits_b2 = (self.bits_b2 & !(0b00001111 << 0)) | ((value & 0b00001111) << 0);
+    pub fn set_rand_varia_normal(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 16))
+            | ((value & 0b00000000000000000000000000001111) << 16);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_height(&self) -> u8 {
-        (self.bits_b2 >> 1) & 0b00001111
+    pub fn rand_varia_height(&self) -> u32 {
+        (self.bits_b0 >> 20) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_height(&mut self, value: u8) {
-        self.bits_b2 = (self.bits_b2 & !(0b00001111 << 1)) | ((value & 0b00001111) << 1);
+    pub fn set_rand_varia_height(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 20))
+            | ((value & 0b00000000000000000000000000001111) << 20);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn rand_varia_emissive(&self) -> u8 {
-        (self.bits_b3 >> 0) & 0b00001111
+    pub fn rand_varia_emissive(&self) -> u32 {
+        (self.bits_b0 >> 24) & 0b00000000000000000000000000001111
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_rand_varia_emissive(&mut self, value: u8) {
-        self.bits_b3 = (self.bits_b3 & !(0b00001111 << 0)) | ((value & 0b00001111) << 0);
+    pub fn set_rand_varia_emissive(&mut self, value: u32) {
+        self.bits_b0 = (self.bits_b0 & !(0b00000000000000000000000000001111 << 24))
+            | ((value & 0b00000000000000000000000000001111) << 24);
     }
 
     pub fn fade_in_time_sec(&self) -> f32 {
@@ -15023,32 +15033,32 @@ impl EQUIP_PARAM_GOODS_ST {
 
     #[allow(clippy::identity_op)]
     pub fn is_sleep_collection_item(&self) -> u8 {
-        (self.bits_6f >> 4) & 0b00000001
+        (self.bits_6f >> 5) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_sleep_collection_item(&mut self, value: u8) {
-        self.bits_6f = (self.bits_6f & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+        self.bits_6f = (self.bits_6f & !(0b00000001 << 5)) | ((value & 0b00000001) << 5);
     }
 
     #[allow(clippy::identity_op)]
     pub fn enable_riding(&self) -> u8 {
-        (self.bits_6f >> 5) & 0b00000001
+        (self.bits_6f >> 6) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_enable_riding(&mut self, value: u8) {
-        self.bits_6f = (self.bits_6f & !(0b00000001 << 5)) | ((value & 0b00000001) << 5);
+        self.bits_6f = (self.bits_6f & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
     }
 
     #[allow(clippy::identity_op)]
     pub fn disable_riding(&self) -> u8 {
-        (self.bits_6f >> 6) & 0b00000001
+        (self.bits_6f >> 7) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_disable_riding(&mut self, value: u8) {
-        self.bits_6f = (self.bits_6f & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
+        self.bits_6f = (self.bits_6f & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
     }
 
     pub fn max_repository_num(&self) -> i16 {
@@ -18840,22 +18850,22 @@ impl EQUIP_PARAM_WEAPON_ST {
 
     #[allow(clippy::identity_op)]
     pub fn is_dragon_slayer(&self) -> u8 {
-        (self.bits_108 >> 1) & 0b00000001
+        (self.bits_108 >> 6) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_dragon_slayer(&mut self, value: u8) {
-        self.bits_108 = (self.bits_108 & !(0b00000001 << 1)) | ((value & 0b00000001) << 1);
+        self.bits_108 = (self.bits_108 & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_deposit(&self) -> u8 {
-        (self.bits_108 >> 2) & 0b00000001
+        (self.bits_108 >> 7) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_deposit(&mut self, value: u8) {
-        self.bits_108 = (self.bits_108 & !(0b00000001 << 2)) | ((value & 0b00000001) << 2);
+        self.bits_108 = (self.bits_108 & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
     }
 
     #[allow(clippy::identity_op)]
@@ -18920,12 +18930,12 @@ impl EQUIP_PARAM_WEAPON_ST {
 
     #[allow(clippy::identity_op)]
     pub fn disable_gem_attr(&self) -> u8 {
-        (self.bits_109 >> 6) & 0b00000001
+        (self.bits_109 >> 7) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_disable_gem_attr(&mut self, value: u8) {
-        self.bits_109 = (self.bits_109 & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
+        self.bits_109 = (self.bits_109 & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
     }
 
     pub fn def_sfx_material1(&self) -> u16 {
@@ -30392,42 +30402,42 @@ impl HIT_MTRL_PARAM_ST {
 
     #[allow(clippy::identity_op)]
     pub fn foot_effect_dir_type(&self) -> u8 {
-        (self.bits_c >> 1) & 0b00000011
+        (self.bits_c >> 2) & 0b00000011
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_foot_effect_dir_type(&mut self, value: u8) {
-        self.bits_c = (self.bits_c & !(0b00000011 << 1)) | ((value & 0b00000011) << 1);
+        self.bits_c = (self.bits_c & !(0b00000011 << 2)) | ((value & 0b00000011) << 2);
     }
 
     #[allow(clippy::identity_op)]
     pub fn floor_height_type(&self) -> u8 {
-        (self.bits_c >> 2) & 0b00000011
+        (self.bits_c >> 4) & 0b00000011
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_floor_height_type(&mut self, value: u8) {
-        self.bits_c = (self.bits_c & !(0b00000011 << 2)) | ((value & 0b00000011) << 2);
+        self.bits_c = (self.bits_c & !(0b00000011 << 4)) | ((value & 0b00000011) << 4);
     }
 
     #[allow(clippy::identity_op)]
     pub fn disable_fall_damage(&self) -> u8 {
-        (self.bits_c >> 3) & 0b00000001
+        (self.bits_c >> 6) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_disable_fall_damage(&mut self, value: u8) {
-        self.bits_c = (self.bits_c & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+        self.bits_c = (self.bits_c & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
     }
 
     #[allow(clippy::identity_op)]
     pub fn is_hardness_for_sound_reverb(&self) -> u8 {
-        (self.bits_c >> 4) & 0b00000001
+        (self.bits_c >> 7) & 0b00000001
     }
 
     #[allow(clippy::identity_op)]
     pub fn set_is_hardness_for_sound_reverb(&mut self, value: u8) {
-        self.bits_c = (self.bits_c & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+        self.bits_c = (self.bits_c & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
     }
 
     pub fn hardness_type(&self) -> u8 {
@@ -30655,8 +30665,7 @@ pub struct ITEMLOT_PARAM_ST {
     lot_item_num06: u8,
     lot_item_num07: u8,
     lot_item_num08: u8,
-    bits_92: u8,
-    bits_93: u8,
+    bits_92: u16,
     game_clear_offset: i8,
     bits_95: u8,
     end_padding: [u8; 66],
@@ -31084,163 +31093,179 @@ impl ITEMLOT_PARAM_ST {
     }
 
     #[allow(clippy::identity_op)]
-    pub fn enable_luck01(&self) -> u8 {
-        (self.bits_92 >> 0) & 0b00000001
+    pub fn enable_luck01(&self) -> u16 {
+        (self.bits_92 >> 0) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_enable_luck01(&mut self, value: u8) {
-        self.bits_92 = (self.bits_92 & !(0b00000001 << 0)) | ((value & 0b00000001) << 0);
+    pub fn set_enable_luck01(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 0)) | ((value & 0b0000000000000001) << 0);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn enable_luck02(&self) -> u8 {
-        (self.bits_92 >> 1) & 0b00000001
+    pub fn enable_luck02(&self) -> u16 {
+        (self.bits_92 >> 1) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_enable_luck02(&mut self, value: u8) {
-        self.bits_92 = (self.bits_92 & !(0b00000001 << 1)) | ((value & 0b00000001) << 1);
+    pub fn set_enable_luck02(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 1)) | ((value & 0b0000000000000001) << 1);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn enable_luck03(&self) -> u8 {
-        (self.bits_92 >> 2) & 0b00000001
+    pub fn enable_luck03(&self) -> u16 {
+        (self.bits_92 >> 2) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_enable_luck03(&mut self, value: u8) {
-        self.bits_92 = (self.bits_92 & !(0b00000001 << 2)) | ((value & 0b00000001) << 2);
+    pub fn set_enable_luck03(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 2)) | ((value & 0b0000000000000001) << 2);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn enable_luck04(&self) -> u8 {
-        (self.bits_92 >> 3) & 0b00000001
+    pub fn enable_luck04(&self) -> u16 {
+        (self.bits_92 >> 3) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_enable_luck04(&mut self, value: u8) {
-        self.bits_92 = (self.bits_92 & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+    pub fn set_enable_luck04(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 3)) | ((value & 0b0000000000000001) << 3);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn enable_luck05(&self) -> u8 {
-        (self.bits_92 >> 4) & 0b00000001
+    pub fn enable_luck05(&self) -> u16 {
+        (self.bits_92 >> 4) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_enable_luck05(&mut self, value: u8) {
-        self.bits_92 = (self.bits_92 & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+    pub fn set_enable_luck05(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 4)) | ((value & 0b0000000000000001) << 4);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn enable_luck06(&self) -> u8 {
-        (self.bits_92 >> 5) & 0b00000001
+    pub fn enable_luck06(&self) -> u16 {
+        (self.bits_92 >> 5) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_enable_luck06(&mut self, value: u8) {
-        self.bits_92 = (self.bits_92 & !(0b00000001 << 5)) | ((value & 0b00000001) << 5);
+    pub fn set_enable_luck06(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 5)) | ((value & 0b0000000000000001) << 5);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn enable_luck07(&self) -> u8 {
-        (self.bits_92 >> 6) & 0b00000001
+    pub fn enable_luck07(&self) -> u16 {
+        (self.bits_92 >> 6) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_enable_luck07(&mut self, value: u8) {
-        self.bits_92 = (self.bits_92 & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
+    pub fn set_enable_luck07(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 6)) | ((value & 0b0000000000000001) << 6);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn enable_luck08(&self) -> u8 {
-        (self.bits_92 >> 7) & 0b00000001
+    pub fn enable_luck08(&self) -> u16 {
+        (self.bits_92 >> 7) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_enable_luck08(&mut self, value: u8) {
-        self.bits_92 = (self.bits_92 & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
+    pub fn set_enable_luck08(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 7)) | ((value & 0b0000000000000001) << 7);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn cumulate_reset01(&self) -> u8 {
-        (self.bits_93 >> 0) & 0b00000001
+    pub fn cumulate_reset01(&self) -> u16 {
+        (self.bits_92 >> 8) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_cumulate_reset01(&mut self, value: u8) {
-        self.bits_93 = (self.bits_93 & !(0b00000001 << 0)) | ((value & 0b00000001) << 0);
+    pub fn set_cumulate_reset01(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 8)) | ((value & 0b0000000000000001) << 8);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn cumulate_reset02(&self) -> u8 {
-        (self.bits_93 >> 1) & 0b00000001
+    pub fn cumulate_reset02(&self) -> u16 {
+        (self.bits_92 >> 9) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_cumulate_reset02(&mut self, value: u8) {
-        self.bits_93 = (self.bits_93 & !(0b00000001 << 1)) | ((value & 0b00000001) << 1);
+    pub fn set_cumulate_reset02(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 9)) | ((value & 0b0000000000000001) << 9);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn cumulate_reset03(&self) -> u8 {
-        (self.bits_93 >> 2) & 0b00000001
+    pub fn cumulate_reset03(&self) -> u16 {
+        (self.bits_92 >> 10) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_cumulate_reset03(&mut self, value: u8) {
-        self.bits_93 = (self.bits_93 & !(0b00000001 << 2)) | ((value & 0b00000001) << 2);
+    pub fn set_cumulate_reset03(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 10)) | ((value & 0b0000000000000001) << 10);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn cumulate_reset04(&self) -> u8 {
-        (self.bits_93 >> 3) & 0b00000001
+    pub fn cumulate_reset04(&self) -> u16 {
+        (self.bits_92 >> 11) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_cumulate_reset04(&mut self, value: u8) {
-        self.bits_93 = (self.bits_93 & !(0b00000001 << 3)) | ((value & 0b00000001) << 3);
+    pub fn set_cumulate_reset04(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 11)) | ((value & 0b0000000000000001) << 11);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn cumulate_reset05(&self) -> u8 {
-        (self.bits_93 >> 4) & 0b00000001
+    pub fn cumulate_reset05(&self) -> u16 {
+        (self.bits_92 >> 12) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_cumulate_reset05(&mut self, value: u8) {
-        self.bits_93 = (self.bits_93 & !(0b00000001 << 4)) | ((value & 0b00000001) << 4);
+    pub fn set_cumulate_reset05(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 12)) | ((value & 0b0000000000000001) << 12);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn cumulate_reset06(&self) -> u8 {
-        (self.bits_93 >> 5) & 0b00000001
+    pub fn cumulate_reset06(&self) -> u16 {
+        (self.bits_92 >> 13) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_cumulate_reset06(&mut self, value: u8) {
-        self.bits_93 = (self.bits_93 & !(0b00000001 << 5)) | ((value & 0b00000001) << 5);
+    pub fn set_cumulate_reset06(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 13)) | ((value & 0b0000000000000001) << 13);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn cumulate_reset07(&self) -> u8 {
-        (self.bits_93 >> 6) & 0b00000001
+    pub fn cumulate_reset07(&self) -> u16 {
+        (self.bits_92 >> 14) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_cumulate_reset07(&mut self, value: u8) {
-        self.bits_93 = (self.bits_93 & !(0b00000001 << 6)) | ((value & 0b00000001) << 6);
+    pub fn set_cumulate_reset07(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 14)) | ((value & 0b0000000000000001) << 14);
     }
 
     #[allow(clippy::identity_op)]
-    pub fn cumulate_reset08(&self) -> u8 {
-        (self.bits_93 >> 7) & 0b00000001
+    pub fn cumulate_reset08(&self) -> u16 {
+        (self.bits_92 >> 15) & 0b0000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_cumulate_reset08(&mut self, value: u8) {
-        self.bits_93 = (self.bits_93 & !(0b00000001 << 7)) | ((value & 0b00000001) << 7);
+    pub fn set_cumulate_reset08(&mut self, value: u16) {
+        self.bits_92 =
+            (self.bits_92 & !(0b0000000000000001 << 15)) | ((value & 0b0000000000000001) << 15);
     }
 
     pub fn game_clear_offset(&self) -> i8 {
@@ -44652,7 +44677,7 @@ pub struct PARTS_DRAW_PARAM_ST {
     lv45_play_dist: f32,
     tex_lv01_border_dist: f32,
     tex_lv01_play_dist: f32,
-    bits_30: u8,
+    bits_30: u32,
     draw_dist: f32,
     draw_fade_range: f32,
     shadow_draw_dist: f32,
@@ -44783,13 +44808,14 @@ impl PARTS_DRAW_PARAM_ST {
     }
 
     #[allow(clippy::identity_op)]
-    pub fn enable_cross_fade(&self) -> u8 {
-        (self.bits_30 >> 0) & 0b00000001
+    pub fn enable_cross_fade(&self) -> u32 {
+        (self.bits_30 >> 0) & 0b00000000000000000000000000000001
     }
 
     #[allow(clippy::identity_op)]
-    pub fn set_enable_cross_fade(&mut self, value: u8) {
-        self.bits_30 = (self.bits_30 & !(0b00000001 << 0)) | ((value & 0b00000001) << 0);
+    pub fn set_enable_cross_fade(&mut self, value: u32) {
+        self.bits_30 = (self.bits_30 & !(0b00000000000000000000000000000001 << 0))
+            | ((value & 0b00000000000000000000000000000001) << 0);
     }
 
     pub fn draw_dist(&self) -> f32 {
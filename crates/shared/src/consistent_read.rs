@@ -0,0 +1,160 @@
+//! Reading structures the game may be writing to at the same time.
+//!
+//! Reads from outside the game's own threads, e.g. from an overlay's render
+//! thread, can see a structure halfway through an update. These helpers
+//! repeat a read until it can be trusted not to be torn. They don't make the
+//! read itself safe: the closures still have to only read memory that stays
+//! allocated while they run.
+use std::sync::atomic::{Ordering, compiler_fence, fence};
+
+use thiserror::Error;
+
+/// How often a read is attempted before giving up.
+pub const MAX_READ_ATTEMPTS: usize = 8;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("Value kept changing over {attempts} reads")]
+pub struct TornReadError {
+    pub attempts: usize,
+}
+
+/// Repeats `read` until two consecutive reads return the same value.
+///
+/// Two equal reads aren't a guarantee that neither is torn, ex. when both
+/// land in the middle of the same slow write, only that it's unlikely. Use
+/// [consistent_read_sequenced] where the writer keeps a sequence counter.
+pub fn consistent_read<T: PartialEq>(mut read: impl FnMut() -> T) -> Result<T, TornReadError> {
+    let mut previous = read();
+    for _ in 1..MAX_READ_ATTEMPTS {
+        compiler_fence(Ordering::SeqCst);
+        let current = read();
+        if current == previous {
+            return Ok(current);
+        }
+
+        previous = current;
+    }
+
+    Err(TornReadError {
+        attempts: MAX_READ_ATTEMPTS,
+    })
+}
+
+/// Repeats `read` until `sequence`, the counter of a seqlock, is even and
+/// the same before and after the read.
+///
+/// The writer has to bump the counter both before and after every update, so
+/// it's odd while an update is in progress. Counters that are only bumped
+/// once per update can't tell a read that happened entirely during the
+/// update from one that didn't, so they can't be used here.
+///
+/// This is the cheaper option for structures that have such a counter, since
+/// the structure itself only has to be read once when nothing changes.
+pub fn consistent_read_sequenced<T>(
+    mut sequence: impl FnMut() -> u64,
+    mut read: impl FnMut() -> T,
+) -> Result<T, TornReadError> {
+    for _ in 0..MAX_READ_ATTEMPTS {
+        let before = sequence();
+        if before % 2 == 1 {
+            std::hint::spin_loop();
+            continue;
+        }
+
+        fence(Ordering::Acquire);
+        let value = read();
+        fence(Ordering::Acquire);
+        if sequence() == before {
+            return Ok(value);
+        }
+    }
+
+    Err(TornReadError {
+        attempts: MAX_READ_ATTEMPTS,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::Cell;
+
+    use super::{MAX_READ_ATTEMPTS, TornReadError, consistent_read, consistent_read_sequenced};
+
+    #[test]
+    fn settles_once_reads_agree() {
+        let values = [1, 2, 3, 3];
+        let reads = Cell::new(0);
+        let value = consistent_read(|| {
+            let value = values[reads.get()];
+            reads.set(reads.get() + 1);
+            value
+        });
+
+        assert_eq!(value, Ok(3));
+        assert_eq!(reads.get(), 4);
+    }
+
+    #[test]
+    fn gives_up_on_changing_values() {
+        let counter = Cell::new(0);
+        let value = consistent_read(|| {
+            counter.set(counter.get() + 1);
+            counter.get()
+        });
+
+        assert_eq!(
+            value,
+            Err(TornReadError {
+                attempts: MAX_READ_ATTEMPTS
+            })
+        );
+    }
+
+    #[test]
+    fn retries_when_sequence_changes() {
+        let sequence = Cell::new(0);
+        let value = consistent_read_sequenced(
+            || sequence.get(),
+            || {
+                // The writer starts an update during the first read, and
+                // finishes it during the second attempt.
+                match sequence.get() {
+                    0 => {
+                        sequence.set(1);
+                        "torn"
+                    }
+                    _ => "whole",
+                }
+            },
+        );
+        assert_eq!(
+            value,
+            Err(TornReadError {
+                attempts: MAX_READ_ATTEMPTS
+            })
+        );
+
+        let sequence = Cell::new(0);
+        let attempts = Cell::new(0);
+        let value = consistent_read_sequenced(
+            || {
+                attempts.set(attempts.get() + 1);
+                // The update that started during the first read finishes
+                // while the reader waits on the odd counter.
+                if sequence.get() == 1 && attempts.get() > 3 {
+                    sequence.set(2);
+                }
+                sequence.get()
+            },
+            || {
+                if sequence.get() == 0 {
+                    sequence.set(1);
+                    "torn"
+                } else {
+                    "whole"
+                }
+            },
+        );
+        assert_eq!(value, Ok("whole"));
+    }
+}
@@ -0,0 +1,206 @@
+//! Test harness for exercising bindings without a running game, enabled with
+//! the `fixtures` feature.
+//!
+//! [install_program] swaps out what [Program::current] returns on the calling
+//! thread, so code resolving RVAs can run against a memory snapshot of the
+//! game's executable loaded with [load_snapshot]. Snapshots have to be dumps
+//! of the mapped image, e.g. taken from a debugger, not the file on disk.
+//! [empty_program] stands in for an executable the bindings don't know.
+//!
+//! [FixtureMemory] places synthetic structures in memory so container and
+//! struct logic that follows raw pointers can be tested on its own.
+use std::alloc::{self, Layout};
+use std::cell::Cell;
+use std::path::Path;
+use std::ptr::NonNull;
+use std::{fs, io};
+
+use pelite::pe64::PeView;
+
+use crate::Program;
+
+/// Alignment snapshots are loaded at, matching the page alignment of a real
+/// mapped image.
+const SNAPSHOT_ALIGNMENT: usize = 0x1000;
+
+thread_local! {
+    static CURRENT_OVERRIDE: Cell<Option<Program<'static>>> = const { Cell::new(None) };
+}
+
+pub(crate) fn current_override() -> Option<Program<'static>> {
+    CURRENT_OVERRIDE.get()
+}
+
+/// Restores the previously installed program when dropped.
+#[must_use = "the program is uninstalled again when the guard is dropped"]
+pub struct ProgramGuard {
+    previous: Option<Program<'static>>,
+}
+
+impl Drop for ProgramGuard {
+    fn drop(&mut self) {
+        CURRENT_OVERRIDE.set(self.previous);
+    }
+}
+
+/// Makes [Program::current] return `program` on this thread until the
+/// returned guard is dropped.
+pub fn install_program(program: Program<'static>) -> ProgramGuard {
+    ProgramGuard {
+        previous: CURRENT_OVERRIDE.replace(Some(program)),
+    }
+}
+
+/// Loads a memory snapshot of a mapped image. The snapshot is leaked, since a
+/// [Program] borrows its image for as long as it's used.
+pub fn load_snapshot(path: impl AsRef<Path>) -> io::Result<Program<'static>> {
+    let bytes = fs::read(path)?;
+    PeView::from_bytes(leak_image(&bytes)?)
+        .map(Program::Mapping)
+        .map_err(io::Error::other)
+}
+
+/// Returns a mapped 64-bit image with nothing but its headers: no code, no
+/// resources and no RTTI. Leaked like [load_snapshot].
+pub fn empty_program() -> Program<'static> {
+    let mut image = vec![0u8; SNAPSHOT_ALIGNMENT];
+    let mut write = |offset: usize, bytes: &[u8]| {
+        image[offset..offset + bytes.len()].copy_from_slice(bytes);
+    };
+
+    // DOS header, pointing to the NT headers right after it.
+    write(0x0, b"MZ");
+    write(0x3C, &0x40u32.to_le_bytes());
+    // NT headers of an x64 image without sections.
+    write(0x40, b"PE\0\0");
+    write(0x44, &0x8664u16.to_le_bytes());
+    write(0x54, &0xF0u16.to_le_bytes());
+    write(0x56, &0x22u16.to_le_bytes());
+    // PE32+ optional header.
+    write(0x58, &0x20Bu16.to_le_bytes());
+    write(0x70, &0x1_4000_0000u64.to_le_bytes());
+    write(0x78, &(SNAPSHOT_ALIGNMENT as u32).to_le_bytes());
+    write(0x7C, &0x200u32.to_le_bytes());
+    write(0x90, &(SNAPSHOT_ALIGNMENT as u32).to_le_bytes());
+    write(0x94, &0x200u32.to_le_bytes());
+    write(0x9C, &2u16.to_le_bytes());
+    write(0xC4, &16u32.to_le_bytes());
+
+    let image = leak_image(&image).expect("the image has a valid layout");
+    Program::Mapping(PeView::from_bytes(image).expect("the headers are valid"))
+}
+
+/// Copies `bytes` to a leaked allocation aligned like a mapped image.
+fn leak_image(bytes: &[u8]) -> io::Result<&'static [u8]> {
+    let layout = Layout::from_size_align(bytes.len().max(1), SNAPSHOT_ALIGNMENT)
+        .map_err(io::Error::other)?;
+
+    unsafe {
+        let ptr = alloc::alloc(layout);
+        if ptr.is_null() {
+            alloc::handle_alloc_error(layout);
+        }
+        ptr.copy_from_nonoverlapping(bytes.as_ptr(), bytes.len());
+        Ok(std::slice::from_raw_parts(ptr, bytes.len()))
+    }
+}
+
+/// Memory holding synthetic fixtures. Pointers handed out stay valid until
+/// the fixture memory is dropped. Values placed in it are never dropped.
+#[derive(Default)]
+pub struct FixtureMemory {
+    allocations: Vec<(NonNull<u8>, Layout)>,
+}
+
+impl FixtureMemory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn allocate(&mut self, layout: Layout) -> NonNull<u8> {
+        // Zero sized allocations aren't allowed, pad them to a single byte.
+        let layout = Layout::from_size_align(layout.size().max(1), layout.align()).unwrap();
+        let ptr = NonNull::new(unsafe { alloc::alloc_zeroed(layout) })
+            .unwrap_or_else(|| alloc::handle_alloc_error(layout));
+
+        self.allocations.push((ptr, layout));
+        ptr
+    }
+
+    /// Moves `value` into fixture memory.
+    pub fn alloc<T>(&mut self, value: T) -> NonNull<T> {
+        let ptr = self.allocate(Layout::new::<T>()).cast::<T>();
+        unsafe { ptr.write(value) };
+        ptr
+    }
+
+    /// Allocates zeroed memory for a `T`, for game structures with private
+    /// fields that can't be constructed directly.
+    pub fn alloc_zeroed<T>(&mut self) -> NonNull<T> {
+        self.allocate(Layout::new::<T>()).cast::<T>()
+    }
+
+    /// Copies `values` into fixture memory and returns a pointer to the first
+    /// one.
+    pub fn alloc_slice<T: Copy>(&mut self, values: &[T]) -> NonNull<T> {
+        let ptr = self
+            .allocate(Layout::array::<T>(values.len()).unwrap())
+            .cast::<T>();
+        unsafe {
+            ptr.as_ptr()
+                .copy_from_nonoverlapping(values.as_ptr(), values.len())
+        };
+        ptr
+    }
+}
+
+impl Drop for FixtureMemory {
+    fn drop(&mut self) {
+        for (ptr, layout) in self.allocations.drain(..) {
+            unsafe { alloc::dealloc(ptr.as_ptr(), layout) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use pelite::pe64::Pe;
+
+    use super::{FixtureMemory, empty_program, install_program};
+    use crate::Program;
+
+    #[repr(C, align(16))]
+    struct Aligned(u32);
+
+    #[test]
+    fn alloc_respects_alignment() {
+        let mut memory = FixtureMemory::new();
+        let ptr = memory.alloc(Aligned(5));
+
+        assert_eq!(ptr.as_ptr() as usize % 16, 0);
+        assert_eq!(unsafe { ptr.as_ref() }.0, 5);
+    }
+
+    #[test]
+    fn alloc_slice_copies_values() {
+        let mut memory = FixtureMemory::new();
+        let ptr = memory.alloc_slice(&[1u16, 2, 3]);
+
+        assert_eq!(
+            unsafe { std::slice::from_raw_parts(ptr.as_ptr(), 3) },
+            &[1, 2, 3]
+        );
+        memory.alloc_slice::<u64>(&[]);
+    }
+
+    #[test]
+    fn installed_program_is_current() {
+        assert!(!Program::current_is_fixture());
+        {
+            let _guard = install_program(empty_program());
+            assert!(Program::current_is_fixture());
+            assert!(Program::current().resources().is_err());
+        }
+        assert!(!Program::current_is_fixture());
+    }
+}
@@ -0,0 +1,115 @@
+//! The hash functions the games use to look things up by name, as `const fn`s
+//! so the hashes can be computed at compile time or from offline tools and
+//! still match what the game computes at runtime.
+//!
+//! Only algorithms that have been confirmed against the games are included.
+
+/// Applies the same normalization the games use for archive paths: ASCII
+/// lowercase, backslashes turned into forward slashes, and an implicit
+/// leading slash.
+const fn normalized_path_byte(byte: u8) -> u8 {
+    match byte {
+        b'\\' => b'/',
+        _ => byte.to_ascii_lowercase(),
+    }
+}
+
+/// Hashes a path inside of a BHD5 archive the way games up to and including
+/// Sekiro do, e.g. `/param/gameparam/gameparam.parambnd.dcx`.
+pub const fn path_hash_32(path: &str) -> u32 {
+    const PRIME: u32 = 37;
+
+    let bytes = path.as_bytes();
+    let mut hash: u32 = 0;
+    if bytes.is_empty() || normalized_path_byte(bytes[0]) != b'/' {
+        hash = b'/' as u32;
+    }
+
+    let mut i = 0;
+    while i < bytes.len() {
+        hash = hash
+            .wrapping_mul(PRIME)
+            .wrapping_add(normalized_path_byte(bytes[i]) as u32);
+        i += 1;
+    }
+
+    hash
+}
+
+/// Hashes a path inside of a BHD5 archive the way Elden Ring and later
+/// games do.
+pub const fn path_hash_64(path: &str) -> u64 {
+    const PRIME: u64 = 0x85;
+
+    let bytes = path.as_bytes();
+    let mut hash: u64 = 0;
+    if bytes.is_empty() || normalized_path_byte(bytes[0]) != b'/' {
+        hash = b'/' as u64;
+    }
+
+    let mut i = 0;
+    while i < bytes.len() {
+        hash = hash
+            .wrapping_mul(PRIME)
+            .wrapping_add(normalized_path_byte(bytes[i]) as u64);
+        i += 1;
+    }
+
+    hash
+}
+
+/// Computes the Wwise short ID of a name, which is how events, switches,
+/// states and banks are referred to at runtime. This is a 32 bit FNV-1 hash
+/// of the lowercased name.
+pub const fn wwise_short_id(name: &str) -> u32 {
+    const OFFSET_BASIS: u32 = 0x811c9dc5;
+    const PRIME: u32 = 0x01000193;
+
+    let bytes = name.as_bytes();
+    let mut hash = OFFSET_BASIS;
+
+    let mut i = 0;
+    while i < bytes.len() {
+        hash = hash.wrapping_mul(PRIME) ^ bytes[i].to_ascii_lowercase() as u32;
+        i += 1;
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod test {
+    use super::{path_hash_32, path_hash_64, wwise_short_id};
+
+    #[test]
+    fn path_hashes_normalize() {
+        const HASH: u64 = path_hash_64("/param/gameparam/gameparam.parambnd.dcx");
+
+        assert_eq!(path_hash_64("param/gameparam/gameparam.parambnd.dcx"), HASH);
+        assert_eq!(
+            path_hash_64("\\PARAM\\GameParam\\GameParam.parambnd.dcx"),
+            HASH
+        );
+        assert_eq!(
+            path_hash_32("/param/gameparam/gameparam.parambnd.dcx"),
+            path_hash_32("param\\gameparam\\GAMEPARAM.parambnd.dcx")
+        );
+    }
+
+    #[test]
+    fn path_hashes_match_reference() {
+        assert_eq!(path_hash_32("/a"), 47 * 37 + 97);
+        assert_eq!(path_hash_64("/a"), 47 * 0x85 + 97);
+        assert_eq!(path_hash_64(""), 47);
+    }
+
+    #[test]
+    fn wwise_short_id_matches_fnv1() {
+        assert_eq!(wwise_short_id(""), 0x811c9dc5);
+        assert_eq!(wwise_short_id("a"), 0x050c5d7e);
+        assert_eq!(
+            wwise_short_id("Play_Footstep"),
+            wwise_short_id("play_footstep")
+        );
+    }
+}
@@ -0,0 +1,176 @@
+//! Developer mode for iterating on a mod against a running game, enabled with
+//! the `hot-reload` feature.
+//!
+//! A thin loader stub stays injected into the game and owns a [HotReloader],
+//! while the actual mod logic lives in a second DLL. The reloader loads a
+//! shadow copy of that DLL so it can be rebuilt while the game is running,
+//! and swaps it out whenever the file changes.
+//!
+//! The reloadable DLL exports its entrypoints with [reloadable_mod!]. Its
+//! load function should set up all hooks and tasks, registering anything
+//! that needs to be undone with [crate::register_teardown]. Before the old
+//! copy is unloaded, its teardown registry is run so that everything can be
+//! registered again by the new copy.
+//!
+//! Tasks registered with the game can't be removed from the game's task
+//! system yet. Long-lived tasks should be registered by the loader stub and
+//! call into the reloadable DLL through [HotReloader::get] every frame.
+use std::ffi::CStr;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use thiserror::Error;
+use windows::Win32::Foundation::HMODULE;
+use windows::Win32::System::LibraryLoader::{FreeLibrary, GetProcAddress, LoadLibraryW};
+use windows::core::{HSTRING, PCSTR};
+
+/// Name of the export called after the DLL was loaded.
+pub const LOAD_EXPORT: &CStr = c"fromsoftware_mod_load";
+/// Name of the export called right before the DLL is unloaded.
+pub const UNLOAD_EXPORT: &CStr = c"fromsoftware_mod_unload";
+
+#[derive(Error, Debug)]
+pub enum HotReloadError {
+    #[error("Could not access the mod DLL: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not load the mod DLL: {0}")]
+    Load(windows::core::Error),
+    #[error("Mod DLL does not export {0:?}")]
+    MissingExport(&'static CStr),
+}
+
+/// Exports the entrypoints a [HotReloader] looks for. The load function is
+/// called on every (re)load, unloading always runs the teardown registry.
+#[macro_export]
+macro_rules! reloadable_mod {
+    ($load:expr) => {
+        #[unsafe(no_mangle)]
+        pub extern "C" fn fromsoftware_mod_load() {
+            ($load)();
+        }
+
+        #[unsafe(no_mangle)]
+        pub extern "C" fn fromsoftware_mod_unload() {
+            $crate::run_teardown();
+        }
+    };
+}
+
+struct LoadedMod {
+    module: HMODULE,
+    modified: SystemTime,
+    shadow_path: PathBuf,
+}
+
+pub struct HotReloader {
+    path: PathBuf,
+    generation: usize,
+    loaded: Option<LoadedMod>,
+}
+
+impl HotReloader {
+    /// Loads the mod DLL at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Result<Self, HotReloadError> {
+        let mut reloader = Self {
+            path: path.into(),
+            generation: 0,
+            loaded: None,
+        };
+
+        reloader.reload()?;
+        Ok(reloader)
+    }
+
+    /// Reloads the mod DLL if it changed on disk since it was last loaded.
+    /// Returns whether a reload happened.
+    pub fn poll(&mut self) -> Result<bool, HotReloadError> {
+        let modified = std::fs::metadata(&self.path)?.modified()?;
+        if self
+            .loaded
+            .as_ref()
+            .is_some_and(|loaded| loaded.modified >= modified)
+        {
+            return Ok(false);
+        }
+
+        self.reload()?;
+        Ok(true)
+    }
+
+    /// Unloads the current copy of the mod, if any, and loads a fresh one.
+    pub fn reload(&mut self) -> Result<(), HotReloadError> {
+        self.unload();
+
+        let modified = std::fs::metadata(&self.path)?.modified()?;
+
+        // Windows keeps loaded DLLs locked, so load a copy to keep the
+        // original free to be overwritten by the next build.
+        self.generation += 1;
+        let shadow_path = shadow_path(&self.path, self.generation);
+        std::fs::copy(&self.path, &shadow_path)?;
+
+        let module = unsafe { LoadLibraryW(&HSTRING::from(shadow_path.as_os_str())) }
+            .map_err(HotReloadError::Load)?;
+
+        self.loaded = Some(LoadedMod {
+            module,
+            modified,
+            shadow_path,
+        });
+
+        // SAFETY: reloadable_mod! exports it with this signature.
+        let load: extern "C" fn() =
+            unsafe { self.get(LOAD_EXPORT) }.ok_or(HotReloadError::MissingExport(LOAD_EXPORT))?;
+        load();
+        Ok(())
+    }
+
+    /// Looks up an export of the currently loaded copy.
+    ///
+    /// # Safety
+    /// `T` must be a function pointer type matching the signature and ABI of
+    /// the export, ex. `extern "C" fn(u32) -> bool`. The result must not be
+    /// called after the next reload, which unloads the code it points to.
+    ///
+    /// # Panics
+    /// If `T` isn't pointer sized.
+    pub unsafe fn get<T: Copy>(&self, name: &CStr) -> Option<T> {
+        assert_eq!(size_of::<T>(), size_of::<usize>());
+        let loaded = self.loaded.as_ref()?;
+        let export = unsafe { GetProcAddress(loaded.module, PCSTR(name.as_ptr().cast())) }?;
+
+        // SAFETY: the size is checked above, the signature is up to the caller.
+        Some(unsafe { std::mem::transmute_copy(&export) })
+    }
+
+    fn unload(&mut self) {
+        if self.loaded.is_none() {
+            return;
+        }
+
+        // SAFETY: reloadable_mod! exports it with this signature.
+        if let Some(unload) = unsafe { self.get::<extern "C" fn()>(UNLOAD_EXPORT) } {
+            unload();
+        }
+
+        let loaded = self.loaded.take().unwrap();
+        unsafe {
+            let _ = FreeLibrary(loaded.module);
+        }
+
+        // Clean up the copy, this is allowed to fail if something still has
+        // the DLL mapped for some reason.
+        let _ = std::fs::remove_file(loaded.shadow_path);
+    }
+}
+
+impl Drop for HotReloader {
+    fn drop(&mut self) {
+        self.unload();
+    }
+}
+
+fn shadow_path(path: &Path, generation: usize) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}.hot{generation}.dll"))
+}
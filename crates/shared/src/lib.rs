@@ -1,19 +1,36 @@
 pub mod arxan;
+pub mod consistent_read;
 pub mod dl_math;
 pub mod ext;
+#[cfg(feature = "fixtures")]
+pub mod fixture;
+pub mod hash;
+#[cfg(all(windows, feature = "hot-reload"))]
+pub mod hot_reload;
+pub mod listeners;
+#[cfg(feature = "mod-config")]
+pub mod mod_config;
 pub mod owned_pointer;
 pub mod program;
 pub mod rtti;
+pub mod scan;
 mod r#static;
 pub mod task;
+#[cfg(feature = "task-profiling")]
+pub mod task_profiler;
+pub mod teardown;
 
 pub use arxan::*;
+pub use consistent_read::*;
 pub use dl_math::*;
+pub use hash::*;
+pub use listeners::*;
 pub use owned_pointer::*;
 pub use program::*;
 pub use rtti::*;
 pub use r#static::*;
 pub use task::*;
+pub use teardown::*;
 
 pub use from_singleton::FromSingleton;
 pub use fromsoftware_shared_macros::singleton;
@@ -0,0 +1,82 @@
+use std::sync::Mutex;
+
+type Listener<E> = Box<dyn FnMut(E) + Send>;
+
+/// Callbacks registered from anywhere and notified about events from
+/// whichever thread polls for them.
+///
+/// The list isn't locked while the listeners run, so they're free to register
+/// more listeners or to read the state the events came from. Listeners
+/// registered during a notification only see later events.
+pub struct Listeners<E> {
+    listeners: Mutex<Vec<Listener<E>>>,
+}
+
+impl<E: Clone> Listeners<E> {
+    pub const fn new() -> Self {
+        Self {
+            listeners: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn add(&self, listener: impl FnMut(E) + Send + 'static) {
+        self.listeners.lock().unwrap().push(Box::new(listener));
+    }
+
+    /// Calls every listener with each of `events`, in order. Callers must not
+    /// hold any lock a listener could need.
+    pub fn notify(&self, events: impl IntoIterator<Item = E>) {
+        let mut events = events.into_iter().peekable();
+        if events.peek().is_none() {
+            return;
+        }
+
+        let mut notified = std::mem::take(&mut *self.listeners.lock().unwrap());
+        for event in events {
+            for listener in notified.iter_mut() {
+                listener(event.clone());
+            }
+        }
+
+        let mut listeners = self.listeners.lock().unwrap();
+        let added = std::mem::replace(&mut *listeners, notified);
+        listeners.extend(added);
+    }
+}
+
+impl<E: Clone> Default for Listeners<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::Listeners;
+
+    static LISTENERS: Listeners<u32> = Listeners::new();
+
+    #[test]
+    fn listeners_can_register_while_notified() {
+        let calls = Arc::new(AtomicU32::new(0));
+
+        let outer_calls = calls.clone();
+        LISTENERS.add(move |event| {
+            outer_calls.fetch_add(event, Ordering::Relaxed);
+            let inner_calls = outer_calls.clone();
+            LISTENERS.add(move |event| {
+                inner_calls.fetch_add(event * 100, Ordering::Relaxed);
+            });
+        });
+
+        LISTENERS.notify([1]);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        // The listener added by the first notification runs from now on.
+        LISTENERS.notify([2]);
+        assert_eq!(calls.load(Ordering::Relaxed), 1 + 2 + 200);
+    }
+}
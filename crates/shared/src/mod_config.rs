@@ -0,0 +1,255 @@
+//! Settings loaded from a TOML file next to the mod DLL, enabled with the
+//! `mod-config` feature.
+//!
+//! The settings are any type implementing serde's `Deserialize` and
+//! `Default`, missing files fall back to the default and can optionally be
+//! written out so users have something to edit. [ModConfig::poll] picks up
+//! changes to the file while the game is running, e.g. when called from a
+//! recurring task, and notifies every listener registered with
+//! [ModConfigBuilder::on_change].
+//!
+//! ```ignore
+//! #[derive(Default, Deserialize, Serialize)]
+//! #[serde(default)]
+//! struct Settings {
+//!     infinite_stamina: bool,
+//! }
+//!
+//! let mut config = ModConfig::<Settings>::builder()
+//!     .file_name("my_mod.toml")
+//!     .write_default()
+//!     .on_change(|settings| tracing::info!("stamina: {}", settings.infinite_stamina))
+//!     .build()?;
+//! ```
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ModConfigError {
+    #[error("Could not access the config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse the config file: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Could not write the default config: {0}")]
+    Serialize(#[from] toml::ser::Error),
+    #[error("Could not determine the path of the mod DLL")]
+    UnknownModulePath,
+}
+
+type ChangeListener<T> = Box<dyn FnMut(&T) + Send>;
+type DefaultWriter<T> = fn(&T) -> Result<String, toml::ser::Error>;
+
+pub struct ModConfigBuilder<T> {
+    path: Option<PathBuf>,
+    file_name: Option<String>,
+    write_default: Option<DefaultWriter<T>>,
+    listeners: Vec<ChangeListener<T>>,
+}
+
+impl<T: DeserializeOwned + Default> ModConfigBuilder<T> {
+    /// Loads the config from `path` instead of from next to the mod DLL.
+    pub fn path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.path = Some(path.into());
+        self
+    }
+
+    /// Name of the file next to the mod DLL. Defaults to the DLL's name with a
+    /// `.toml` extension.
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Writes the default settings to the file if it doesn't exist yet.
+    pub fn write_default(mut self) -> Self
+    where
+        T: Serialize,
+    {
+        self.write_default = Some(|settings| toml::to_string_pretty(settings));
+        self
+    }
+
+    /// Registers a listener that's called with the new settings every time
+    /// they're reloaded.
+    pub fn on_change(mut self, listener: impl FnMut(&T) + Send + 'static) -> Self {
+        self.listeners.push(Box::new(listener));
+        self
+    }
+
+    /// Resolves the path and loads the settings for the first time. Listeners
+    /// aren't called for the initial load.
+    pub fn build(self) -> Result<ModConfig<T>, ModConfigError> {
+        let path = match (self.path, self.file_name) {
+            (Some(path), _) => path,
+            (None, Some(file_name)) => module_path()?.with_file_name(file_name),
+            (None, None) => module_path()?.with_extension("toml"),
+        };
+
+        if let Some(write_default) = self.write_default
+            && !path.exists()
+        {
+            fs::write(&path, write_default(&T::default())?)?;
+        }
+
+        let mut config = ModConfig {
+            path,
+            modified: None,
+            settings: T::default(),
+            listeners: self.listeners,
+        };
+        config.load()?;
+
+        Ok(config)
+    }
+}
+
+pub struct ModConfig<T> {
+    path: PathBuf,
+    /// Modification time of the file when it was last loaded, None if it
+    /// didn't exist.
+    modified: Option<SystemTime>,
+    settings: T,
+    listeners: Vec<ChangeListener<T>>,
+}
+
+impl<T: DeserializeOwned + Default> ModConfig<T> {
+    pub fn builder() -> ModConfigBuilder<T> {
+        ModConfigBuilder {
+            path: None,
+            file_name: None,
+            write_default: None,
+            listeners: Vec::new(),
+        }
+    }
+
+    /// The currently loaded settings.
+    pub fn get(&self) -> &T {
+        &self.settings
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reloads the settings if the file changed since it was last loaded and
+    /// notifies the listeners. Returns whether a reload happened.
+    ///
+    /// If the new contents can't be parsed the previous settings are kept, so
+    /// a typo while editing the file doesn't reset everything.
+    pub fn poll(&mut self) -> Result<bool, ModConfigError> {
+        if modified_time(&self.path)? == self.modified {
+            return Ok(false);
+        }
+
+        self.reload()?;
+        Ok(true)
+    }
+
+    /// Reloads the settings from disk and notifies the listeners.
+    pub fn reload(&mut self) -> Result<(), ModConfigError> {
+        self.load()?;
+        for listener in self.listeners.iter_mut() {
+            listener(&self.settings);
+        }
+
+        Ok(())
+    }
+
+    fn load(&mut self) -> Result<(), ModConfigError> {
+        let modified = modified_time(&self.path)?;
+        let settings = match modified {
+            Some(_) => toml::from_str(&fs::read_to_string(&self.path)?)?,
+            None => T::default(),
+        };
+
+        self.settings = settings;
+        self.modified = modified;
+        Ok(())
+    }
+}
+
+fn modified_time(path: &Path) -> Result<Option<SystemTime>, ModConfigError> {
+    match fs::metadata(path) {
+        Ok(metadata) => Ok(Some(metadata.modified()?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Path of the DLL this crate was linked into.
+#[cfg(windows)]
+fn module_path() -> Result<PathBuf, ModConfigError> {
+    use windows::Win32::Foundation::HMODULE;
+    use windows::Win32::System::LibraryLoader::{
+        GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS, GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+        GetModuleFileNameW, GetModuleHandleExW,
+    };
+    use windows::core::PCWSTR;
+
+    let mut module = HMODULE::default();
+    unsafe {
+        GetModuleHandleExW(
+            GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS | GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+            PCWSTR(module_path as *const u16),
+            &mut module,
+        )
+    }
+    .map_err(|_| ModConfigError::UnknownModulePath)?;
+
+    let mut buffer = [0u16; 1024];
+    let len = unsafe { GetModuleFileNameW(module, &mut buffer) } as usize;
+    if len == 0 || len == buffer.len() {
+        return Err(ModConfigError::UnknownModulePath);
+    }
+
+    Ok(PathBuf::from(String::from_utf16_lossy(&buffer[..len])))
+}
+
+#[cfg(not(windows))]
+fn module_path() -> Result<PathBuf, ModConfigError> {
+    Err(ModConfigError::UnknownModulePath)
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use serde::Deserialize;
+
+    use super::ModConfig;
+
+    #[derive(Default, Deserialize)]
+    #[serde(default)]
+    struct Settings {
+        scale: f32,
+    }
+
+    #[test]
+    fn reload_keeps_settings_on_parse_error() {
+        let path = std::env::temp_dir().join(format!("mod_config_{}.toml", std::process::id()));
+        std::fs::write(&path, "scale = 2.0").unwrap();
+
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let listener_seen = seen.clone();
+        let mut config = ModConfig::<Settings>::builder()
+            .path(&path)
+            .on_change(move |settings| listener_seen.lock().unwrap().push(settings.scale))
+            .build()
+            .unwrap();
+        assert_eq!(config.get().scale, 2.0);
+
+        std::fs::write(&path, "scale = 3.0").unwrap();
+        config.reload().unwrap();
+        std::fs::write(&path, "scale = ").unwrap();
+        assert!(config.reload().is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(config.get().scale, 3.0);
+        assert_eq!(*seen.lock().unwrap(), [3.0]);
+    }
+}
@@ -1,8 +1,9 @@
+#[cfg(windows)]
 use std::sync::LazyLock;
 
 use pelite::pe64::{Pe, PeFile, PeObject, PeView};
-use windows::Win32::System::LibraryLoader::GetModuleHandleA;
-use windows::core::PCSTR;
+#[cfg(windows)]
+use windows::{Win32::System::LibraryLoader::GetModuleHandleA, core::PCSTR};
 
 #[derive(Copy, Clone)]
 pub enum Program<'a> {
@@ -10,6 +11,7 @@ pub enum Program<'a> {
     Mapping(PeView<'a>),
 }
 
+#[cfg(windows)]
 static CURRENT_BASE: LazyLock<Program> = LazyLock::new(|| {
     let module = unsafe { GetModuleHandleA(PCSTR(std::ptr::null())).unwrap().0 } as *const u8;
     Program::Mapping(unsafe { PeView::module(module) })
@@ -17,8 +19,32 @@ static CURRENT_BASE: LazyLock<Program> = LazyLock::new(|| {
 
 impl Program<'_> {
     /// Returns the currently running programing.
+    ///
+    /// Outside of Windows there's no game to run in, so this panics unless a
+    /// program was installed with the `fixtures` feature.
     pub fn current() -> Self {
-        *CURRENT_BASE
+        #[cfg(feature = "fixtures")]
+        if let Some(program) = crate::fixture::current_override() {
+            return program;
+        }
+
+        #[cfg(windows)]
+        return *CURRENT_BASE;
+
+        #[cfg(not(windows))]
+        panic!("Program::current is only available inside the game on Windows");
+    }
+
+    /// Whether [Program::current] returns a program installed by a fixture on
+    /// this thread instead of the running executable. Anything derived from
+    /// the running executable can be cached process-wide, but fixtures may
+    /// install a different program on every thread.
+    pub fn current_is_fixture() -> bool {
+        #[cfg(feature = "fixtures")]
+        return crate::fixture::current_override().is_some();
+
+        #[cfg(not(feature = "fixtures"))]
+        false
     }
 }
 
@@ -0,0 +1,88 @@
+//! Looks up addresses in the running executable at runtime, for game
+//! versions the generated RVA tables don't know about.
+//!
+//! Patterns use [pelite's syntax](pelite::pattern::parse), the same as the
+//! `mapper-profile.toml` of the game crates, and are matched against the code
+//! of [Program::current]. Scanning the whole executable takes a while, so
+//! every result is cached and each pattern is only ever scanned for once per
+//! program.
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{LazyLock, Mutex};
+
+use pelite::pattern;
+use pelite::pe64::{Pe, Rva, Va};
+use thiserror::Error;
+
+use crate::{Program, find_rtti_classes};
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    #[error("Could not parse pattern \"{0}\"")]
+    InvalidPattern(String),
+    #[error("Pattern \"{0}\" not found")]
+    NotFound(String),
+    #[error("No RTTI class named {0}")]
+    MissingClass(String),
+}
+
+/// Looks for the first match of `pattern` in the code of `program` and
+/// returns the RVAs it saved. The first one is the start of the match.
+pub fn scan_code<'a>(program: impl Pe<'a>, pattern: &str) -> Result<Vec<Rva>, ScanError> {
+    let atoms =
+        pattern::parse(pattern).map_err(|_| ScanError::InvalidPattern(pattern.to_string()))?;
+
+    let mut saves = vec![0; pattern::save_len(&atoms)];
+    if program.scanner().matches_code(&atoms).next(&mut saves) {
+        Ok(saves)
+    } else {
+        Err(ScanError::NotFound(pattern.to_string()))
+    }
+}
+
+/// Scan results by the address of the scanned image and the pattern.
+type PatternCache = BTreeMap<(usize, &'static str), Result<Vec<Rva>, ScanError>>;
+
+static PATTERNS: Mutex<PatternCache> = Mutex::new(BTreeMap::new());
+
+/// Like [scan_code] on [Program::current], but cached.
+pub fn find_pattern(pattern: &'static str) -> Result<Vec<Rva>, ScanError> {
+    let program = Program::current();
+    PATTERNS
+        .lock()
+        .unwrap()
+        .entry((program.image().as_ptr() as usize, pattern))
+        .or_insert_with(|| scan_code(program, pattern))
+        .clone()
+}
+
+fn vftables(program: Program) -> HashMap<String, Rva> {
+    find_rtti_classes(&program)
+        .map(|class| (class.name, class.vftable))
+        .collect()
+}
+
+/// Vftable RVAs of the running executable by demangled class name. Walking
+/// the RTTI is about as slow as scanning for a pattern, so it's only done
+/// once for every class.
+static VFTABLES: LazyLock<HashMap<String, Rva>> = LazyLock::new(|| vftables(Program::current()));
+
+/// Returns the RVA of the vftable of the RTTI class named `class`, ex.
+/// `CS::CSEzStateTalkEvent`.
+pub fn find_vftable(class: &str) -> Result<Rva, ScanError> {
+    let vftable = if Program::current_is_fixture() {
+        vftables(Program::current()).get(class).copied()
+    } else {
+        VFTABLES.get(class).copied()
+    };
+    vftable.ok_or_else(|| ScanError::MissingClass(class.to_string()))
+}
+
+/// Returns the RVA of the function at `index` in the vftable of `class`.
+pub fn find_vmt_fn(class: &str, index: u32) -> Result<Rva, ScanError> {
+    let vftable = find_vftable(class)?;
+    let program = Program::current();
+    program
+        .derva::<Va>(vftable + index * size_of::<Va>() as u32)
+        .and_then(|va| program.va_to_rva(*va))
+        .map_err(|_| ScanError::MissingClass(class.to_string()))
+}
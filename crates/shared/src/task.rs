@@ -70,22 +70,35 @@ impl<TTaskData: Send + 'static> Drop for RecurringTaskHandle<TTaskData> {
 pub struct RecurringTask<TTaskData: Send + 'static> {
     vftable: VPtr<dyn SharedTaskBaseVmt, Self>,
     unk8: usize,
-    closure: Box<dyn FnMut(&TTaskData)>,
+    /// Dropped once the task has been cancelled.
+    closure: Option<Box<dyn FnMut(&TTaskData)>>,
+    /// Name the task is profiled under.
+    name: &'static str,
     unregister_requested: AtomicBool,
     self_ref: UnsafeCell<Option<Arc<Self>>>,
 }
 
 impl<TTaskData: Send + 'static> RecurringTask<TTaskData> {
     pub fn new<F: FnMut(&TTaskData) + 'static + Send>(closure: F) -> Self {
+        Self::named(std::any::type_name::<F>(), closure)
+    }
+
+    /// Same as [RecurringTask::new], with the name the task shows up as in
+    /// the `task-profiling` timings.
+    pub fn named<F: FnMut(&TTaskData) + 'static + Send>(name: &'static str, closure: F) -> Self {
         Self {
             vftable: Default::default(),
             unk8: 0,
-            closure: Box::new(closure),
+            closure: Some(Box::new(closure)),
+            name,
             unregister_requested: AtomicBool::new(false),
             self_ref: UnsafeCell::new(None),
         }
     }
 
+    /// Stops the closure from running again. It's dropped the next time the
+    /// game runs the task, along with everything it captured. The task
+    /// itself stays registered with the game.
     pub fn cancel(&self) {
         self.unregister_requested.store(true, Ordering::Relaxed);
     }
@@ -101,21 +114,23 @@ impl<TTaskData: Send + 'static> SharedTaskBaseVmt for RecurringTask<TTaskData> {
     }
 
     extern "C" fn execute(&mut self, data: *const c_void) {
-        // Run the task if cancellation wasn't requested.
-        // if !self.unregister_requested.load(Ordering::Relaxed) {
+        // TODO: implement the games unregister fn to properly get the task removed from the task
+        // pool instead of just not running the closure. Until then the game keeps calling into
+        // the task, so `self_ref` has to keep it alive.
+        if self.unregister_requested.load(Ordering::Relaxed) {
+            self.closure = None;
+        }
+        let Some(closure) = self.closure.as_mut() else {
+            return;
+        };
 
         // SAFETY: We're declaring the type of the data in the first place.
-        (self.closure)(unsafe { &*(data as *const TTaskData) });
-
-        // }
+        let data = unsafe { &*(data as *const TTaskData) };
 
-        // TODO: implement the games unregister fn to properly get the task removed from the task
-        // pool instead of just not running the closure.
-
-        // Drop if we got cancelled in the meanwhile.
-        // if self.unregister_requested.load(Ordering::Relaxed) {
-        //     self.self_ref.get_mut().take();
-        // }
+        #[cfg(feature = "task-profiling")]
+        crate::task_profiler::time_callback(self.name, || closure(data));
+        #[cfg(not(feature = "task-profiling"))]
+        closure(data);
     }
 }
 
@@ -136,3 +151,33 @@ trait SharedTaskBaseVmt {
     // TODO: Make data generic once vtable-rs supports this.
     fn execute(&mut self, data: *const c_void);
 }
+
+#[cfg(test)]
+mod test {
+    use std::ffi::c_void;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::{RecurringTask, SharedTaskBaseVmt};
+
+    #[test]
+    fn cancelled_task_stops_running() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let captured = runs.clone();
+        let mut task = RecurringTask::<u32>::new(move |data| {
+            captured.fetch_add(*data, Ordering::Relaxed);
+        });
+        let data = 1u32;
+        let data = &data as *const u32 as *const c_void;
+
+        task.execute(data);
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+
+        task.cancel();
+        task.execute(data);
+        task.execute(data);
+        assert_eq!(runs.load(Ordering::Relaxed), 1);
+        // The closure and its copy of `runs` are gone.
+        assert_eq!(Arc::strong_count(&runs), 1);
+    }
+}
@@ -0,0 +1,125 @@
+//! Per-callback timing for finding what causes frame spikes, enabled with the
+//! `task-profiling` feature.
+//!
+//! Every task registered through [crate::SharedTaskImpExt::run_recurring] is
+//! timed automatically, under the name given to [RecurringTask::named] or the
+//! type name of its closure otherwise, which includes the crate it comes
+//! from. Hooks aren't registered through this crate, so their detours have to
+//! wrap their body in [time_callback] to show up.
+//!
+//! [RecurringTask::named]: crate::RecurringTask::named
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How many of the most recent calls the percentiles are calculated over.
+pub const SAMPLE_WINDOW: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CallbackTiming {
+    pub name: &'static str,
+    /// Calls since the timings were last reset.
+    pub calls: u64,
+    pub p50: Duration,
+    pub p99: Duration,
+    pub max: Duration,
+}
+
+#[derive(Default)]
+struct Samples {
+    calls: u64,
+    recent: VecDeque<Duration>,
+}
+
+impl Samples {
+    fn add(&mut self, elapsed: Duration) {
+        self.calls += 1;
+        if self.recent.len() == SAMPLE_WINDOW {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(elapsed);
+    }
+
+    fn timing(&self, name: &'static str) -> CallbackTiming {
+        let mut sorted = self.recent.iter().copied().collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        CallbackTiming {
+            name,
+            calls: self.calls,
+            p50: percentile(&sorted, 50),
+            p99: percentile(&sorted, 99),
+            max: sorted.last().copied().unwrap_or_default(),
+        }
+    }
+}
+
+/// Nearest-rank percentile of already sorted samples.
+fn percentile(sorted: &[Duration], percent: usize) -> Duration {
+    if sorted.is_empty() {
+        return Duration::ZERO;
+    }
+    let rank = (sorted.len() * percent).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+static SAMPLES: Mutex<BTreeMap<&'static str, Samples>> = Mutex::new(BTreeMap::new());
+
+/// Runs `callback` and records how long it took under `name`.
+pub fn time_callback<R>(name: &'static str, callback: impl FnOnce() -> R) -> R {
+    let start = Instant::now();
+    let result = callback();
+    let elapsed = start.elapsed();
+
+    SAMPLES
+        .lock()
+        .unwrap()
+        .entry(name)
+        .or_default()
+        .add(elapsed);
+    result
+}
+
+/// The timings of every callback that ran, slowest p99 first.
+pub fn callback_timings() -> Vec<CallbackTiming> {
+    let mut timings = SAMPLES
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(name, samples)| samples.timing(name))
+        .collect::<Vec<_>>();
+    timings.sort_by(|a, b| b.p99.cmp(&a.p99));
+    timings
+}
+
+pub fn reset_callback_timings() {
+    SAMPLES.lock().unwrap().clear();
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{SAMPLE_WINDOW, Samples};
+
+    #[test]
+    fn calculates_percentiles_over_window() {
+        let mut samples = Samples::default();
+        for ms in 1..=100 {
+            samples.add(Duration::from_millis(ms));
+        }
+
+        let timing = samples.timing("task");
+        assert_eq!(timing.calls, 100);
+        assert_eq!(timing.p50, Duration::from_millis(50));
+        assert_eq!(timing.p99, Duration::from_millis(99));
+        assert_eq!(timing.max, Duration::from_millis(100));
+
+        for _ in 0..SAMPLE_WINDOW {
+            samples.add(Duration::from_millis(1));
+        }
+        let timing = samples.timing("task");
+        assert_eq!(timing.calls, 100 + SAMPLE_WINDOW as u64);
+        assert_eq!(timing.max, Duration::from_millis(1));
+    }
+}
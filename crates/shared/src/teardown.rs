@@ -0,0 +1,104 @@
+//! A process-wide registry of cleanup steps to run when a mod gets unloaded
+//! through `FreeLibrary`, ex. while iterating on a mod.
+//!
+//! Steps are grouped into [TeardownStage]s which always run in the same
+//! order: first everything that lets the game reach into mod memory is cut
+//! off, only then is the mod's own machinery unwound. Within a stage steps
+//! run in reverse registration order, so something registered later (which
+//! might depend on something registered earlier) is torn down first.
+//!
+//! Nothing runs automatically. Call [run_teardown] right before the mod's DLL
+//! gets unloaded, which `reloadable_mod!` does for mods loaded by a
+//! `HotReloader`. Don't call it from `DllMain`: `DLL_PROCESS_DETACH` is
+//! handled under the loader lock, where disabling hooks or waiting on the
+//! game's threads can deadlock. When the game exits there's nothing that
+//! needs tearing down.
+use std::sync::Mutex;
+
+/// Order in which registered steps are run, from first to last.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TeardownStage {
+    /// Clearing out pointers to mod memory that were handed to the game,
+    /// e.g. strings, vtables or custom structures stored in game objects.
+    EnginePointers,
+    /// Cancelling tasks registered with the game's task system. Cancelled
+    /// tasks stop running their closure, but stay registered with the game,
+    /// so the code registering them has to stay loaded.
+    Tasks,
+    /// Disabling function hooks.
+    Hooks,
+    /// Reverting patches to the game's code or data.
+    Patches,
+}
+
+type TeardownFn = Box<dyn FnOnce() + Send>;
+
+static REGISTRY: Mutex<Vec<(TeardownStage, &'static str, TeardownFn)>> = Mutex::new(Vec::new());
+
+/// Registers a cleanup step to run during [run_teardown]. The name only
+/// exists to make reports about failing steps readable.
+pub fn register_teardown(
+    stage: TeardownStage,
+    name: &'static str,
+    teardown: impl FnOnce() + Send + 'static,
+) {
+    REGISTRY
+        .lock()
+        .unwrap()
+        .push((stage, name, Box::new(teardown)));
+}
+
+/// Keeps `value` alive until teardown and drops it during `stage`. Useful for
+/// RAII handles like [RecurringTaskHandle](crate::RecurringTaskHandle) that
+/// would otherwise have to be leaked.
+pub fn drop_on_teardown<T: Send + 'static>(stage: TeardownStage, name: &'static str, value: T) {
+    register_teardown(stage, name, move || drop(value));
+}
+
+/// Runs every registered step once, in stage order, and clears the registry.
+/// Returns the names of steps that panicked. As long as panics unwind, a
+/// panicking step doesn't stop the ones after it from running.
+pub fn run_teardown() -> Vec<&'static str> {
+    let mut steps = std::mem::take(&mut *REGISTRY.lock().unwrap());
+
+    // The sort is stable, so reversing first keeps steps within a stage in
+    // reverse registration order.
+    steps.reverse();
+    steps.sort_by_key(|(stage, _, _)| *stage);
+
+    steps
+        .into_iter()
+        .filter_map(|(_, name, teardown)| {
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(teardown))
+                .is_err()
+                .then_some(name)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use super::{TeardownStage, register_teardown, run_teardown};
+
+    #[test]
+    fn runs_in_stage_then_reverse_order() {
+        let order = Arc::new(Mutex::new(Vec::new()));
+        for (stage, name) in [
+            (TeardownStage::Patches, "patch"),
+            (TeardownStage::Tasks, "task 1"),
+            (TeardownStage::EnginePointers, "pointer"),
+            (TeardownStage::Tasks, "task 2"),
+        ] {
+            let order = order.clone();
+            register_teardown(stage, name, move || order.lock().unwrap().push(name));
+        }
+
+        assert!(run_teardown().is_empty());
+        assert_eq!(
+            *order.lock().unwrap(),
+            ["pointer", "task 2", "task 1", "patch"]
+        );
+    }
+}
@@ -17,6 +17,7 @@ use serde::Deserialize;
 enum OutputFormat {
     Print,
     RustStruct,
+    RustScan,
     Rust,
 }
 
@@ -79,6 +80,10 @@ fn main() {
                 print!("{}", generate_rust_struct(&profile));
                 return;
             }
+            if let OutputFormat::RustScan = args.output {
+                print!("{}", generate_rust_scan(&profile));
+                return;
+            }
 
             let results = map_results(
                 &profile,
@@ -93,7 +98,7 @@ fn main() {
             match args.output {
                 OutputFormat::Print => println!("Results: {results:#x?}"),
                 OutputFormat::Rust => println!("{}", generate_rust_instance(&results)),
-                OutputFormat::RustStruct => { /* handled above */ }
+                OutputFormat::RustStruct | OutputFormat::RustScan => { /* handled above */ }
             }
         }
         BinaryMapper::EldenRing(args) => {
@@ -105,6 +110,7 @@ fn main() {
                 .unwrap_or_else(|| game_crate_path("eldenring"));
             let profile = read_profile(er.join("mapper-profile.toml"));
             fs::write(er.join("src/rva/bundle.rs"), generate_rust_struct(&profile)).unwrap();
+            fs::write(er.join("src/rva/scan.rs"), generate_rust_scan(&profile)).unwrap();
             fs::write(
                 er.join("src/rva/rva_ww.rs"),
                 generate_rust_instance(&map_results(&profile, &args.ww_exe)),
@@ -130,6 +136,7 @@ fn main() {
                 generate_rust_struct(&profile),
             )
             .unwrap();
+            fs::write(ds3.join("src/rva/scan.rs"), generate_rust_scan(&profile)).unwrap();
             fs::write(
                 ds3.join("src/rva/rva_data.rs"),
                 generate_rust_instance(&map_results(&profile, &args.exe)),
@@ -240,6 +247,74 @@ fn generate_rust_struct(profile: &MapperProfile) -> String {
     output
 }
 
+/// Generates a file that implements `RvaBundle::scan`, which looks up the
+/// entries of [profile] in the running executable.
+fn generate_rust_scan(profile: &MapperProfile) -> String {
+    let mut output = String::from(
+        "//! Generated runtime lookups of the RVAs.\n\
+                 \n\
+                 // DO NOT EDIT THIS FILE DIRECTLY.\n\
+                 \n",
+    );
+
+    let mut imports = vec!["ScanError"];
+    if !profile.patterns.is_empty() {
+        imports.push("find_pattern");
+    }
+    if profile.vmts.iter().any(|entry| entry.vftable.is_some()) {
+        imports.push("find_vftable");
+    }
+    if profile.vmts.iter().any(|entry| !entry.captures.is_empty()) {
+        imports.push("find_vmt_fn");
+    }
+    writeln!(output, "use shared::scan::{{{}}};\n", imports.join(", ")).unwrap();
+
+    output.push_str(
+        "use super::RvaBundle;\n\
+         \n\
+         impl RvaBundle {\n\
+         /// Looks up every RVA in the current executable, the same way they're\n\
+         /// found by `tools/binary-mapper` using `mapper-profile.toml` in the\n\
+         /// root of this package. This is slow, so it's only done for versions\n\
+         /// without generated RVAs.\n\
+         pub fn scan() -> Result<Self, ScanError> {\n",
+    );
+
+    let mut fields = Vec::new();
+    for (i, entry) in profile.patterns.iter().enumerate() {
+        writeln!(
+            output,
+            "let pattern_{i} = find_pattern({:?})?;",
+            entry.pattern
+        )
+        .unwrap();
+        for (capture, name) in entry.captures.iter().enumerate() {
+            if !name.is_empty() {
+                fields.push((name.clone(), format!("pattern_{i}[{capture}]")));
+            }
+        }
+    }
+    for entry in &profile.vmts {
+        for (name, index) in &entry.captures {
+            fields.push((
+                name.clone(),
+                format!("find_vmt_fn({:?}, {index})?", entry.class),
+            ));
+        }
+        if let Some(name) = &entry.vftable {
+            fields.push((name.clone(), format!("find_vftable({:?})?", entry.class)));
+        }
+    }
+    fields.sort();
+
+    output.push_str("Ok(Self {\n");
+    for (name, value) in fields {
+        writeln!(output, "{name}: {value},").unwrap();
+    }
+    output.push_str("})\n}\n}");
+    output
+}
+
 /// Generates a file that declares an instance of `RvaBundle` with the given
 /// [results].
 fn generate_rust_instance(results: &[MapperEntryResult]) -> String {
@@ -8,6 +8,18 @@ impl DebugDisplay for DLFileDeviceManager {
         ui.input_text("File Device Count", &mut self.devices.len().to_string())
             .read_only(true)
             .build();
+        ui.input_text(
+            "Archive Device Count",
+            &mut self.archive_devices().count().to_string(),
+        )
+        .read_only(true)
+        .build();
+        ui.input_text(
+            "Plain Device Count",
+            &mut self.plain_devices().count().to_string(),
+        )
+        .read_only(true)
+        .build();
 
         if ui.collapsing_header("Virtual Roots", TreeNodeFlags::empty()) {
             ui.indent();
@@ -22,11 +34,11 @@ impl DebugDisplay for DLFileDeviceManager {
                     | TableFlags::ROW_BG
                     | TableFlags::SIZING_STRETCH_PROP,
             ) {
-                self.virtual_roots.items().iter().for_each(|vr| {
+                self.mounts().iter().for_each(|mount| {
                     ui.table_next_column();
-                    ui.text(vr[0].to_string());
+                    ui.text(&mount.root);
                     ui.table_next_column();
-                    ui.text(vr[1].to_string());
+                    ui.text(&mount.target);
                 });
             }
             ui.unindent();
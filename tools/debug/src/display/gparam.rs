@@ -89,5 +89,19 @@ impl DebugDisplay for CSGparamIdLerper {
 }
 
 impl DebugDisplay for CSWorldAreaBlockSceneDrawParam {
-    fn render_debug(&self, _ui: &&mut Ui) {}
+    fn render_debug(&self, ui: &&mut Ui) {
+        ui.text(format!(
+            "Overworld gparam ref settings: {}",
+            self.is_gparam_ref_settings_for_overworld
+        ));
+        ui.text(format!(
+            "Transition to override: {}",
+            self.transition_to_override
+        ));
+        ui.text(format!("Override gparam: {}", self.override_gparam));
+        ui.text(format!(
+            "Override transition duration: {}",
+            self.override_transition_duration
+        ));
+    }
 }
@@ -0,0 +1,105 @@
+//! Browser for the known event flags, with their live values.
+//!
+//! Flags come from the database in
+//! [event_flag_names](eldenring::util::event_flag_names) and can be filtered
+//! by category and by their ID or name. Toggling goes through
+//! [CSEventFlagMan], so it only changes the flag locally, just like the
+//! game's own debug menus.
+use std::sync::Mutex;
+
+use eldenring::cs::CSEventFlagMan;
+use eldenring::util::event_flag_names::{FlagDescription, categories, known_flags};
+use fromsoftware_shared::FromStatic;
+use hudhook::imgui::{TableColumnSetup, TableFlags, Ui};
+
+/// Most flags listed at once, the filters narrow the list down.
+const MAX_LISTED_FLAGS: usize = 500;
+
+struct EventFlagBrowser {
+    filter: String,
+    /// Index into the categories, 0 for all of them.
+    category: usize,
+    only_set: bool,
+    flag_id: i32,
+}
+
+static EVENT_FLAG_BROWSER: Mutex<EventFlagBrowser> = Mutex::new(EventFlagBrowser {
+    filter: String::new(),
+    category: 0,
+    only_set: false,
+    flag_id: 0,
+});
+
+pub fn render_event_flag_browser(ui: &&mut Ui) {
+    let Ok(event_flag_man) = (unsafe { CSEventFlagMan::instance() }) else {
+        ui.text("CSEventFlagMan isn't available");
+        return;
+    };
+    let flags = &mut event_flag_man.virtual_memory_flag;
+    let mut browser = EVENT_FLAG_BROWSER.lock().unwrap();
+
+    // Flags that aren't in the database can still be toggled by ID.
+    ui.input_int("Flag ID", &mut browser.flag_id).build();
+    let flag_id = browser.flag_id.max(0) as u32;
+    let mut state = flags.get_flag(flag_id);
+    ui.same_line();
+    if ui.checkbox("Set", &mut state) {
+        flags.set_flag(flag_id, state);
+    }
+    ui.separator();
+
+    let mut category_names = vec!["All"];
+    category_names.extend(categories());
+    ui.combo_simple_string("Category", &mut browser.category, &category_names);
+    ui.input_text("Search", &mut browser.filter).build();
+    ui.checkbox("Only set flags", &mut browser.only_set);
+
+    let category = (browser.category > 0).then(|| category_names[browser.category]);
+    let filter = browser.filter.to_lowercase();
+    let mut listed = known_flags()
+        .filter(|(id, description)| {
+            category.is_none_or(|c| c == description.category)
+                && matches_filter(*id, description, &filter)
+        })
+        .filter(|(id, _)| !browser.only_set || flags.get_flag(*id))
+        .collect::<Vec<_>>();
+    listed.sort_unstable_by_key(|(id, _)| *id);
+
+    ui.text(format!("{} flags", listed.len()));
+    let Some(_t) = ui.begin_table_header_with_flags(
+        "event-flag-browser",
+        [
+            TableColumnSetup::new("ID"),
+            TableColumnSetup::new("Category"),
+            TableColumnSetup::new("Name"),
+            TableColumnSetup::new("Set"),
+        ],
+        TableFlags::RESIZABLE
+            | TableFlags::BORDERS
+            | TableFlags::ROW_BG
+            | TableFlags::SIZING_STRETCH_PROP,
+    ) else {
+        return;
+    };
+
+    for (id, description) in listed.into_iter().take(MAX_LISTED_FLAGS) {
+        ui.table_next_column();
+        ui.text(id.to_string());
+        ui.table_next_column();
+        ui.text(description.category);
+        ui.table_next_column();
+        ui.text(description.name);
+        ui.table_next_column();
+
+        let mut state = flags.get_flag(id);
+        if ui.checkbox(format!("##{id}"), &mut state) {
+            flags.set_flag(id, state);
+        }
+    }
+}
+
+fn matches_filter(id: u32, description: &FlagDescription, filter: &str) -> bool {
+    filter.is_empty()
+        || id.to_string().contains(filter)
+        || description.name.to_lowercase().contains(filter)
+}
@@ -0,0 +1,298 @@
+//! Field-by-field inspector for singletons and the character under the
+//! crosshair.
+//!
+//! The DLRF reflection data only names classes, their fields aren't described
+//! by anything mapped. Fields are added by hand instead, as an offset into
+//! the object and a primitive type, and are read and edited live. Offsets
+//! are limited to the size of the struct the bindings define for the object,
+//! so nothing outside of it is touched.
+//!
+//! The inspector's state lives in a static, so a live reload clears the
+//! fields that were added.
+use std::mem::size_of;
+use std::ptr::NonNull;
+use std::sync::Mutex;
+
+use eldenring::cs::{
+    CSCamera, CSEventFlagMan, CSFade, CSGaitemImp, CSNetMan, CSSessionManager, CSWindowImp,
+    CSWorldGeomMan, ChrIns, FieldInsHandle, WorldAreaTime, WorldChrMan,
+};
+use eldenring::fd4::FD4ParamRepository;
+use eldenring::util::picking::pick_under_crosshair;
+use fromsoftware_shared::FromStatic;
+use hudhook::imgui::{TableColumnSetup, TableFlags, Ui};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum FieldKind {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    F32,
+    Bool,
+    Pointer,
+}
+
+impl FieldKind {
+    const ALL: [FieldKind; 10] = [
+        FieldKind::U8,
+        FieldKind::I8,
+        FieldKind::U16,
+        FieldKind::I16,
+        FieldKind::U32,
+        FieldKind::I32,
+        FieldKind::U64,
+        FieldKind::F32,
+        FieldKind::Bool,
+        FieldKind::Pointer,
+    ];
+
+    const NAMES: [&str; 10] = [
+        "u8", "i8", "u16", "i16", "u32", "i32", "u64", "f32", "bool", "pointer",
+    ];
+
+    fn name(&self) -> &'static str {
+        Self::NAMES[Self::ALL.iter().position(|k| k == self).unwrap()]
+    }
+
+    fn size(&self) -> usize {
+        match self {
+            FieldKind::U8 | FieldKind::I8 | FieldKind::Bool => 1,
+            FieldKind::U16 | FieldKind::I16 => 2,
+            FieldKind::U32 | FieldKind::I32 | FieldKind::F32 => 4,
+            FieldKind::U64 | FieldKind::Pointer => 8,
+        }
+    }
+}
+
+struct InspectorField {
+    name: String,
+    offset: usize,
+    kind: FieldKind,
+}
+
+/// An object fields can be read from: its address and the size of its
+/// struct.
+type Resolver = fn(&Inspector) -> Option<(NonNull<u8>, usize)>;
+
+fn singleton<T: FromStatic>(_: &Inspector) -> Option<(NonNull<u8>, usize)> {
+    let instance = unsafe { T::instance() }.ok()?;
+    Some((NonNull::from(instance).cast(), size_of::<T>()))
+}
+
+fn picked_character(inspector: &Inspector) -> Option<(NonNull<u8>, usize)> {
+    let handle = inspector.picked?;
+    let world_chr_man = unsafe { WorldChrMan::instance() }.ok()?;
+    let chr_ins = world_chr_man.chr_ins_by_handle(&handle)?;
+    Some((NonNull::from(chr_ins).cast(), size_of::<ChrIns>()))
+}
+
+const TARGETS: &[(&str, Resolver)] = &[
+    ("Picked character (ChrIns)", picked_character),
+    ("CSCamera", singleton::<CSCamera>),
+    ("CSEventFlagMan", singleton::<CSEventFlagMan>),
+    ("CSFade", singleton::<CSFade>),
+    ("CSGaitemImp", singleton::<CSGaitemImp>),
+    ("CSNetMan", singleton::<CSNetMan>),
+    ("CSSessionManager", singleton::<CSSessionManager>),
+    ("CSWindowImp", singleton::<CSWindowImp>),
+    ("CSWorldGeomMan", singleton::<CSWorldGeomMan>),
+    ("FD4ParamRepository", singleton::<FD4ParamRepository>),
+    ("WorldAreaTime", singleton::<WorldAreaTime>),
+    ("WorldChrMan", singleton::<WorldChrMan>),
+];
+
+/// Bytes shown in the hex view.
+const HEX_VIEW_LEN: usize = 0x100;
+
+struct Inspector {
+    target: usize,
+    picked: Option<FieldInsHandle>,
+    /// Fields added per target.
+    fields: Vec<Vec<InspectorField>>,
+    new_name: String,
+    new_offset: String,
+    new_kind: usize,
+    hex_offset: i32,
+}
+
+static INSPECTOR: Mutex<Option<Inspector>> = Mutex::new(None);
+
+pub fn render_inspector(ui: &&mut Ui) {
+    let mut inspector = INSPECTOR.lock().unwrap();
+    let inspector = inspector.get_or_insert_with(|| Inspector {
+        target: 0,
+        picked: None,
+        fields: TARGETS.iter().map(|_| Vec::new()).collect(),
+        new_name: String::new(),
+        new_offset: String::new(),
+        new_kind: 0,
+        hex_offset: 0,
+    });
+
+    let names = TARGETS.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+    ui.combo_simple_string("Target", &mut inspector.target, &names);
+
+    if ui.button("Pick under crosshair") {
+        match pick_under_crosshair() {
+            Ok(picked) => inspector.picked = picked.map(|p| p.handle),
+            Err(e) => tracing::error!("Couldn't pick: {e:?}"),
+        }
+    }
+    match inspector.picked {
+        Some(handle) => ui.text(format!("Picked: {handle}")),
+        None => ui.text("Nothing picked"),
+    }
+    ui.separator();
+
+    let Some((base, size)) = (TARGETS[inspector.target].1)(inspector) else {
+        ui.text("Target isn't available");
+        return;
+    };
+    ui.text(format!(
+        "Address: {:#x}, size: {size:#x}",
+        base.as_ptr() as usize
+    ));
+
+    render_fields(ui, inspector, base, size);
+    ui.separator();
+    render_new_field(ui, inspector, size);
+    ui.separator();
+    render_hex_view(ui, inspector, base, size);
+}
+
+fn render_fields(ui: &&mut Ui, inspector: &mut Inspector, base: NonNull<u8>, size: usize) {
+    let Some(_t) = ui.begin_table_header_with_flags(
+        "inspector-fields",
+        [
+            TableColumnSetup::new("Name"),
+            TableColumnSetup::new("Offset"),
+            TableColumnSetup::new("Type"),
+            TableColumnSetup::new("Value"),
+            TableColumnSetup::new(""),
+        ],
+        TableFlags::RESIZABLE
+            | TableFlags::BORDERS
+            | TableFlags::ROW_BG
+            | TableFlags::SIZING_STRETCH_PROP,
+    ) else {
+        return;
+    };
+
+    let mut removed = None;
+    for (i, field) in inspector.fields[inspector.target].iter().enumerate() {
+        let _id = ui.push_id_usize(i);
+        ui.table_next_column();
+        ui.text(&field.name);
+        ui.table_next_column();
+        ui.text(format!("{:#x}", field.offset));
+        ui.table_next_column();
+        ui.text(field.kind.name());
+        ui.table_next_column();
+
+        if field.offset + field.kind.size() > size {
+            ui.text("Out of bounds");
+        } else {
+            // SAFETY: the field is within the target's struct.
+            let ptr = unsafe { base.as_ptr().add(field.offset) };
+            unsafe { render_value(ui, ptr, field.kind) };
+        }
+
+        ui.table_next_column();
+        if ui.small_button("Remove") {
+            removed = Some(i);
+        }
+    }
+
+    if let Some(i) = removed {
+        inspector.fields[inspector.target].remove(i);
+    }
+}
+
+/// Shows a field's value and writes it back once edited.
+///
+/// # Safety
+/// `ptr` has to point to `kind.size()` readable and writable bytes.
+unsafe fn render_value(ui: &&mut Ui, ptr: *mut u8, kind: FieldKind) {
+    macro_rules! scalar {
+        ($t:ty) => {{
+            let mut value = unsafe { (ptr as *const $t).read_unaligned() };
+            if ui
+                .input_scalar("##value", &mut value)
+                .enter_returns_true(true)
+                .build()
+            {
+                unsafe { (ptr as *mut $t).write_unaligned(value) };
+            }
+        }};
+    }
+
+    match kind {
+        FieldKind::U8 => scalar!(u8),
+        FieldKind::I8 => scalar!(i8),
+        FieldKind::U16 => scalar!(u16),
+        FieldKind::I16 => scalar!(i16),
+        FieldKind::U32 => scalar!(u32),
+        FieldKind::I32 => scalar!(i32),
+        FieldKind::U64 => scalar!(u64),
+        FieldKind::F32 => scalar!(f32),
+        FieldKind::Bool => {
+            let mut value = unsafe { ptr.read() } != 0;
+            if ui.checkbox("##value", &mut value) {
+                unsafe { ptr.write(value as u8) };
+            }
+        }
+        // Pointers are only shown, writing them is too easy to get wrong.
+        FieldKind::Pointer => {
+            let value = unsafe { (ptr as *const usize).read_unaligned() };
+            ui.text(format!("{value:#x}"));
+        }
+    }
+}
+
+fn render_new_field(ui: &&mut Ui, inspector: &mut Inspector, size: usize) {
+    ui.input_text("Name", &mut inspector.new_name).build();
+    ui.input_text("Offset", &mut inspector.new_offset)
+        .chars_hexadecimal(true)
+        .build();
+    ui.combo_simple_string("Type", &mut inspector.new_kind, &FieldKind::NAMES);
+
+    if ui.button("Add field") {
+        let offset = usize::from_str_radix(inspector.new_offset.trim_start_matches("0x"), 16);
+        let kind = FieldKind::ALL[inspector.new_kind];
+        match offset {
+            Ok(offset) if offset + kind.size() <= size => {
+                inspector.fields[inspector.target].push(InspectorField {
+                    name: std::mem::take(&mut inspector.new_name),
+                    offset,
+                    kind,
+                });
+                inspector.new_offset.clear();
+            }
+            Ok(offset) => tracing::warn!("Offset {offset:#x} is past the end of the struct"),
+            Err(e) => tracing::warn!("Invalid offset {:?}: {e}", inspector.new_offset),
+        }
+    }
+}
+
+fn render_hex_view(ui: &&mut Ui, inspector: &mut Inspector, base: NonNull<u8>, size: usize) {
+    ui.input_int("Hex view offset", &mut inspector.hex_offset)
+        .step(0x10)
+        .build();
+    let start = (inspector.hex_offset.max(0) as usize).min(size) & !0xf;
+    let end = (start + HEX_VIEW_LEN).min(size);
+
+    // SAFETY: the range is within the target's struct.
+    let bytes = unsafe { std::slice::from_raw_parts(base.as_ptr().add(start), end - start) };
+    for (i, line) in bytes.chunks(0x10).enumerate() {
+        let hex = line
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        ui.text(format!("{:#06x}: {hex}", start + i * 0x10));
+    }
+}
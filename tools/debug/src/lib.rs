@@ -24,6 +24,8 @@ use eldenring::cs::FieldArea;
 use eldenring::cs::WorldAreaTime;
 use eldenring::cs::WorldChrMan;
 use eldenring::fd4::FD4ParamRepository;
+use eldenring::util::camera::world_to_screen;
+use eldenring::util::debug_draw::take_world_text;
 use eldenring::util::system::wait_for_system_init;
 
 use fromsoftware_shared::program::Program;
@@ -42,11 +44,17 @@ use hudhook::windows::Win32::Foundation::HINSTANCE;
 use pelite::pe64::Pe;
 
 use display::render_debug_singleton;
+use event_flag_browser::render_event_flag_browser;
+use inspector::render_inspector;
+use param_editor::render_param_editor;
 use rva::RVA_GLOBAL_FIELD_AREA;
 use tracing_panic::panic_hook;
 use windows::Win32::System::SystemServices::DLL_PROCESS_ATTACH;
 
 mod display;
+mod event_flag_browser;
+mod inspector;
+mod param_editor;
 mod rva;
 
 /// # Safety
@@ -127,6 +135,19 @@ impl ImguiRenderLoop for EldenRingDebugGui {
         unsafe {
             render_live_reload(self.size, self.scale, ui);
         }
+
+        // Text queued through `debug_draw::draw_text_3d` this frame.
+        let draw_list = ui.get_background_draw_list();
+        for text in take_world_text() {
+            if let Some((screen_x, screen_y)) = world_to_screen(text.position.into()) {
+                let color = text.color;
+                draw_list.add_text(
+                    [screen_x, screen_y],
+                    [color.0, color.1, color.2, color.3],
+                    &text.text,
+                );
+            }
+        }
     }
 }
 
@@ -196,6 +217,18 @@ unsafe fn render_live_reload(gui_size: [f32; 2], gui_scale: f32, ui: &mut Ui) {
                 render_debug_singleton::<CSFeManImp>(&ui);
                 item.end();
             }
+            if let Some(item) = ui.tab_item("Event Flags") {
+                render_event_flag_browser(&ui);
+                item.end();
+            }
+            if let Some(item) = ui.tab_item("Params") {
+                render_param_editor(&ui);
+                item.end();
+            }
+            if let Some(item) = ui.tab_item("Inspector") {
+                render_inspector(&ui);
+                item.end();
+            }
             if let Some(item) = ui.tab_item("Eject") {
                 if ui.button("Eject") {
                     eject();
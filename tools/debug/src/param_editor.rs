@@ -0,0 +1,272 @@
+//! Browser and live editor for the loaded params.
+//!
+//! Field layouts and row names come from a Paramdex checkout next to the
+//! game, `paramdex/Defs/<ParamType>.xml` and `paramdex/Names/<Param>.txt`.
+//! Params without a paramdef can still be browsed by row ID. Edits go
+//! through a [ParamPatcher] so they can be undone.
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use eldenring::fd4::FD4ParamRepository;
+use eldenring::formats::{ParamDefinition, ParamFieldType, ParamValue};
+use eldenring::util::param_patch::ParamPatcher;
+use fromsoftware_shared::FromStatic;
+use hudhook::imgui::{TableColumnSetup, TableFlags, Ui};
+
+const PARAMDEX_DIR: &str = "paramdex";
+
+/// Most rows listed at once, the search narrows the list down.
+const MAX_LISTED_ROWS: usize = 200;
+
+#[derive(Default)]
+struct ParamEditor {
+    patcher: ParamPatcher,
+    param_filter: String,
+    row_filter: String,
+    selected_param: Option<String>,
+    selected_row: Option<u32>,
+    /// Paramdefs by param type, None if there's no file for it.
+    defs: HashMap<String, Option<ParamDefinition>>,
+    /// Row names by param name.
+    names: HashMap<String, HashMap<u32, String>>,
+    /// Text being edited per field.
+    edits: HashMap<String, String>,
+}
+
+impl ParamEditor {
+    fn def(&mut self, param_type: &str) -> Option<&ParamDefinition> {
+        self.defs
+            .entry(param_type.to_string())
+            .or_insert_with(|| {
+                let path = format!("{PARAMDEX_DIR}/Defs/{param_type}.xml");
+                let xml = std::fs::read_to_string(path).ok()?;
+                ParamDefinition::from_xml(&xml)
+                    .inspect_err(|e| tracing::warn!("Couldn't parse paramdef {param_type}: {e}"))
+                    .ok()
+            })
+            .as_ref()
+    }
+
+    fn names(&mut self, param_name: &str) -> &HashMap<u32, String> {
+        self.names.entry(param_name.to_string()).or_insert_with(|| {
+            let path = format!("{PARAMDEX_DIR}/Names/{param_name}.txt");
+            std::fs::read_to_string(path)
+                .unwrap_or_default()
+                .lines()
+                .filter_map(|line| {
+                    let (id, name) = line.split_once(' ')?;
+                    Some((id.trim().parse().ok()?, name.trim().to_string()))
+                })
+                .collect()
+        })
+    }
+}
+
+static PARAM_EDITOR: Mutex<Option<ParamEditor>> = Mutex::new(None);
+
+pub fn render_param_editor(ui: &&mut Ui) {
+    let Ok(params) = (unsafe { FD4ParamRepository::instance() }) else {
+        ui.text("FD4ParamRepository isn't available");
+        return;
+    };
+
+    let mut editor = PARAM_EDITOR.lock().unwrap();
+    let editor = editor.get_or_insert_with(ParamEditor::default);
+
+    ui.text(format!("Edits: {}", editor.patcher.history().len()));
+    ui.same_line();
+    if ui.button("Undo")
+        && let Err(e) = editor.patcher.undo()
+    {
+        tracing::error!("Couldn't undo param edit: {e}");
+    }
+    ui.same_line();
+    if ui.button("Revert all")
+        && let Err(e) = editor.patcher.revert_all()
+    {
+        tracing::error!("Couldn't revert param edits: {e}");
+    }
+    ui.separator();
+
+    ui.input_text("Param", &mut editor.param_filter).build();
+    let param_filter = editor.param_filter.to_lowercase();
+    let mut param_names = params
+        .res_rep
+        .res_cap_holder
+        .entries()
+        .map(|e| e.inner.name.to_string())
+        .filter(|name| name.to_lowercase().contains(&param_filter))
+        .collect::<Vec<_>>();
+    param_names.sort();
+
+    ui.child_window("param-list").size([0.0, 120.0]).build(|| {
+        for name in param_names {
+            let selected = editor.selected_param.as_ref() == Some(&name);
+            if ui.selectable_config(&name).selected(selected).build() {
+                editor.selected_param = Some(name);
+                editor.selected_row = None;
+                editor.edits.clear();
+            }
+        }
+    });
+
+    let Some(param_name) = editor.selected_param.clone() else {
+        return;
+    };
+    let Some(res_cap) = params
+        .res_rep
+        .res_cap_holder
+        .entries()
+        .find(|e| e.inner.name.to_string() == param_name)
+    else {
+        ui.text(format!("{param_name} isn't loaded"));
+        return;
+    };
+    let table = match res_cap.table() {
+        Ok(table) => table,
+        Err(e) => {
+            ui.text(format!("Couldn't read {param_name}: {e}"));
+            return;
+        }
+    };
+    ui.separator();
+
+    ui.input_text("Row ID or name", &mut editor.row_filter)
+        .build();
+    let row_filter = editor.row_filter.to_lowercase();
+    let names = editor.names(&param_name);
+    let rows = table
+        .ids()
+        .filter_map(|id| {
+            let name = names.get(&id);
+            let matches = id.to_string().contains(&row_filter)
+                || name.is_some_and(|name| name.to_lowercase().contains(&row_filter));
+            matches.then(|| match name {
+                Some(name) => (id, format!("{id} {name}")),
+                None => (id, id.to_string()),
+            })
+        })
+        .take(MAX_LISTED_ROWS)
+        .collect::<Vec<_>>();
+
+    ui.child_window("row-list").size([0.0, 120.0]).build(|| {
+        for (id, label) in rows {
+            let selected = editor.selected_row == Some(id);
+            if ui.selectable_config(&label).selected(selected).build() {
+                editor.selected_row = Some(id);
+                editor.edits.clear();
+            }
+        }
+    });
+
+    let Some(row_id) = editor.selected_row else {
+        return;
+    };
+    let Ok(Some(row)) = table.row(row_id) else {
+        ui.text(format!("{param_name} has no row {row_id}"));
+        return;
+    };
+    let row = row.to_vec();
+    ui.separator();
+
+    let Some(def) = editor.def(table.name()).cloned() else {
+        ui.text(format!(
+            "No paramdef for {}, showing raw bytes",
+            table.name()
+        ));
+        for (i, line) in row.chunks(0x10).enumerate() {
+            let hex = line
+                .iter()
+                .map(|b| format!("{b:02x}"))
+                .collect::<Vec<_>>()
+                .join(" ");
+            ui.text(format!("{:#06x}: {hex}", i * 0x10));
+        }
+        return;
+    };
+
+    let Some(_t) = ui.begin_table_header_with_flags(
+        "param-editor-fields",
+        [
+            TableColumnSetup::new("Field"),
+            TableColumnSetup::new("Type"),
+            TableColumnSetup::new("Value"),
+        ],
+        TableFlags::RESIZABLE
+            | TableFlags::BORDERS
+            | TableFlags::ROW_BG
+            | TableFlags::SIZING_STRETCH_PROP,
+    ) else {
+        return;
+    };
+
+    for field in def
+        .fields
+        .iter()
+        .filter(|f| f.field_type != ParamFieldType::Dummy8)
+    {
+        let Some(value) = field.read(&row) else {
+            continue;
+        };
+
+        ui.table_next_column();
+        ui.text(&field.name);
+        ui.table_next_column();
+        ui.text(format!("{:?}", field.field_type));
+        ui.table_next_column();
+
+        let editable = matches!(
+            value,
+            ParamValue::Signed(_) | ParamValue::Unsigned(_) | ParamValue::Float(_)
+        );
+        let text = editor
+            .edits
+            .entry(field.name.clone())
+            .or_insert_with(|| display_value(&value));
+        let label = format!("##{}", field.name);
+        let committed = ui
+            .input_text(&label, text)
+            .read_only(!editable)
+            .enter_returns_true(true)
+            .build();
+
+        if committed && editable {
+            let Some(new_value) = parse_value(&value, text) else {
+                tracing::warn!("Invalid value {text:?} for {}", field.name);
+                continue;
+            };
+            if let Err(e) = editor
+                .patcher
+                .write_field(&param_name, row_id, field, &new_value)
+            {
+                tracing::error!("Couldn't edit param: {e}");
+            }
+            editor.edits.remove(&field.name);
+        }
+    }
+}
+
+fn display_value(value: &ParamValue) -> String {
+    match value {
+        ParamValue::Signed(v) => v.to_string(),
+        ParamValue::Unsigned(v) => v.to_string(),
+        ParamValue::Float(v) => v.to_string(),
+        ParamValue::String(v) => v.clone(),
+        ParamValue::Array(values) => values
+            .iter()
+            .map(display_value)
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+/// Parses edited text as the same kind of value as the field's current one.
+fn parse_value(current: &ParamValue, text: &str) -> Option<ParamValue> {
+    let text = text.trim();
+    match current {
+        ParamValue::Signed(_) => text.parse().ok().map(ParamValue::Signed),
+        ParamValue::Unsigned(_) => text.parse().ok().map(ParamValue::Unsigned),
+        ParamValue::Float(_) => text.parse().ok().map(ParamValue::Float),
+        _ => None,
+    }
+}
@@ -100,16 +100,6 @@ fn load_definitions(input_path: impl AsRef<Path>) -> io::Result<Vec<StructDef>>
 fn generate_code(def: &StructDef) -> String {
     let mut code = String::new();
 
-    // Group the bitfields
-    let mut bitfield_groups: HashMap<usize, Vec<(&LayoutUnit, u8)>> = HashMap::new();
-    for unit in def.layout.iter() {
-        if let FieldType::Bitfield(_) = unit.field_type {
-            bitfield_groups
-                .entry(unit.offset)
-                .or_default()
-                .push((unit, unit.size as u8));
-        }
-    }
     let mut grouped_names = HashMap::new();
 
     code.push_str("#[derive(Debug, Clone)]\n");
@@ -119,14 +109,18 @@ fn generate_code(def: &StructDef) -> String {
     code.push_str(&format!("pub struct {} {{\n", def.name));
     for unit in def.layout.iter() {
         match &unit.field_type {
-            FieldType::Bitfield(_) => {
+            FieldType::Bitfield(_, _) => {
                 if grouped_names.contains_key(&unit.offset) {
                     continue;
                 }
 
                 let group_name = format!("bits_{:x}", &unit.offset);
 
-                code.push_str(&format!("    {}: u8,\n", &group_name));
+                code.push_str(&format!(
+                    "    {}: {},\n",
+                    &group_name,
+                    unit.field_type.native_type()
+                ));
 
                 grouped_names.insert(unit.offset, group_name);
             }
@@ -165,24 +159,22 @@ fn generate_code(def: &StructDef) -> String {
 
         let normalized_name = normalize_name(&unit.name);
         match &unit.field_type {
-            FieldType::Bitfield(bits) => {
+            FieldType::Bitfield(_, bits) => {
                 let group_name = &grouped_names[&unit.offset];
-                let used_bits: u8 = bitfield_groups[&unit.offset]
-                    .iter()
-                    .take_while(|(u, _)| u.name != unit.name)
-                    .map(|(_, b)| *b)
-                    .sum();
+                let used_bits = unit.bit_offset;
+                let ty = unit.field_type.native_type();
+                let width = unit.size * 8;
 
-                let mask = (1 << bits) - 1;
+                let mask = (1u64 << bits) - 1;
 
                 code.push_str("    #[allow(clippy::identity_op)]\n");
                 code.push_str(&format!(
-                    "    pub fn {normalized_name}(&self) -> u8 {{\n        (self.{group_name} >> {used_bits}) & 0b{mask:08b}\n    }}\n\n"
+                    "    pub fn {normalized_name}(&self) -> {ty} {{\n        (self.{group_name} >> {used_bits}) & 0b{mask:0width$b}\n    }}\n\n"
                 ));
 
                 code.push_str("    #[allow(clippy::identity_op)]\n");
                 code.push_str(&format!(
-                    "    pub fn set_{normalized_name}(&mut self, value: u8) {{\n        self.{group_name} = (self.{group_name} & !(0b{mask:08b} << {used_bits})) | ((value & 0b{mask:08b}) << {used_bits});\n    }}\n\n"
+                    "    pub fn set_{normalized_name}(&mut self, value: {ty}) {{\n        self.{group_name} = (self.{group_name} & !(0b{mask:0width$b} << {used_bits})) | ((value & 0b{mask:0width$b}) << {used_bits});\n    }}\n\n"
                 ));
             }
             FieldType::Standard(_) => {
@@ -238,44 +230,47 @@ fn build_definition(parsed: &ParamDef) -> StructDef {
 fn layout_struct(fields: &[LayoutField]) -> Vec<LayoutUnit> {
     let mut offset = 0;
     let mut layout = Vec::new();
-    let mut bit_cursor: Option<(usize, u8)> = None;
+    // Offset and size of the storage unit being filled with bitfields, and
+    // how many of its bits are used.
+    let mut bit_cursor: Option<(usize, usize, u8)> = None;
 
     for field in fields {
         let (alignment, size) = field.field_type.alignment_and_size();
 
         match &field.field_type {
-            FieldType::Bitfield(bits) => {
-                // Either get the current bit cursor or start a new one
-                let (byte_offset, used_bits) = bit_cursor.unwrap_or((offset, 0));
-
-                // Wrap around the 8 bit boundaries.
-                if used_bits + bits > 8 {
-                    offset += 1;
-                    bit_cursor = Some((offset, *bits));
-                } else {
-                    bit_cursor = Some((byte_offset, used_bits + bits));
-                }
+            FieldType::Bitfield(_, bits) => {
+                // Like MSVC, consecutive bitfields share a storage unit as long
+                // as their declared types have the same size and there are
+                // bits left, otherwise a new unit of the declared type starts.
+                let (unit_offset, used_bits) = match bit_cursor {
+                    Some((unit_offset, unit_size, used_bits))
+                        if unit_size == size && used_bits as usize + *bits as usize <= size * 8 =>
+                    {
+                        (unit_offset, used_bits)
+                    }
+                    Some((unit_offset, unit_size, _)) => {
+                        offset = align_offset(unit_offset + unit_size, alignment);
+                        (offset, 0)
+                    }
+                    None => {
+                        offset = align_offset(offset, alignment);
+                        (offset, 0)
+                    }
+                };
+                bit_cursor = Some((unit_offset, size, used_bits + bits));
 
                 layout.push(LayoutUnit {
                     name: field.name.clone(),
-                    offset,
+                    offset: unit_offset,
                     size,
+                    bit_offset: used_bits,
                     field_type: field.field_type.clone(),
                 });
-
-                // Yeet bit cursor and advance offset if we get to the end of a byte.
-                if let Some((_, used)) = bit_cursor
-                    && used == 8
-                {
-                    offset += 1;
-                    bit_cursor = None;
-                }
             }
             FieldType::Standard(_) | FieldType::Array(_, _) => {
-                // Clean bit cursor if we're in the middle of a byte and need to enforce alignment.
-                if bit_cursor.is_some() {
-                    offset += 1;
-                    bit_cursor = None;
+                // Close the storage unit of any bitfields before this field.
+                if let Some((unit_offset, unit_size, _)) = bit_cursor.take() {
+                    offset = unit_offset + unit_size;
                 }
 
                 // Align to current types alignment.
@@ -284,6 +279,7 @@ fn layout_struct(fields: &[LayoutField]) -> Vec<LayoutUnit> {
                     name: field.name.clone(),
                     offset,
                     size,
+                    bit_offset: 0,
                     field_type: field.field_type.clone(),
                 });
                 offset += size;
@@ -334,7 +330,7 @@ fn parse_field(field: &FieldDef) -> Option<LayoutField> {
         (None, Some(array_size)) => {
             FieldType::Array(Box::new(FieldType::Standard(orig_type)), array_size)
         }
-        (Some(bit_width), None) => FieldType::Bitfield(bit_width as u8),
+        (Some(bit_width), None) => FieldType::Bitfield(orig_type, bit_width as u8),
         (Some(_), Some(_)) => unimplemented!(),
     };
 
@@ -417,7 +413,8 @@ struct FieldDef {
 
 #[derive(Clone, Debug, PartialEq)]
 enum FieldType {
-    Bitfield(u8),
+    /// The declared type and the width in bits.
+    Bitfield(String, u8),
     Standard(String),
     Array(Box<FieldType>, usize),
 }
@@ -425,8 +422,7 @@ enum FieldType {
 impl FieldType {
     fn alignment_and_size(&self) -> (usize, usize) {
         match self {
-            FieldType::Bitfield(_) => (1, 1),
-            FieldType::Standard(ty) => match ty.as_str() {
+            FieldType::Standard(ty) | FieldType::Bitfield(ty, _) => match ty.as_str() {
                 "u8" | "s8" | "dummy8" | "fixstr" => (1, 1),
                 "u16" | "s16" | "fixstrW" => (2, 2),
                 "u32" | "s32" | "f32" => (4, 4),
@@ -441,7 +437,7 @@ impl FieldType {
 
     fn native_type(&self) -> &str {
         match self {
-            FieldType::Standard(ty) => match ty.as_str() {
+            FieldType::Standard(ty) | FieldType::Bitfield(ty, _) => match ty.as_str() {
                 "u8" | "dummy8" | "fixstr" => "u8",
                 "s8" => "i8",
                 "u16" | "fixstrW" => "u16",
@@ -473,7 +469,10 @@ struct LayoutField {
 struct LayoutUnit {
     name: String,
     offset: usize,
+    /// For bitfields, the size of their storage unit.
     size: usize,
+    /// For bitfields, the position of their lowest bit in the storage unit.
+    bit_offset: u8,
     field_type: FieldType,
 }
 